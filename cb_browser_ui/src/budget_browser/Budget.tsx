@@ -0,0 +1,46 @@
+import * as React from 'react';
+import { InputNumber } from 'antd';
+
+export type TaxRates = {
+    property_per_square_meter_and_day: number,
+    income: number,
+    sales: number,
+};
+
+export const initialState = {
+    taxRates: null as TaxRates | null
+};
+
+const PERCENT = {
+    formatter: value => `${value}%`,
+    parser: value => value.replace('%', '')
+};
+
+export function TaxRatesEditor(props: { state }) {
+    const taxRates: TaxRates | null = props.state.budget.taxRates;
+
+    if (!taxRates) {
+        return <p>Loading tax rates...</p>;
+    }
+
+    const setRate = (rate: keyof TaxRates, value: number | undefined) => {
+        if (value !== undefined && !isNaN(value)) {
+            cbRustBrowser.set_tax_rates({ ...taxRates, [rate]: value });
+        }
+    };
+
+    return <div className="tax-rates">
+        <p>Property tax per m² of lot and day{" "}
+            <InputNumber value={taxRates.property_per_square_meter_and_day} min={0} step={0.001}
+                onChange={value => setRate("property_per_square_meter_and_day", value)} />
+        </p>
+        <p>Income tax{" "}
+            <InputNumber value={Math.round(taxRates.income * 1000) / 10} min={0} max={100} step={1} {...PERCENT}
+                onChange={value => setRate("income", value / 100)} />
+        </p>
+        <p>Sales tax{" "}
+            <InputNumber value={Math.round(taxRates.sales * 1000) / 10} min={0} max={100} step={1} {...PERCENT}
+                onChange={value => setRate("sales", value / 100)} />
+        </p>
+    </div>;
+}
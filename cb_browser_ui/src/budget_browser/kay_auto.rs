@@ -0,0 +1,85 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for BrowserBudgetUI {
+    type ID = BrowserBudgetUIID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct BrowserBudgetUIID {
+    _raw_id: RawID
+}
+
+impl Copy for BrowserBudgetUIID {}
+impl Clone for BrowserBudgetUIID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for BrowserBudgetUIID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "BrowserBudgetUIID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for BrowserBudgetUIID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for BrowserBudgetUIID {
+    fn eq(&self, other: &BrowserBudgetUIID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for BrowserBudgetUIID {}
+
+impl TypedID for BrowserBudgetUIID {
+    type Target = BrowserBudgetUI;
+
+    fn from_raw(id: RawID) -> Self {
+        BrowserBudgetUIID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl BrowserBudgetUIID {
+    pub fn spawn(world: &mut World) -> Self {
+        let id = BrowserBudgetUIID::from_raw(world.allocate_instance_id::<BrowserBudgetUI>());
+        let swarm = world.local_broadcast::<BrowserBudgetUI>();
+        world.send(swarm, MSG_BrowserBudgetUI_spawn(id, ));
+        id
+    }
+}
+
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_BrowserBudgetUI_spawn(pub BrowserBudgetUIID, );
+
+impl Into<ConfigUserID<TaxRates>> for BrowserBudgetUIID {
+    fn into(self) -> ConfigUserID<TaxRates> {
+        ConfigUserID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    ConfigUserID::<TaxRates>::register_implementor::<BrowserBudgetUI>(system);
+    system.add_spawner::<BrowserBudgetUI, _, _>(
+        |&MSG_BrowserBudgetUI_spawn(id, ), world| {
+            BrowserBudgetUI::spawn(id, world)
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,67 @@
+use kay::{World, ActorSystem};
+use compact::{CHashMap, COption};
+use stdweb::serde::Serde;
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+use stdweb::js_export;
+use SYSTEM;
+use cb_util::config_manager::{Name, ConfigUser, ConfigUserID, ConfigManagerID};
+use economy::city_budget::{TaxRates, city_tax_rates_name};
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn set_tax_rates(tax_rates: Serde<TaxRates>) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    ConfigManagerID::<TaxRates>::global_first(world).update_entry(
+        city_tax_rates_name(),
+        COption(Some(tax_rates.0)),
+        world,
+    );
+}
+
+#[derive(Compact, Clone)]
+pub struct BrowserBudgetUI {
+    id: BrowserBudgetUIID,
+    tax_rates: CHashMap<Name, TaxRates>,
+}
+
+impl BrowserBudgetUI {
+    pub fn spawn(id: BrowserBudgetUIID, world: &mut World) -> BrowserBudgetUI {
+        let ui = BrowserBudgetUI {
+            id,
+            tax_rates: CHashMap::new(),
+        };
+        ui.get_initial_config(world);
+        ui
+    }
+}
+
+impl ConfigUser<TaxRates> for BrowserBudgetUI {
+    fn local_cache(&mut self) -> &mut CHashMap<Name, TaxRates> {
+        &mut self.tax_rates
+    }
+
+    fn on_config_change(&mut self, name: Name, maybe_value: &COption<TaxRates>, world: &mut World) {
+        self.apply_config_change(name, maybe_value, world);
+
+        if name == city_tax_rates_name() {
+            let maybe_tax_rates: Option<TaxRates> = **maybe_value;
+            js! {
+                window.cbReactApp.boundSetState(oldState => update(oldState, {
+                    budget: {taxRates: {"$set": @{Serde(maybe_tax_rates)}}}
+                }));
+            }
+        }
+    }
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<BrowserBudgetUI>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World) {
+    BrowserBudgetUIID::spawn(world);
+}
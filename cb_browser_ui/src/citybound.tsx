@@ -41,9 +41,11 @@ import ContainerDimensions from 'react-container-dimensions';
 import update from 'immutability-helper';
 import * as Camera from './camera/Camera';
 import * as Planning from './planning_browser/Planning';
+import { ConstructionSiteLayers } from './planning_browser/ConstructionSiteLayers';
 import * as Transport from './transport_browser/Transport';
 import * as LandUse from './land_use_browser/LandUse';
 import * as Households from './households_browser/Households';
+import * as Budget from './budget_browser/Budget';
 import * as Vegetation from './vegetation_browser/Vegetation';
 import MainUIModes from './uiModes';
 import * as Time from './time_browser/Time';
@@ -70,6 +72,7 @@ declare module '../target/wasm32-unknown-unknown/release/cb_browser_ui' {
         insert_control_point(projectId: string, gestureId: string, point: [number, number], doneInserting: boolean);
         split_gesture(projectId: string, gestureId: string, point: [number, number], doneSplitting: boolean);
         set_n_lanes(projectId: string, gestureId: string, nLanesForward: number, nLanesBackward: number, doneChanging: boolean);
+        set_tax_rates(taxRates: import('./budget_browser/Budget').TaxRates): void;
     }
 }
 
@@ -91,6 +94,7 @@ export type SharedState = {
     transport: any,
     landUse: any,
     households: any,
+    budget: typeof Budget.initialState,
     vegetation: any,
     debug: any,
     system: {
@@ -126,6 +130,7 @@ import('../target/wasm32-unknown-unknown/release/cb_browser_ui').then(mod => mod
         camera: Camera.settingSpec,
         debug: Debug.settingsSpec,
         planning: Planning.settingsSpec,
+        landUse: LandUse.settingsSpec,
         rendering: {
             retinaFactor: { default: 2, description: "Oversampling/Retina Factor", min: 0.5, max: 4.0, step: 0.1 }
         }
@@ -144,6 +149,7 @@ import('../target/wasm32-unknown-unknown/release/cb_browser_ui').then(mod => mod
                 transport: Transport.initialState,
                 landUse: LandUse.initialState,
                 households: Households.initialState,
+                budget: Budget.initialState,
                 vegetation: Vegetation.initialState,
                 debug: Debug.initialState,
                 system: {
@@ -196,6 +202,7 @@ import('../target/wasm32-unknown-unknown/release/cb_browser_ui').then(mod => mod
                                             <LandUse.Layers state={this.state} />
                                             <Vegetation.Layers state={this.state} />
                                             <Transport.Layers state={this.state} />
+                                            <ConstructionSiteLayers state={this.state} />
 
                                         </Utils.RenderContext.Provider>
                                     </Utils.Interactive3DContext.Provider>
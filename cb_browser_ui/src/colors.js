@@ -52,6 +52,8 @@ export default {
     grass,
     trunks: [0.4, 0.3, 0.2],
     canopies: [0.3, 0.5, 0.2],
+    canopiesAutumn: [0.65, 0.4, 0.15],
+    canopiesWinter: [0.42, 0.38, 0.32],
     asphalt: [0.6, 0.6, 0.6],
     roadMarker: [1.0, 1.0, 1.0],
 
@@ -70,6 +72,10 @@ export default {
     plannedRoadMarker: [0.6, 0.6, 0.6],
     destructedAsphalt: [1.0, 0.0, 0.0],
     buildingOutlines: [0.0, 0.0, 0.0],
+    constructionSite: [0.76, 0.6, 0.42],
+
+    satisfied: [0.2, 0.8, 0.3],
+    unsatisfied: [0.9, 0.2, 0.1],
 
     controlPointMaster: [0.3, 0.3, 1.0],
     controlPointCurrentProject: [0.0, 0.061, 1.0],//[0, 72, 255]
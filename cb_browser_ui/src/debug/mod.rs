@@ -2,11 +2,12 @@ use kay::TypedID;
 use stdweb::serde::Serde;
 #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
 use stdweb::js_export;
-use SYSTEM;
+use {SYSTEM, client_key};
 
 use cb_planning::GestureID;
 use cb_planning::plan_manager::ProjectID;
 use planning::{CBPlanManagerID, CBGestureIntent};
+use planning_browser::BrowserPlanningUIID;
 use transport::transport_planning::RoadLaneConfig;
 use descartes::{Corner};
 
@@ -16,6 +17,7 @@ pub fn plan_grid(project_id: Serde<ProjectID>, n: Serde<isize>, n_lanes: Serde<u
     let world = &mut system.world();
 
     let plan_manager = CBPlanManagerID::global_first(world);
+    let requester = BrowserPlanningUIID::local_first(world).into();
 
     use ::transport::transport_planning::RoadIntent;
     use ::descartes::P2;
@@ -35,6 +37,8 @@ pub fn plan_grid(project_id: Serde<ProjectID>, n: Serde<isize>, n_lanes: Serde<u
                     n_lanes_backward: n_lanes.0
                 }
             )),
+            requester,
+            client_key(),
             world,
         );
     }
@@ -54,6 +58,8 @@ pub fn plan_grid(project_id: Serde<ProjectID>, n: Serde<isize>, n_lanes: Serde<u
                     n_lanes_backward: n_lanes.0
                 }
             )),
+            requester,
+            client_key(),
             world,
         );
     }
@@ -115,6 +115,8 @@ class BuildingInfo extends React.Component {
             {this.props.pinned && <a className="close-window" onClick={this.props.closeWindow}>×</a>}
             {this.props.inspectedBuildingState && [
                 <h1>{this.props.inspectedBuildingState.style}</h1>,
+                this.props.inspectedBuildingState.satisfaction !== null &&
+                    <p>Satisfaction: {(this.props.inspectedBuildingState.satisfaction * 100).toFixed(0)}%</p>,
                 <div className="household-list">
                     {this.props.inspectedBuildingState.households.map(id => <div className="household">{[
                         <h3>{fmtId(id)}</h3>,
@@ -127,24 +129,61 @@ class BuildingInfo extends React.Component {
 }
 
 function HouseholdInfo(props) {
-    const { resources, member_resources, member_tasks } = props.core;
+    const { resources, member_resources, member_tasks, member_agendas, finances, satisfaction, decision_history, provided_offers } = props.core;
 
     return [
+        <p>Satisfaction: {(satisfaction * 100).toFixed(0)}%</p>,
+        finances && [
+            <p>Balance/day: {finances.rolling_balance.toFixed(2)}</p>,
+            <p>Debt: {finances.debt.toFixed(2)}</p>,
+        ],
         resources.entries.map(([resource, amount]) =>
             <p>{resource}: {amount.toFixed(2)}</p>
         ),
+        provided_offers && provided_offers.filter(offer => !offer.is_internal).map(offer => {
+            const queueLength = Math.max(0, offer.active_users.length - offer.service_capacity);
+            return <p>
+                Offers {offer.deal.delta.entries.map(([resource]) => resource).join(", ")}:
+                {" "}{offer.active_users.length - queueLength}/{offer.service_capacity} served
+                {queueLength > 0 ? `, ${queueLength} queuing` : ""}
+            </p>
+        }),
         member_resources.map((memberResources, memberI) =>
             [
                 <h4>Member {memberI}</h4>,
                 <p><StateAndGoal here={props.here} state={member_tasks[memberI].state} goal={member_tasks[memberI].goal} /></p>,
+                member_agendas && member_agendas[memberI].items.length > 0 &&
+                    <p>Agenda: {member_agendas[memberI].items.map(item => item.resource).join(", ")}</p>,
                 memberResources.entries.map(([resource, amount]) =>
                     <p>{resource}: {amount.toFixed(2)}</p>
                 ),
+                decision_history && <DecisionHistory traces={decision_history.traces.filter(trace => trace.member == memberI)} />,
             ]
         )
     ]
 }
 
+function DecisionHistory(props) {
+    return props.traces.length > 0 && <details>
+        <summary>Recent decisions</summary>
+        {props.traces.slice().reverse().map(trace => <div>
+            <p>At tick {trace.instant}, problems: {trace.top_problems.map(([resource, graveness]) => resource + " (" + graveness.toFixed(1) + ")").join(", ")}</p>
+            {trace.candidates.length == 0 && <p>No offers found</p>}
+            {trace.candidates.map(candidate => {
+                const isChosen = trace.chosen
+                    && trace.chosen.household == candidate.offer.household
+                    && trace.chosen.idx == candidate.offer.idx;
+                return <p>
+                    {isChosen ? "→ " : ""}{candidate.resource} at {fmtId(candidate.offer.household)}:
+                    {" "}usefulness {candidate.usefulness.toFixed(3)}, travel {(candidate.travel_time / 60).toFixed(0)}min
+                    {candidate.waiting_time > 0 ? `, waiting ${(candidate.waiting_time / 60).toFixed(0)}min` : ""}
+                    {candidate.open ? "" : ", closed"}
+                </p>
+            })}
+        </div>)}
+    </details>
+}
+
 function StateAndGoal(props) {
     let statePart;
     let goalGerund = false;
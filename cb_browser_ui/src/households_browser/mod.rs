@@ -68,10 +68,14 @@ impl HouseholdUI for BrowserHouseholdUI {
                                 resources: @{Serde(&core.resources)},
                                 member_resources: @{Serde(&core.member_resources)},
                                 member_tasks: @{Serde(&core.member_tasks)},
+                                member_agendas: @{Serde(&core.member_agendas)},
                                 decision_state: @{Serde(decision_state_workaround)},
                                 used_offers: @{Serde(&core.used_offers)},
                                 member_used_offers: @{Serde(&core.member_used_offers)},
                                 provided_offers: @{Serde(&core.provided_offers)},
+                                finances: @{Serde(&core.finances)},
+                                satisfaction: @{Serde(&core.satisfaction)},
+                                decision_history: @{Serde(&core.decision_history)},
                             }
                         }}
                     }
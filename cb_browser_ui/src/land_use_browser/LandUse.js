@@ -1,6 +1,6 @@
 import colors from '../colors';
 import renderOrder from '../renderOrder';
-import { RenderLayer } from "../browser_utils/Utils";
+import { RenderLayer, useSettings } from "../browser_utils/Utils";
 import * as propMeshes from './propMeshes';
 
 const MATERIALS = ["WhiteWall", "TiledRoof", "FlatRoof", "FieldWheat", "FieldRows", "FieldPlant", "FieldMeadow", "WoodenFence", "MetalFence", "LotAsphalt"];
@@ -9,6 +9,7 @@ const PROP_TYPES = ["SmallWindow", "ShopWindowGlass", "ShopWindowBanner", "Narro
 const initialRenderingState = {
     buildingMeshes: {},
     buildingProps: {},
+    lotMeshes: {},
     propMeshes: {
         SmallWindow: propMeshes.smallWindow,
         NarrowDoor: propMeshes.narrowDoor,
@@ -29,15 +30,41 @@ for (let propType of PROP_TYPES) {
 }
 
 export const initialState = {
-    rendering: initialRenderingState
+    rendering: initialRenderingState,
+    satisfaction: {}
+}
+
+export const settingsSpec = {
+    showSatisfactionOverlay: { default: false, description: "Show Household Satisfaction Overlay" }
+}
+
+function satisfactionInstance(satisfaction) {
+    const color = [0, 1, 2].map(i =>
+        colors.unsatisfied[i] * (1 - satisfaction) + colors.satisfied[i] * satisfaction
+    );
+    return new Float32Array([0.0, 0.0, 0.0, 1.0, 0.0, ...color]);
 }
 
 import React from 'react';
 
 export function Layers(props) {
     let { state } = props;
+    const { showSatisfactionOverlay } = useSettings().landUse;
+
+    const satisfactionLayer = showSatisfactionOverlay ? [
+        <RenderLayer
+            key="satisfaction"
+            decal={true}
+            renderOrder={renderOrder.buildingOutlines}
+            batches={Object.keys(state.landUse.rendering.lotMeshes)
+                .filter(id => state.landUse.satisfaction[id] !== undefined)
+                .map(id => ({
+                    mesh: state.landUse.rendering.lotMeshes[id],
+                    instances: satisfactionInstance(state.landUse.satisfaction[id])
+                }))} />
+    ] : [];
 
-    return MATERIALS.map(material =>
+    return satisfactionLayer.concat(MATERIALS.map(material =>
         <RenderLayer
             key={material}
             decal={false}
@@ -56,5 +83,5 @@ export function Layers(props) {
                 instances: new Float32Array(Object.values(state.landUse.rendering.buildingProps[propType])
                     .reduce((allPropInstances, buildingPropInstances) => allPropInstances.concat(buildingPropInstances), []))
             }]} />
-    ));
+    )));
 }
\ No newline at end of file
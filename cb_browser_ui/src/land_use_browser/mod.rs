@@ -4,15 +4,18 @@ use stdweb::serde::Serde;
 #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
 use stdweb::js_export;
 use browser_utils::{to_js_mesh, flatten_instances};
+use michelangelo::Mesh;
 use SYSTEM;
 use cb_util::config_manager::{Name, ConfigUser, ConfigUserID};
 use ::std::collections::HashMap;
 use ::land_use::buildings::{BuildingID, BuildingStyle};
 use ::land_use::buildings::architecture::{build_building};
 use ::land_use::buildings::architecture::language::ArchitectureRule;
-use ::land_use::buildings::architecture::materials_and_props::{ALL_MATERIALS, ALL_PROP_TYPES};
+use ::land_use::buildings::architecture::materials_and_props::{ALL_MATERIALS, ALL_PROP_TYPES,
+BuildingMaterial};
 use ::land_use::zone_planning::Lot;
 use ::economy::households::HouseholdID;
+use ::cb_planning::plan_manager::ProjectID;
 
 #[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
 pub fn get_building_info(building_id: Serde<BuildingID>) {
@@ -69,11 +72,12 @@ impl LandUseUI for BrowserLandUseUI {
         &mut self,
         id: BuildingID,
         lot: &Lot,
-        households: &CVec<HouseholdID>,
+        _households: &CVec<HouseholdID>,
         style: BuildingStyle,
+        field_material: Option<BuildingMaterial>,
         world: &mut World,
     ) {
-        let result = build_building(lot, style, &self.architecture_rules, households, world);
+        let result = build_building(lot, style, field_material, &self.architecture_rules, world);
         match result {
             Ok(building_mesh) => {
                 let material_updates: ::stdweb::Object = building_mesh
@@ -113,11 +117,14 @@ impl LandUseUI for BrowserLandUseUI {
                     .collect::<HashMap<_, _>>()
                     .into();;
 
+                let lot_mesh = to_js_mesh(&Mesh::from_area(&lot.original_area));
+
                 js! {
                     window.cbReactApp.boundSetState(oldState => update(oldState, {
                         landUse: {rendering: {
                             buildingMeshes: @{material_updates},
-                            buildingProps: @{prop_updates}
+                            buildingProps: @{prop_updates},
+                            lotMeshes: {[@{Serde(id)}]: {"$set": @{lot_mesh}}}
                         }},
                         households: {
                             buildingPositions: {[@{Serde(id)}]: {
@@ -155,7 +162,8 @@ impl LandUseUI for BrowserLandUseUI {
             window.cbReactApp.boundSetState(oldState => update(oldState, {
                 landUse: {rendering: {
                     buildingMeshes: @{material_unsets},
-                    buildingProps: @{prop_unsets}
+                    buildingProps: @{prop_unsets},
+                    lotMeshes: {"$unset": [@{Serde(id)}]}
                 }},
                 households: {buildingPositions: {"$unset": [@{Serde(id)}]}}
             }));
@@ -167,6 +175,7 @@ impl LandUseUI for BrowserLandUseUI {
         _id: BuildingID,
         style: BuildingStyle,
         households: &CVec<HouseholdID>,
+        satisfaction: Option<f32>,
         _world: &mut World,
     ) {
         js! {
@@ -175,11 +184,55 @@ impl LandUseUI for BrowserLandUseUI {
                     inspectedBuildingState: {"$set": {
                         households: @{Serde(households)},
                         style: @{Serde(style)},
+                        satisfaction: @{Serde(satisfaction)},
                     }}
                 }
             }));
         }
     }
+
+    fn on_building_satisfaction(
+        &mut self,
+        id: BuildingID,
+        satisfaction: Option<f32>,
+        _world: &mut World,
+    ) {
+        if let Some(satisfaction) = satisfaction {
+            js! {
+                window.cbReactApp.boundSetState(oldState => update(oldState, {
+                    landUse: {satisfaction: {[@{Serde(id)}]: {"$set": @{satisfaction}}}}
+                }));
+            }
+        } else {
+            js! {
+                window.cbReactApp.boundSetState(oldState => update(oldState, {
+                    landUse: {satisfaction: {"$unset": [@{Serde(id)}]}}
+                }));
+            }
+        }
+    }
+
+    fn on_building_demolition_report(
+        &mut self,
+        project_id: ProjectID,
+        id: BuildingID,
+        style: BuildingStyle,
+        households: &CVec<HouseholdID>,
+        _world: &mut World,
+    ) {
+        js! {
+            window.cbReactApp.boundSetState(oldState => oldState.planning.impacts[@{Serde(project_id)}]
+                ? update(oldState, {
+                    planning: {impacts: {[@{Serde(project_id)}]: {
+                        demolishedBuildings: {[@{Serde(id)}]: {"$set": {
+                            style: @{Serde(style)},
+                            households: @{Serde(households)},
+                        }}}
+                    }}}
+                })
+                : oldState);
+        }
+    }
 }
 
 mod kay_auto;
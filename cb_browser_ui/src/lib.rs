@@ -29,6 +29,7 @@ pub mod planning_browser;
 pub mod debug;
 pub mod time_browser;
 pub mod households_browser;
+pub mod budget_browser;
 pub mod transport_browser;
 pub mod land_use_browser;
 pub mod vegetation_browser;
@@ -36,6 +37,17 @@ pub mod browser_utils;
 
 // TODO: not thread safe for now
 static mut SYSTEM: *mut ActorSystem = 0 as *mut ActorSystem;
+/// Sent along with requests that the server only accepts from certain clients
+static mut CLIENT_KEY: cb_util::client_keys::ClientKey = cb_util::client_keys::ClientKey(0);
+
+pub fn client_key() -> cb_util::client_keys::ClientKey {
+    unsafe { CLIENT_KEY }
+}
+
+/// Called with the key the server issued to this client whenever it's received
+pub fn set_client_key(key: cb_util::client_keys::ClientKey) {
+    unsafe { CLIENT_KEY = key }
+}
 
 #[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
 pub fn start() {
@@ -79,6 +91,7 @@ pub fn start() {
     time_browser::setup(&mut system);
     land_use_browser::setup(&mut system);
     households_browser::setup(&mut system);
+    budget_browser::setup(&mut system);
     vegetation_browser::setup(&mut system);
 
     js! {
@@ -93,6 +106,7 @@ pub fn start() {
     time_browser::spawn(&mut system.world());
     land_use_browser::spawn(&mut system.world());
     households_browser::spawn(&mut system.world());
+    budget_browser::spawn(&mut system.world());
     vegetation_browser::spawn(&mut system.world());
 
     system.process_all_messages();
@@ -5,6 +5,7 @@ import { Settings } from './settings';
 import { Collapse, Checkbox, Tabs, Progress } from 'antd';
 import aePlayLogo from '../assets/ae_play.png';
 import { ToToolPortal, ToWindowPortal } from './citybound';
+import { TaxRatesEditor } from './budget_browser/Budget';
 
 const Panel = Collapse.Panel;
 const TabPane = Tabs.TabPane;
@@ -41,7 +42,7 @@ function CBLogo() {
 
 export default function MainMenu(props: { state, setState, settingSpecs }) {
     const [visible, setVisible] = useState<boolean>(!localStorage["cb-hide-menu"]);
-    const [tabKey, setTabKey] = useState<'about' | 'credits' | 'tutorial' | 'settings'>('about');
+    const [tabKey, setTabKey] = useState<'about' | 'credits' | 'tutorial' | 'budget' | 'settings'>('about');
 
 
     return <>
@@ -56,7 +57,7 @@ export default function MainMenu(props: { state, setState, settingSpecs }) {
         {visible && <ToWindowPortal>
             <div key="debug" className="window menu">
                 <a className="close-window" onClick={() => setVisible(false)}>×</a>
-                <Tabs type="card" size="large" activeKey={tabKey} onChange={newTabKey => setTabKey(newTabKey as 'about' | 'credits' | 'tutorial' | 'settings')}>
+                <Tabs type="card" size="large" activeKey={tabKey} onChange={newTabKey => setTabKey(newTabKey as 'about' | 'credits' | 'tutorial' | 'budget' | 'settings')}>
                     <TabPane tab="About" key="about">
                         <CBLogo />
                         <a className="become-patron" href="https://patreon.com/citybound" target="_blank"> </a>
@@ -109,6 +110,10 @@ export default function MainMenu(props: { state, setState, settingSpecs }) {
                         <p><em>Speed up time using the slider next to the clock in the top left corner</em> and see what happens.</p>
                         <p><em>Click on the eye icon and hover/click on buildings to inspect them</em></p>
                     </TabPane>
+                    <TabPane tab="City Budget" key="budget">
+                        <h3>Tax Rates</h3>
+                        <TaxRatesEditor state={props.state} />
+                    </TabPane>
                     <TabPane tab="Settings &amp; Controls" key="settings">
                         <Settings currentSettings={props.state.settings} specs={props.settingSpecs} setState={props.setState} />
                     </TabPane>
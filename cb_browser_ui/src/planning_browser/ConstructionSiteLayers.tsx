@@ -0,0 +1,17 @@
+import * as React from 'react';
+import renderOrder from '../renderOrder';
+import { RenderLayer } from '../browser_utils/Utils';
+import { SharedState } from '../citybound';
+import colors from '../colors';
+
+const constructionSiteInstance = new Float32Array([0.0, 0.0, 0.0, 1.0, 0.0, ...colors.constructionSite]);
+
+// Shown in all modes, since construction continues after a project was implemented
+export function ConstructionSiteLayers({ state }: {
+    state: SharedState;
+}) {
+    return <RenderLayer renderOrder={renderOrder.constructionSites} decal={true} batches={[...state.planning.rendering.constructionSiteGroups.values()].map(groupMesh => ({
+        mesh: groupMesh,
+        instances: constructionSiteInstance
+    }))} />;
+}
@@ -15,11 +15,32 @@ type GroupID = string;
 type GroupMesh = {};
 
 type Project = {
+    step_id: string,
     gestures: {}
 }
 
+export type HistoryStep = {
+    plan: Project,
+    parent: string | null,
+    created: number
+}
+
 type Mesh = {};
 
+export type ProjectImpact = {
+    categories: { category: string, constructed: number, morphed: number, destroyed: number }[],
+    constructionCost: number,
+    overlappingProjects: string[],
+    conflictingGestures: string[],
+    planningFailed: boolean,
+    demolishedBuildings: {
+        [buildingId: string]: {
+            style: string,
+            households: string[]
+        }
+    }
+}
+
 export type PlanningSharedState = {
     planningMode: null | "roads" | "zoning",
     rendering: {
@@ -36,7 +57,9 @@ export type PlanningSharedState = {
             zoneOutlineGroups: Map<BatchID, Map<GroupID, GroupMesh>>,
             buildingOutlinesGroup: Map<BatchID, Map<GroupID, GroupMesh>>,
         },
-        roadInfos: {}
+        constructionSiteGroups: Map<BatchID, Map<GroupID, GroupMesh>>,
+        roadInfos: {},
+        constructionCost: number
     },
     master: {
         gestures: {}
@@ -44,10 +67,35 @@ export type PlanningSharedState = {
     projects: {
         [projectId: string]: {
             undoable_history: Project[]
-            ongoing: Project
+            ongoing: Project,
+            history_tree: { [stepId: string]: HistoryStep }
+        }
+    },
+    impacts: {
+        [projectId: string]: ProjectImpact
+    },
+    stepImpacts: {
+        [stepId: string]: Pick<ProjectImpact, 'categories' | 'constructionCost' | 'planningFailed'>
+    },
+    access: {
+        projects: {
+            [projectId: string]: {
+                owner: number,
+                locked: boolean,
+                approved_by: number[]
+            }
+        },
+        approvalRequired: boolean,
+        ownClient: number | null
+    },
+    templates: {
+        [name: string]: {
+            gestures: any[]
         }
     },
-    currentProject: string | null
+    currentProject: string | null,
+    // why the last project that was started or imported wasn't created
+    projectError: string | null
 }
 
 export const initialState: PlanningSharedState = {
@@ -62,13 +110,28 @@ export const initialState: PlanningSharedState = {
             zoneOutlineGroups: new Map(LAND_USES.map(landUse => [landUse, new Map()])),
             buildingOutlinesGroup: new Map(),
         },
-        roadInfos: {}
+        constructionSiteGroups: new Map(),
+        roadInfos: {},
+        constructionCost: 0
     },
     master: {
         gestures: {}
     },
     projects: {
-    }
+    },
+    impacts: {
+    },
+    stepImpacts: {
+    },
+    access: {
+        projects: {},
+        approvalRequired: false,
+        ownClient: null
+    },
+    templates: {
+    },
+    currentProject: null,
+    projectError: null
 };
 
 export const settingsSpec = {
@@ -96,8 +159,9 @@ export function PlanningUI(props: { state: SharedState, setState: SetSharedState
     const [planningMode, setPlanningMode] = useState<'roads' | 'zoning' | null>(null);
     const [currentProject, setCurrentProject] = [
         props.state.planning.currentProject,
-        (newProject) => props.setState(oldState => update(oldState, { planning: { currentProject: { $set: newProject } } }))
+        (newProject) => props.setState(oldState => update(oldState, { planning: { currentProject: { $set: newProject }, projectError: { $set: null } } }))
     ]
+    const clearProjectError = () => props.setState(oldState => update(oldState, { planning: { projectError: { $set: null } } }));
     const [addToEnd, setAddToEnd] = useState<boolean>(true);
     const [editedGesture, setEditedGesture] = useState<boolean>(false);
     const [intent, setIntent] = useState<Intent | null>(null);
@@ -129,6 +193,7 @@ export function PlanningUI(props: { state: SharedState, setState: SetSharedState
             setPlanningMode={setPlanningMode}
             currentProject={currentProject}
             setCurrentProject={setCurrentProject}
+            clearProjectError={clearProjectError}
             intent={intent}
             setIntent={setIntent} />
     </>
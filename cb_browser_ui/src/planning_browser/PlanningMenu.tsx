@@ -1,7 +1,7 @@
 import colors, { toCSS, fromLinFloat } from '../colors';
 import * as React from 'react';
-import { useCallback } from 'react';
-import { Button, Select, Divider, Icon } from 'antd';
+import { useCallback, useRef } from 'react';
+import { Button, Select, Divider, Icon, Popover, Alert, Modal } from 'antd';
 const Option = Select.Option;
 import uuid from '../uuid';
 
@@ -9,10 +9,16 @@ import { Toolbar } from '../toolbar';
 import { SharedState, ToToolPortal } from '../citybound';
 import { Intent } from '../wasm32-unknown-unknown/release/cb_browser_ui';
 import { useInputBinding } from '../browser_utils/Utils';
+import { downloadProject, readProjectFile } from './projectFiles';
+import { TemplateLibrary } from './TemplateLibrary';
+import { ProjectHistory } from './ProjectHistory';
+
+// TODO: share constants with Rust somehow
+const RELOCATION_TIMEOUT_HOURS = 48;
 
 export function PlanningMenu(
-    { state, currentProject, setCurrentProject, planningMode, setPlanningMode, intent, setIntent }:
-        { state: SharedState, currentProject: string | null, setCurrentProject: (project: string) => void, intent: Intent | null, setIntent: (intent: Intent | null) => void, planningMode: 'roads' | 'zoning' | null, setPlanningMode: (mode: 'roads' | 'zoning' | null) => void }) {
+    { state, currentProject, setCurrentProject, clearProjectError, planningMode, setPlanningMode, intent, setIntent }:
+        { state: SharedState, currentProject: string | null, setCurrentProject: (project: string) => void, clearProjectError: () => void, intent: Intent | null, setIntent: (intent: Intent | null) => void, planningMode: 'roads' | 'zoning' | null, setPlanningMode: (mode: 'roads' | 'zoning' | null) => void }) {
 
     const startNewProject = useCallback(() => {
         const projectId = uuid();
@@ -20,12 +26,60 @@ export function PlanningMenu(
         setCurrentProject(projectId);
     }, [setCurrentProject])
 
+    const exportProject = useCallback(() => {
+        const project = currentProject && state.planning.projects[currentProject];
+        if (project) downloadProject(project, `project-${currentProject.slice(0, 3).toUpperCase()}`);
+    }, [currentProject, state.planning.projects]);
+
+    const importFileInput = useRef<HTMLInputElement>(null);
+
+    const importProject = useCallback((event: React.ChangeEvent<HTMLInputElement>) => {
+        const file = event.target.files[0];
+        event.target.value = "";
+        if (!file) return;
+
+        const cameraTarget = state.camera.target;
+        readProjectFile(file, [cameraTarget[0], cameraTarget[1]]).then(project => {
+            const projectId = uuid();
+            cbRustBrowser.import_project(projectId, project);
+            setCurrentProject(projectId);
+        }).catch(error => console.error("Couldn't import project", error));
+    }, [state.camera.target, setCurrentProject]);
+
+    const { access } = state.planning;
+    const currentAccess = currentProject && access.projects[currentProject];
+    const isOwnProject = !currentAccess || currentAccess.owner === access.ownClient;
+    const mayEdit = !currentAccess || !currentAccess.locked || isOwnProject;
+    const isApproved = !currentAccess
+        || currentAccess.approved_by.some(approver => approver !== currentAccess.owner);
+    const mayImplement = mayEdit && (!access.approvalRequired || isApproved);
+
+    const impact = currentProject && state.planning.impacts[currentProject];
+
+    // overlaps with other pending projects have to be confirmed, the server checks that
     const implementProject = useCallback(() => {
+        if (!mayImplement) return;
         if (currentProject) {
-            cbRustBrowser.implement_project(currentProject);
+            if (!impact) {
+                cbRustBrowser.get_project_impact(currentProject);
+                return;
+            }
+            const confirmedOverlaps = impact.overlappingProjects;
+            if (confirmedOverlaps.length > 0) {
+                Modal.confirm({
+                    title: "Implement overlapping project?",
+                    content: `Overlaps with ${confirmedOverlaps.map(projectLabel).join(", ")}`,
+                    onOk: () => {
+                        cbRustBrowser.implement_project(currentProject, confirmedOverlaps);
+                        setCurrentProject(null);
+                    },
+                });
+                return;
+            }
+            cbRustBrowser.implement_project(currentProject, []);
         }
         setCurrentProject(null);
-    }, [currentProject, setCurrentProject]);
+    }, [currentProject, setCurrentProject, mayImplement, impact]);
 
     const undo = useCallback(() => {
         if (currentProject) {
@@ -50,6 +104,67 @@ export function PlanningMenu(
         if (currentProject) cbRustBrowser.redo(currentProject);
     }, [currentProject]);
 
+    const reviewProject = useCallback((visible: boolean) => {
+        if (currentProject && visible) cbRustBrowser.get_project_impact(currentProject);
+    }, [currentProject]);
+
+    const setLocked = useCallback((locked: boolean) => {
+        if (currentProject) cbRustBrowser.set_project_locked(currentProject, locked);
+    }, [currentProject]);
+
+    const approveProject = useCallback(() => {
+        if (currentProject) cbRustBrowser.approve_project(currentProject);
+    }, [currentProject]);
+
+    const projectLabel = (projectId: string) => {
+        const projectAccess = access.projects[projectId];
+        const owner = !projectAccess ? ""
+            : projectAccess.owner === access.ownClient ? " (yours)" : ` (client ${projectAccess.owner})`;
+        return `Project '${projectId.slice(0, 3).toUpperCase()}'${owner}${projectAccess && projectAccess.locked ? " 🔒" : ""}`;
+    };
+
+    const resolveConflict = useCallback((gestureId: string, keepProjectVersion: boolean) => {
+        if (currentProject) {
+            cbRustBrowser.resolve_conflict(currentProject, gestureId, keepProjectVersion);
+            cbRustBrowser.get_project_impact(currentProject);
+        }
+    }, [currentProject]);
+
+    const hasConflicts = impact && impact.conflictingGestures.length > 0;
+    const demolishedBuildings = impact ? Object.keys(impact.demolishedBuildings) : [];
+    const relocatedHouseholds = demolishedBuildings.reduce(
+        (sum, buildingId) => sum + impact.demolishedBuildings[buildingId].households.length, 0);
+
+    const impactReport = impact
+        ? <div className="project-impact">
+            {impact.planningFailed &&
+                <Alert type="error" showIcon message="This project can't be planned in its current state" />}
+            <table>
+                <thead><tr><th></th><th>New</th><th>Changed</th><th>Removed</th></tr></thead>
+                <tbody>{impact.categories.map(({ category, constructed, morphed, destroyed }) =>
+                    <tr key={category}><td>{category}</td><td>{constructed}</td><td>{morphed}</td><td>{destroyed}</td></tr>
+                )}</tbody>
+            </table>
+            <p>Construction cost: ${impact.constructionCost.toFixed(0)}</p>
+            {demolishedBuildings.length > 0 && (relocatedHouseholds > 0
+                ? <Alert type="warning" showIcon message={`Displaces ${relocatedHouseholds} households`}
+                    description={`Demolishes ${demolishedBuildings.length} buildings. Their households look for a new home first and are evicted if they don't find one within ${RELOCATION_TIMEOUT_HOURS} hours.`} />
+                : <p>Demolishes {demolishedBuildings.length} empty buildings</p>)}
+            {impact.overlappingProjects.length > 0 &&
+                <p>Overlaps with {impact.overlappingProjects.map(projectLabel).join(", ")}</p>}
+            {hasConflicts && <div className="project-conflicts">
+                <p>Changed in the master plan since this project started changing them:</p>
+                {impact.conflictingGestures.map(gestureId =>
+                    <p key={gestureId}>
+                        Gesture '{gestureId.slice(0, 3).toUpperCase()}'
+                        <Button size="small" onClick={() => resolveConflict(gestureId, true)}>Keep mine</Button>
+                        <Button size="small" onClick={() => resolveConflict(gestureId, false)}>Take master</Button>
+                    </p>
+                )}
+            </div>}
+        </div>
+        : "Calculating...";
+
     useInputBinding({
         [state.settings.planning.implementProjectKey.key]: implementProject,
         [state.settings.planning.undoKey.key]: undo,
@@ -71,17 +186,59 @@ export function PlanningMenu(
                         <div style={{ padding: '8px', cursor: 'pointer' }} onClick={startNewProject}>
                             <Icon type="plus" /> Start another project
                             </div>
+                        <div style={{ padding: '8px', cursor: 'pointer' }} onClick={() => importFileInput.current.click()}>
+                            <Icon type="import" /> Import project
+                            </div>
                         <Divider style={{ margin: '4px 0' }} />
                         {menu}
                     </div>
                 )}
             >{Object.keys(state.planning.projects).map(projectId =>
-                <Option value={projectId}>Project '{projectId.slice(0, 3).toUpperCase()}'</Option>
+                <Option value={projectId}>{projectLabel(projectId)}</Option>
             )}</Select>
-            : <Button type="primary" onClick={startNewProject}>Start new project</Button>}
+            : <>
+                <Button type="primary" onClick={startNewProject}>Start new project</Button>
+                <Button icon="import" onClick={() => importFileInput.current.click()}>Import</Button>
+            </>}
+
+        <input type="file" accept=".json" ref={importFileInput} style={{ display: 'none' }} onChange={importProject} />
+
+        {state.planning.projectError &&
+            <Alert type="error" showIcon closable message={state.planning.projectError} onClose={clearProjectError} />}
+
+        {currentProject && state.planning.projects[currentProject] &&
+            <Button icon="export" onClick={exportProject}>Export</Button>}
+
+        {currentProject &&
+            <Popover title="Project History" trigger="click"
+                content={<ProjectHistory state={state} currentProject={currentProject} mayEdit={mayEdit} />}>
+                <Button icon="branches">History</Button>
+            </Popover>}
+
+        <Popover title="Templates" trigger="click"
+            content={<TemplateLibrary state={state} currentProject={currentProject} mayEdit={mayEdit} />}>
+            <Button icon="appstore">Templates</Button>
+        </Popover>
+
+        {currentProject &&
+            <Popover title="Project Impact" trigger="click" content={impactReport} onVisibleChange={reviewProject}>
+                <Button>Review</Button>
+            </Popover>}
+
+        {currentProject && currentAccess && isOwnProject &&
+            <Button icon={currentAccess.locked ? "lock" : "unlock"} onClick={() => setLocked(!currentAccess.locked)}>
+                {currentAccess.locked ? "Unlock" : "Lock"}
+            </Button>}
+
+        {currentProject && access.approvalRequired && !isOwnProject &&
+            <Button onClick={approveProject} disabled={currentAccess.approved_by.includes(access.ownClient)}>
+                Approve
+            </Button>}
 
         {currentProject &&
-            <Button type="primary" onClick={implementProject} >Implement</Button>}
+            <Button type="primary" onClick={implementProject} disabled={!mayImplement || hasConflicts} >
+                Implement (${state.planning.rendering.constructionCost.toFixed(0)})
+            </Button>}
 
         <Toolbar id="planning-history-toolbar"
             options={{
@@ -0,0 +1,86 @@
+import * as React from 'react';
+import { useState, useEffect } from 'react';
+import { Button, Checkbox } from 'antd';
+
+import { SharedState } from '../citybound';
+import { HistoryStep } from './Planning';
+
+const MAX_COMPARED_STEPS = 2;
+
+function StepImpactTable({ state, stepIds }: { state: SharedState, stepIds: string[] }) {
+    const impacts = stepIds.map(stepId => state.planning.stepImpacts[stepId]);
+    if (impacts.some(impact => !impact)) return <p>Calculating...</p>;
+
+    const categories = Array.from(new Set(
+        impacts.flatMap(impact => impact.categories.map(({ category }) => category))));
+    const categoryImpact = (impact, category) =>
+        impact.categories.find(categoryImpact => categoryImpact.category === category)
+        || { constructed: 0, morphed: 0, destroyed: 0 };
+
+    return <table className="step-comparison">
+        <thead>
+            <tr><th></th>{stepIds.map(stepId => <th key={stepId} colSpan={3}>Step '{stepId.slice(0, 3).toUpperCase()}'</th>)}</tr>
+            <tr><th></th>{stepIds.map(stepId => <React.Fragment key={stepId}><th>New</th><th>Changed</th><th>Removed</th></React.Fragment>)}</tr>
+        </thead>
+        <tbody>
+            {categories.map(category => <tr key={category}>
+                <td>{category}</td>
+                {impacts.map((impact, i) => {
+                    const { constructed, morphed, destroyed } = categoryImpact(impact, category);
+                    return <React.Fragment key={stepIds[i]}><td>{constructed}</td><td>{morphed}</td><td>{destroyed}</td></React.Fragment>;
+                })}
+            </tr>)}
+            <tr>
+                <td>cost</td>
+                {impacts.map((impact, i) => <td key={stepIds[i]} colSpan={3}>
+                    {impact.planningFailed ? "can't be planned" : `$${impact.constructionCost.toFixed(0)}`}
+                </td>)}
+            </tr>
+        </tbody>
+    </table>;
+}
+
+// Shows all steps of a project as a tree of branches, to jump between them and compare them
+export function ProjectHistory({ state, currentProject, mayEdit }:
+    { state: SharedState, currentProject: string, mayEdit: boolean }) {
+
+    const [comparedSteps, setComparedSteps] = useState<string[]>([]);
+
+    useEffect(() => {
+        for (const stepId of comparedSteps) cbRustBrowser.get_step_impact(currentProject, stepId);
+    }, [currentProject, comparedSteps]);
+
+    const project = state.planning.projects[currentProject];
+    if (!project) return null;
+
+    const tree = project.history_tree;
+    const currentLine = new Set(project.undoable_history.map(plan => plan.step_id));
+    const currentStep = project.undoable_history.length > 0
+        && project.undoable_history[project.undoable_history.length - 1].step_id;
+
+    const childrenOf = (parent: string | null) => Object.keys(tree)
+        .filter(stepId => tree[stepId].parent === parent)
+        .sort((a, b) => tree[a].created - tree[b].created);
+
+    const toggleCompared = (stepId: string, compared: boolean) => setComparedSteps(
+        compared ? [...comparedSteps, stepId].slice(-MAX_COMPARED_STEPS) : comparedSteps.filter(id => id !== stepId));
+
+    const renderSteps = (parent: string | null, depth: number) => childrenOf(parent).map(stepId => {
+        const step: HistoryStep = tree[stepId];
+        const nGestures = Object.keys(step.plan.gestures).length;
+        return <React.Fragment key={stepId}>
+            <p style={{ marginLeft: `${depth * 12}px`, fontWeight: stepId === currentStep ? 'bold' : 'normal', opacity: currentLine.has(stepId) ? 1 : 0.6 }}>
+                <Checkbox checked={comparedSteps.includes(stepId)} onChange={e => toggleCompared(stepId, e.target.checked)} />
+                {" "}Step {step.created + 1} ({nGestures} {nGestures === 1 ? "gesture" : "gestures"})
+                {stepId !== currentStep &&
+                    <Button size="small" disabled={!mayEdit} onClick={() => cbRustBrowser.jump_to_step(currentProject, stepId)}>Jump here</Button>}
+            </p>
+            {renderSteps(stepId, childrenOf(stepId).length > 1 ? depth + 1 : depth)}
+        </React.Fragment>;
+    });
+
+    return <div className="project-history">
+        {Object.keys(tree).length > 0 ? renderSteps(null, 0) : <p>No steps yet</p>}
+        {comparedSteps.length === MAX_COMPARED_STEPS && <StepImpactTable state={state} stepIds={comparedSteps} />}
+    </div>;
+}
@@ -0,0 +1,89 @@
+import * as React from 'react';
+import { useState, useCallback } from 'react';
+import { Button, Icon, Input, InputNumber } from 'antd';
+
+import { SharedState } from '../citybound';
+import { gesturePositions, Position } from './projectFiles';
+
+const MAX_TEMPLATE_NAME_LENGTH = 16;
+
+// Turns the current gestures of a project into a template centered around their centroid
+function templateFromProject(project) {
+    const gestures = Object.values(Object.assign({},
+        ...[...project.undoable_history, project.ongoing].map(plan => plan.gestures)));
+    const intents = gestures.map((gesture: any) => gesture.intent);
+
+    const positions = intents.flatMap(gesturePositions);
+    if (positions.length === 0) return null;
+    const center = [0, 1].map(axis =>
+        positions.reduce((sum, position) => sum + position[axis], 0) / positions.length);
+    const relative = (position: Position) => [position[0] - center[0], position[1] - center[1]];
+
+    return {
+        gestures: intents.map(intent => {
+            if (intent.Road) return {
+                Road: [intent.Road.path.corners.map(corner => relative(corner.position)), intent.Road.lane_config]
+            };
+            if (intent.Zone) return {
+                Zone: [intent.Zone.boundary.corners.map(corner => relative(corner.position)), intent.Zone.config]
+            };
+            if (intent.Plant && intent.Plant.Individual) return {
+                Plant: [relative(intent.Plant.Individual.position), intent.Plant.Individual.vegetation_type]
+            };
+            return null;
+        }).filter(gesture => gesture)
+    };
+}
+
+export function TemplateLibrary({ state, currentProject, mayEdit }:
+    { state: SharedState, currentProject: string | null, mayEdit: boolean }) {
+
+    const [rotation, setRotation] = useState(0);
+    const [scale, setScale] = useState(1);
+    const [newTemplateName, setNewTemplateName] = useState("");
+
+    const { templates } = state.planning;
+    const project = currentProject && state.planning.projects[currentProject];
+
+    const placeTemplate = useCallback((name: string) => {
+        if (!currentProject || !mayEdit) return;
+        const cameraTarget = state.camera.target;
+        cbRustBrowser.place_template(
+            currentProject, templates[name], [cameraTarget[0], cameraTarget[1]],
+            rotation * Math.PI / 180, scale
+        );
+    }, [currentProject, mayEdit, templates, state.camera.target, rotation, scale]);
+
+    const saveTemplate = useCallback(() => {
+        const template = project && templateFromProject(project);
+        if (template && newTemplateName) {
+            cbRustBrowser.save_template(newTemplateName, template);
+            setNewTemplateName("");
+        }
+    }, [project, newTemplateName]);
+
+    return <div className="template-library">
+        <p>
+            Rotation <InputNumber size="small" value={rotation} step={15} formatter={value => `${value}°`}
+                parser={value => value.replace('°', '')} onChange={value => setRotation(value || 0)} />
+            {" "}Scale <InputNumber size="small" value={scale} min={0.1} max={10} step={0.1}
+                onChange={value => setScale(value || 1)} />
+        </p>
+        {Object.keys(templates).sort().map(name =>
+            <p key={name}>
+                <Button size="small" disabled={!currentProject || !mayEdit} onClick={() => placeTemplate(name)}>
+                    Place '{name}'
+                </Button>
+                <Icon type="delete" style={{ marginLeft: '8px', cursor: 'pointer' }}
+                    onClick={() => cbRustBrowser.remove_template(name)} />
+            </p>
+        )}
+        {project && <Input.Group compact>
+            <Input size="small" style={{ width: '60%' }} placeholder="Template name"
+                maxLength={MAX_TEMPLATE_NAME_LENGTH} value={newTemplateName}
+                onChange={event => setNewTemplateName(event.target.value)} />
+            <Button size="small" disabled={!newTemplateName} onClick={saveTemplate}>Save project</Button>
+        </Input.Group>}
+        <p>Templates are placed around the center of the view.</p>
+    </div>;
+}
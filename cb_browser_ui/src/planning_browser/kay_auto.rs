@@ -66,6 +66,12 @@ impl BrowserPlanningUIID {
 #[derive(Copy, Clone)] #[allow(non_camel_case_types)]
 struct MSG_BrowserPlanningUI_spawn(pub BrowserPlanningUIID, );
 
+impl Into<ConfigUserID<GestureTemplate>> for BrowserPlanningUIID {
+    fn into(self) -> ConfigUserID<GestureTemplate> {
+        ConfigUserID::from_raw(self.as_raw())
+    }
+}
+
 impl Into<FrameListenerID> for BrowserPlanningUIID {
     fn into(self) -> FrameListenerID {
         FrameListenerID::from_raw(self.as_raw())
@@ -78,12 +84,20 @@ impl Into<PlanningUIID<CBPlanningLogic>> for BrowserPlanningUIID {
     }
 }
 
+impl Into<ConstructionUIID<CBPrototypeKind>> for BrowserPlanningUIID {
+    fn into(self) -> ConstructionUIID<CBPrototypeKind> {
+        ConstructionUIID::from_raw(self.as_raw())
+    }
+}
+
 #[allow(unused_variables)]
 #[allow(unused_mut)]
 pub fn auto_setup(system: &mut ActorSystem) {
     
+    ConfigUserID::<GestureTemplate>::register_implementor::<BrowserPlanningUI>(system);
     FrameListenerID::register_implementor::<BrowserPlanningUI>(system);
     PlanningUIID::<CBPlanningLogic>::register_implementor::<BrowserPlanningUI>(system);
+    ConstructionUIID::<CBPrototypeKind>::register_implementor::<BrowserPlanningUI>(system);
     system.add_spawner::<BrowserPlanningUI, _, _>(
         |&MSG_BrowserPlanningUI_spawn(id, ), world| {
             BrowserPlanningUI::spawn(id, world)
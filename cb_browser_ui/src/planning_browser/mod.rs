@@ -1,22 +1,28 @@
 use stdweb::serde::Serde;
 use kay::{World, Actor, External, ActorSystem, TypedID};
-use compact::{CHashMap};
-use std::collections::HashMap;
+use compact::{CVec, CHashMap, COption, CString};
+use std::collections::{HashMap, HashSet};
 use descartes::{LinePath, P2, EditArcLinePath, ResolutionStrategy, Closedness};
 use michelangelo::{MeshGrouper};
 use cb_planning::{
     Project, GestureID, PrototypeID, PlanHistory, PlanResult, PlanHistoryUpdate, ProjectUpdate,
-    PlanResultUpdate, ActionGroups,
+    PlanResultUpdate, ActionGroups, ProjectImpact, StepID,
 };
 use cb_planning::plan_manager::ProjectID;
 use cb_planning::plan_manager::ui::{PlanningUI, PlanningUIID};
-use planning::{CBPlanningLogic, CBPlanManagerID, CBGestureIntent, CBPrototypeKind};
+use cb_planning::plan_manager::collaboration::ProjectAccess;
+use cb_planning::construction::ConstructionSite;
+use cb_planning::construction::ui::{ConstructionUI, ConstructionUIID};
+use planning::{CBPlanningLogic, CBPlanManagerID, CBConstructionID, CBGestureIntent, CBPrototypeKind};
+use planning::templates::GestureTemplate;
+use cb_util::config_manager::{Name, ConfigUser, ConfigUserID, ConfigManagerID};
+use cb_util::client_keys::{ClientID, ClientKey};
 use land_use::zone_planning::{LandUse, LAND_USES, ZoneIntent, ZoneConfig};
 use browser_utils::{updated_groups_to_js, to_js_mesh, FrameListener, FrameListenerID};
 
 #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
 use stdweb::js_export;
-use SYSTEM;
+use {SYSTEM, client_key, set_client_key};
 
 #[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
 pub fn start_new_gesture(
@@ -30,6 +36,8 @@ pub fn start_new_gesture(
         project_id.0,
         gesture_id.0,
         intent.0,
+        BrowserPlanningUIID::local_first(world).into(),
+        client_key(),
         world,
     )
 }
@@ -73,6 +81,8 @@ pub fn set_intent(
         gesture_id.0,
         intent.0,
         commit,
+        BrowserPlanningUIID::local_first(world).into(),
+        client_key(),
         world,
     )
 }
@@ -81,28 +91,196 @@ pub fn set_intent(
 pub fn undo(project_id: Serde<ProjectID>) {
     let system = unsafe { &mut *SYSTEM };
     let world = &mut system.world();
-    CBPlanManagerID::global_first(world).undo(project_id.0, world)
+    CBPlanManagerID::global_first(world).undo(
+        project_id.0,
+        BrowserPlanningUIID::local_first(world).into(),
+        client_key(),
+        world,
+    )
 }
 
 #[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
 pub fn redo(project_id: Serde<ProjectID>) {
     let system = unsafe { &mut *SYSTEM };
     let world = &mut system.world();
-    CBPlanManagerID::global_first(world).redo(project_id.0, world)
+    CBPlanManagerID::global_first(world).redo(
+        project_id.0,
+        BrowserPlanningUIID::local_first(world).into(),
+        client_key(),
+        world,
+    )
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn jump_to_step(project_id: Serde<ProjectID>, step_id: Serde<StepID>) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    CBPlanManagerID::global_first(world).jump_to_step(
+        project_id.0,
+        step_id.0,
+        BrowserPlanningUIID::local_first(world).into(),
+        client_key(),
+        world,
+    )
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn implement_project(project_id: Serde<ProjectID>, confirmed_overlaps: Serde<Vec<ProjectID>>) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    CBPlanManagerID::global_first(world).implement(
+        project_id.0,
+        confirmed_overlaps.0.into(),
+        BrowserPlanningUIID::local_first(world).into(),
+        client_key(),
+        world,
+    );
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn import_project(project_id: Serde<ProjectID>, project: Serde<Project<CBGestureIntent>>) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    CBPlanManagerID::global_first(world).import_project(
+        project_id.0,
+        project.0,
+        BrowserPlanningUIID::local_first(world).into(),
+        client_key(),
+        world,
+    );
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn place_template(
+    project_id: Serde<ProjectID>,
+    template: Serde<GestureTemplate>,
+    position: Serde<P2>,
+    rotation: Serde<f32>,
+    scale: Serde<f32>,
+) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    let new_gestures = template
+        .0
+        .placed(position.0, rotation.0, scale.0)
+        .into_iter()
+        .map(|intent| (GestureID::new(), intent))
+        .collect();
+    CBPlanManagerID::global_first(world).start_new_gestures(
+        project_id.0,
+        new_gestures,
+        BrowserPlanningUIID::local_first(world).into(),
+        client_key(),
+        world,
+    );
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn save_template(name: String, template: Serde<GestureTemplate>) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    if let Ok(name) = Name::from(&name) {
+        ConfigManagerID::<GestureTemplate>::global_first(world).update_own_entry(
+            name,
+            COption(Some(template.0)),
+            BrowserPlanningUIID::local_first(world).into(),
+            client_key(),
+            world,
+        );
+    }
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn remove_template(name: String) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    if let Ok(name) = Name::from(&name) {
+        ConfigManagerID::<GestureTemplate>::global_first(world).update_own_entry(
+            name,
+            COption(None),
+            BrowserPlanningUIID::local_first(world).into(),
+            client_key(),
+            world,
+        );
+    }
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn set_project_locked(project_id: Serde<ProjectID>, locked: bool) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    CBPlanManagerID::global_first(world).set_project_locked(
+        project_id.0,
+        locked,
+        BrowserPlanningUIID::local_first(world).into(),
+        client_key(),
+        world,
+    );
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn resolve_conflict(
+    project_id: Serde<ProjectID>,
+    gesture_id: Serde<GestureID>,
+    keep_project_version: bool,
+) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    CBPlanManagerID::global_first(world).resolve_conflict(
+        project_id.0,
+        gesture_id.0,
+        keep_project_version,
+        BrowserPlanningUIID::local_first(world).into(),
+        client_key(),
+        world,
+    );
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn approve_project(project_id: Serde<ProjectID>) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    CBPlanManagerID::global_first(world).approve_project(
+        project_id.0,
+        BrowserPlanningUIID::local_first(world).into(),
+        client_key(),
+        world,
+    );
 }
 
 #[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
-pub fn implement_project(project_id: Serde<ProjectID>) {
+pub fn get_project_impact(project_id: Serde<ProjectID>) {
     let system = unsafe { &mut *SYSTEM };
     let world = &mut system.world();
-    CBPlanManagerID::global_first(world).implement(project_id.0, world);
+    CBPlanManagerID::global_first(world).get_project_impact(
+        BrowserPlanningUIID::local_first(world).into(),
+        project_id.0,
+        world,
+    );
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn get_step_impact(project_id: Serde<ProjectID>, step_id: Serde<StepID>) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    CBPlanManagerID::global_first(world).get_step_impact(
+        BrowserPlanningUIID::local_first(world).into(),
+        project_id.0,
+        step_id.0,
+        world,
+    );
 }
 
 #[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
 pub fn start_new_project(project_id: Serde<ProjectID>) {
     let system = unsafe { &mut *SYSTEM };
     let world = &mut system.world();
-    CBPlanManagerID::global_first(world).start_new_project(project_id.0, world);
+    CBPlanManagerID::global_first(world).start_new_project(
+        project_id.0,
+        BrowserPlanningUIID::local_first(world).into(),
+        client_key(),
+        world,
+    );
 }
 
 use transport::transport_planning::{RoadIntent, RoadLaneConfig};
@@ -137,6 +315,7 @@ pub fn new_zone_intent(new_land_use: Serde<LandUse>) -> Serde<ZoneIntent> {
 #[derive(Compact, Clone)]
 pub struct BrowserPlanningUI {
     id: BrowserPlanningUIID,
+    templates: CHashMap<Name, GestureTemplate>,
     state: External<BrowserPlanningUINonPersistedState>,
 }
 
@@ -161,6 +340,9 @@ pub struct BrowserPlanningUINonPersistedState {
     result_preview: PlanResult<CBPrototypeKind>,
     actions_preview: ActionGroups,
     awaiting_preview_update: bool,
+    known_access_version: u32,
+    known_sites_version: u32,
+    construction_site_ids: HashSet<PrototypeID>,
 
     // planning geometry
     lanes_to_construct_grouper: MeshGrouper<PrototypeID>,
@@ -169,6 +351,7 @@ pub struct BrowserPlanningUINonPersistedState {
     zone_groupers: HashMap<LandUse, MeshGrouper<PrototypeID>>,
     zone_outline_groupers: HashMap<LandUse, MeshGrouper<PrototypeID>>,
     building_outlines_grouper: MeshGrouper<PrototypeID>,
+    construction_sites_grouper: MeshGrouper<PrototypeID>,
 }
 
 use descartes::ArcLinePath;
@@ -204,7 +387,7 @@ pub fn static_meshes() -> Vec<(&'static str, Mesh)> {
 }
 
 impl BrowserPlanningUI {
-    pub fn spawn(id: BrowserPlanningUIID, _world: &mut World) -> BrowserPlanningUI {
+    pub fn spawn(id: BrowserPlanningUIID, world: &mut World) -> BrowserPlanningUI {
         {
             for (name, mesh) in static_meshes() {
                 js! {
@@ -217,14 +400,18 @@ impl BrowserPlanningUI {
             }
         }
 
-        BrowserPlanningUI {
+        let ui = BrowserPlanningUI {
             id,
+            templates: CHashMap::new(),
             state: External::new(BrowserPlanningUINonPersistedState {
                 master_plan: PlanHistory::new(),
                 projects: HashMap::new(),
                 result_preview: PlanResult::new(),
                 actions_preview: ActionGroups::new(),
                 awaiting_preview_update: false,
+                known_access_version: 0,
+                known_sites_version: 0,
+                construction_site_ids: HashSet::new(),
                 lanes_to_construct_grouper: MeshGrouper::new(2000),
                 lanes_to_construct_marker_grouper: MeshGrouper::new(2000),
                 lanes_to_construct_marker_gaps_grouper: MeshGrouper::new(2000),
@@ -237,8 +424,40 @@ impl BrowserPlanningUI {
                     .map(|land_use| (*land_use, MeshGrouper::new(2000)))
                     .collect(),
                 building_outlines_grouper: MeshGrouper::new(2000),
+                construction_sites_grouper: MeshGrouper::new(2000),
             }),
+        };
+        ui.get_initial_config(world);
+        ui
+    }
+}
+
+impl ConfigUser<GestureTemplate> for BrowserPlanningUI {
+    fn local_cache(&mut self) -> &mut CHashMap<Name, GestureTemplate> {
+        &mut self.templates
+    }
+
+    fn on_config_change(
+        &mut self,
+        name: Name,
+        maybe_value: &COption<GestureTemplate>,
+        world: &mut World,
+    ) {
+        if let COption(Some(ref template)) = *maybe_value {
+            js! {
+                window.cbReactApp.boundSetState(oldState => update(oldState, {
+                    planning: {templates: {[@{name.as_str()}]: {"$set": @{Serde(template)}}}}
+                }));
+            }
+        } else {
+            js! {
+                window.cbReactApp.boundSetState(oldState => update(oldState, {
+                    planning: {templates: {"$unset": [@{name.as_str()}]}}
+                }));
+            }
         }
+
+        self.apply_config_change(name, maybe_value, world);
     }
 }
 
@@ -253,6 +472,13 @@ impl FrameListener for BrowserPlanningUI {
                 .iter()
                 .map(|(project_id, project)| (*project_id, project.as_known_state()))
                 .collect(),
+            self.known_access_version,
+            world,
+        );
+
+        CBConstructionID::global_first(world).get_construction_sites(
+            self.id_as(),
+            self.known_sites_version,
             world,
         );
 
@@ -350,6 +576,7 @@ impl PlanningUI<CBPlanningLogic> for BrowserPlanningUI {
         effective_history: &PlanHistory<CBGestureIntent>,
         result_update: &PlanResultUpdate<CBPrototypeKind>,
         new_actions: &ActionGroups,
+        construction_cost: f32,
         _world: &mut World,
     ) {
         use transport::transport_planning::{
@@ -631,6 +858,7 @@ impl PlanningUI<CBPlanningLogic> for BrowserPlanningUI {
                         },
                     },
                     roadInfos: {"$set": @{Serde(road_infos)}},
+                    constructionCost: {"$set": @{construction_cost}},
                 }}
             }));
         }
@@ -639,6 +867,190 @@ impl PlanningUI<CBPlanningLogic> for BrowserPlanningUI {
         self.actions_preview = new_actions.clone();
         self.awaiting_preview_update = false;
     }
+
+    fn on_project_access_update(
+        &mut self,
+        project_access: &CHashMap<ProjectID, ProjectAccess>,
+        access_version: u32,
+        approval_required: bool,
+        own_client: ClientID,
+        own_key: ClientKey,
+        _world: &mut World,
+    ) {
+        set_client_key(own_key);
+
+        let access: HashMap<ProjectID, ProjectAccess> = project_access
+            .pairs()
+            .map(|(project_id, access)| (*project_id, access.clone()))
+            .collect();
+
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                planning: {access: {"$set": {
+                    projects: @{Serde(access)},
+                    approvalRequired: @{approval_required},
+                    ownClient: @{own_client},
+                }}}
+            }));
+        }
+
+        self.known_access_version = access_version;
+    }
+
+    fn on_project_rejected(&mut self, project_id: ProjectID, reason: &CString, _world: &mut World) {
+        let reason: &str = reason;
+
+        js! {
+            const projectId = @{Serde(project_id)};
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                planning: {
+                    currentProject: {"$apply": current => current === projectId ? null : current},
+                    projectError: {"$set": "Couldn't create project: " + @{reason}},
+                }
+            }));
+        }
+    }
+
+    fn on_project_impact(
+        &mut self,
+        project_id: ProjectID,
+        impact: &ProjectImpact,
+        world: &mut World,
+    ) {
+        use land_use::buildings::BuildingID;
+        use land_use_browser::BrowserLandUseUIID;
+
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                planning: {impacts: {[@{Serde(project_id)}]: {"$set": {
+                    categories: @{Serde(category_infos(impact))},
+                    constructionCost: @{impact.construction_cost},
+                    overlappingProjects: @{Serde(&impact.overlapping_projects)},
+                    conflictingGestures: @{Serde(&impact.conflicting_gestures)},
+                    planningFailed: @{impact.planning_failed},
+                    demolishedBuildings: {}
+                }}}}
+            }));
+        }
+
+        // buildings only know whether they would be demolished themselves
+        if !impact.destroyed_prototypes.is_empty() {
+            BuildingID::global_broadcast(world).report_if_demolished(
+                project_id,
+                impact.destroyed_prototypes.clone(),
+                BrowserLandUseUIID::local_first(world).into(),
+                world,
+            );
+        }
+    }
+
+    fn on_step_impact(
+        &mut self,
+        _project_id: ProjectID,
+        step_id: StepID,
+        impact: &ProjectImpact,
+        _world: &mut World,
+    ) {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                planning: {stepImpacts: {[@{Serde(step_id)}]: {"$set": {
+                    categories: @{Serde(category_infos(impact))},
+                    constructionCost: @{impact.construction_cost},
+                    planningFailed: @{impact.planning_failed}
+                }}}}
+            }));
+        }
+    }
+}
+
+impl ConstructionUI<CBPrototypeKind> for BrowserPlanningUI {
+    fn on_construction_sites_update(
+        &mut self,
+        sites: &CVec<ConstructionSite<CBPrototypeKind>>,
+        sites_version: u32,
+        _world: &mut World,
+    ) {
+        use transport::transport_planning::{RoadPrototype, LanePrototype, IntersectionPrototype};
+        use transport::ui::lane_mesh;
+        use land_use::zone_planning::LotPrototype;
+
+        let site_ids: HashSet<PrototypeID> = sites.iter().map(|site| site.prototype.id).collect();
+
+        let sites_rem = self
+            .construction_site_ids
+            .difference(&site_ids)
+            .cloned()
+            .collect();
+
+        let sites_add = sites
+            .iter()
+            .filter(|site| !self.construction_site_ids.contains(&site.prototype.id))
+            .filter_map(|site| {
+                let mesh = match site.prototype.kind {
+                    CBPrototypeKind::Road(RoadPrototype::Lane(LanePrototype(ref lane_path, _))) => {
+                        lane_mesh(lane_path)
+                    }
+                    CBPrototypeKind::Road(RoadPrototype::Intersection(IntersectionPrototype {
+                        ref connecting_lanes,
+                        ..
+                    })) => {
+                        let mut intersection_mesh = Mesh::empty();
+                        for &LanePrototype(ref lane_path, _) in
+                            connecting_lanes.values().flat_map(|lanes| lanes)
+                        {
+                            intersection_mesh += lane_mesh(lane_path);
+                        }
+                        intersection_mesh
+                    }
+                    CBPrototypeKind::Road(RoadPrototype::PavedArea(ref area)) => {
+                        Mesh::from_area(area)
+                    }
+                    CBPrototypeKind::Lot(LotPrototype { ref lot, .. }) => {
+                        Mesh::from_area(&lot.area)
+                    }
+                    _ => return None,
+                };
+                Some((site.prototype.id, mesh))
+            })
+            .collect();
+
+        let updated_construction_site_groups =
+            self.construction_sites_grouper.update(sites_rem, sites_add);
+
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                planning: {rendering: {
+                    constructionSiteGroups: {
+                        "$add": @{updated_groups_to_js(updated_construction_site_groups)}
+                    }
+                }}
+            }));
+        }
+
+        self.construction_site_ids = site_ids;
+        self.known_sites_version = sites_version;
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct CategoryInfo {
+    category: String,
+    constructed: u32,
+    morphed: u32,
+    destroyed: u32,
+}
+
+fn category_infos(impact: &ProjectImpact) -> Vec<CategoryInfo> {
+    impact
+        .categories
+        .iter()
+        .map(|category_impact| CategoryInfo {
+            category: category_impact.category.to_string(),
+            constructed: category_impact.constructed,
+            morphed: category_impact.morphed,
+            destroyed: category_impact.destroyed,
+        })
+        .collect()
 }
 
 mod kay_auto;
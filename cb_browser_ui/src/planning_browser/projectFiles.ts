@@ -0,0 +1,119 @@
+const PROJECT_FILE_VERSION = 2;
+
+export type Position = [number, number];
+
+export function gesturePositions(intent): Position[] {
+    if (intent.Road) return intent.Road.path.corners.map(corner => corner.position);
+    if (intent.Zone) return intent.Zone.boundary.corners.map(corner => corner.position);
+    if (intent.Plant && intent.Plant.Individual) return [intent.Plant.Individual.position];
+    return [];
+}
+
+function translatedCorners(path, offset: Position) {
+    return Object.assign({}, path, {
+        corners: path.corners.map(corner => Object.assign({}, corner, {
+            position: [corner.position[0] + offset[0], corner.position[1] + offset[1]]
+        }))
+    });
+}
+
+// Buildings and natural growth are left to the simulation of the importing city,
+// so only intents that can be moved around freely are kept
+function translatedIntent(intent, offset: Position) {
+    if (intent.Road) return { Road: Object.assign({}, intent.Road, { path: translatedCorners(intent.Road.path, offset) }) };
+    if (intent.Zone) return { Zone: Object.assign({}, intent.Zone, { boundary: translatedCorners(intent.Zone.boundary, offset) }) };
+    if (intent.Plant && intent.Plant.Individual) {
+        const plant = intent.Plant.Individual;
+        return {
+            Plant: {
+                Individual: Object.assign({}, plant, {
+                    position: [plant.position[0] + offset[0], plant.position[1] + offset[1]]
+                })
+            }
+        };
+    }
+    return null;
+}
+
+function allPlans(project) {
+    return [...project.undoable_history, project.ongoing, ...project.redoable_history];
+}
+
+// Version 1 files only have the current line of steps, without earlier branches
+function historyTreeOfLine(project) {
+    const line = [...project.undoable_history, ...[...project.redoable_history].reverse()];
+    const tree = {};
+    line.forEach((plan, i) => {
+        tree[plan.step_id] = { plan, parent: i > 0 ? line[i - 1].step_id : null, created: i };
+    });
+    return tree;
+}
+
+export function downloadProject(project, name: string) {
+    const blob = new Blob(
+        [JSON.stringify({ version: PROJECT_FILE_VERSION, project }, null, 2)],
+        { type: 'application/json' }
+    );
+    const link = document.createElement('a');
+    link.href = URL.createObjectURL(blob);
+    link.download = `${name}.cbproject.json`;
+    link.click();
+    URL.revokeObjectURL(link.href);
+}
+
+// Parses an exported project and moves it so that it is centered around `center`,
+// throws if the file isn't a valid project file
+function parsedProject(text: string, center: Position) {
+    const { version, project } = JSON.parse(text);
+    if (version === 1) {
+        project.history_tree = historyTreeOfLine(project);
+    } else if (version !== PROJECT_FILE_VERSION) {
+        throw new Error(`Unsupported project file version ${version}`);
+    }
+
+    const positions = allPlans(project).flatMap(plan =>
+        Object.values(plan.gestures).flatMap((gesture: any) => gesturePositions(gesture.intent)));
+    const projectCenter = positions.length > 0
+        ? [0, 1].map(axis => positions.reduce((sum, position) => sum + position[axis], 0) / positions.length)
+        : center;
+    const offset: Position = [center[0] - projectCenter[0], center[1] - projectCenter[1]];
+
+    const translatedPlan = plan => {
+        const gestures = {};
+        for (const gestureId of Object.keys(plan.gestures)) {
+            const intent = translatedIntent(plan.gestures[gestureId].intent, offset);
+            if (intent) gestures[gestureId] = Object.assign({}, plan.gestures[gestureId], { intent });
+        }
+        return Object.assign({}, plan, { gestures });
+    };
+
+    const historyTree = {};
+    for (const stepId of Object.keys(project.history_tree)) {
+        const step = project.history_tree[stepId];
+        historyTree[stepId] = Object.assign({}, step, { plan: translatedPlan(step.plan) });
+    }
+
+    return Object.assign({}, project, {
+        undoable_history: project.undoable_history.map(translatedPlan),
+        ongoing: translatedPlan(project.ongoing),
+        redoable_history: project.redoable_history.map(translatedPlan),
+        history_tree: historyTree,
+        based_on: {}
+    });
+}
+
+// Reads an exported project and moves it so that it is centered around `center`
+export function readProjectFile(file: File, center: Position): Promise<any> {
+    return new Promise((resolve, reject) => {
+        const reader = new FileReader();
+        reader.onerror = () => reject(reader.error);
+        reader.onload = () => {
+            try {
+                resolve(parsedProject(reader.result as string, center));
+            } catch (e) {
+                reject(e);
+            }
+        };
+        reader.readAsText(file);
+    });
+}
@@ -6,6 +6,7 @@ export default {
     addedGesturesZones: i++,
     addedGesturesZonesStipple: i++,
     addedGesturesZonesOutlines: i++,
+    constructionSites: i++,
     asphalt: i++,
     asphaltMarker: i++,
     asphaltMarkerGap: i++,
@@ -1,30 +1,69 @@
 import React from 'react';
-import { Slider } from 'antd';
+import { Slider, Switch, Tooltip, Button } from 'antd';
 import update from 'immutability-helper';
 
 export const initialState = {
     ticks: 0,
     time: [0, 0],
-    speed: 1
+    date: "",
+    speed: 1,
+    paused: false,
+    fastForward: false,
+    maxSpeed: 32,
+    mayControl: true
 }
 
+const STEP_TICKS = 3 * 60;
+
 export function Windows(props) {
     const { state, setState } = props;
+    const maxSpeedLog = Math.floor(Math.log2(Math.max(state.time.maxSpeed, 1)));
+    const disabled = !state.time.mayControl;
 
     return <div className="sim-time">
+        <span className="sim-date">{state.time.date}</span>
         {(state.time.time[0] + "").padStart(2, "0")}
         <span className="sim-time-colon">:</span>
         {(state.time.time[1] + "").padStart(2, "0")}
-        <Slider className="sim-speed"
-            value={state.time.speed == 0 ? 0 : Math.log2(state.time.speed) + 1}
-            min={0} max={6}
-            marks={{ 0: "||", 1: "1x", 3: "4x", 6: "32x" }}
+        <Slider className="sim-speed" {...{ disabled }}
+            value={Math.log2(Math.max(state.time.speed, 1))}
+            min={0} max={maxSpeedLog}
+            marks={{ 0: "1x", 2: "4x", [maxSpeedLog]: `${Math.pow(2, maxSpeedLog)}x` }}
             onChange={newSpeedLog => {
-                const newSpeed = newSpeedLog == 0 ? 0 : Math.pow(2, newSpeedLog - 1);
+                const newSpeed = Math.pow(2, newSpeedLog);
                 cbRustBrowser.set_sim_speed(newSpeed);
                 setState(oldState => update(oldState, { time: { speed: { $set: newSpeed } } }));
             }}
-            tipFormatter={speed => speed ? `Speed: ${Math.pow(2, speed - 1)}x` : "Pause"}
+            tipFormatter={speedLog => `Speed: ${Math.pow(2, speedLog)}x`}
         />
+        <div className="sim-controls">
+            <Button size="small" {...{ disabled }}
+                icon={state.time.paused ? "caret-right" : "pause"}
+                onClick={() => {
+                    if (state.time.paused) {
+                        cbRustBrowser.resume_sim();
+                    } else {
+                        cbRustBrowser.pause_sim();
+                    }
+                    setState(oldState => update(oldState, { time: { paused: { $set: !state.time.paused } } }));
+                }}
+            />
+            <Tooltip title="Advance by one minute">
+                <Button size="small" icon="step-forward"
+                    disabled={disabled || !state.time.paused}
+                    onClick={() => cbRustBrowser.step_sim(STEP_TICKS)}
+                />
+            </Tooltip>
+            <Tooltip title="Skip ahead while nobody is travelling">
+                <Switch className="sim-fast-forward" size="small" {...{ disabled }}
+                    checked={state.time.fastForward}
+                    checkedChildren="⏩" unCheckedChildren="⏩"
+                    onChange={fastForward => {
+                        cbRustBrowser.set_sim_fast_forward(fastForward);
+                        setState(oldState => update(oldState, { time: { fastForward: { $set: fastForward } } }));
+                    }}
+                />
+            </Tooltip>
+        </div>
     </div>
-}
\ No newline at end of file
+}
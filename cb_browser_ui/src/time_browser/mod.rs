@@ -2,18 +2,52 @@ use kay::{World, ActorSystem, Actor, TypedID};
 use stdweb::serde::Serde;
 #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
 use stdweb::js_export;
-use SYSTEM;
+use {SYSTEM, client_key, set_client_key};
 use browser_utils::{FrameListener, FrameListenerID};
 
 use cb_time::actors::TimeID;
 use cb_time::actors::ui::{TimeUI, TimeUIID};
-use cb_time::units::{TimeOfDay, Instant};
+use cb_time::units::{TimeOfDay, Instant, Date};
+use cb_util::client_keys::ClientKey;
 
 #[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
 pub fn set_sim_speed(new_speed: u16) {
     let system = unsafe { &mut *SYSTEM };
     let world = &mut system.world();
-    TimeID::global_first(world).set_speed(new_speed, world);
+    let requester = BrowserTimeUIID::local_first(world).into();
+    TimeID::global_first(world).set_speed(new_speed, requester, client_key(), world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn pause_sim() {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    let requester = BrowserTimeUIID::local_first(world).into();
+    TimeID::global_first(world).pause(requester, client_key(), world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn resume_sim() {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    let requester = BrowserTimeUIID::local_first(world).into();
+    TimeID::global_first(world).resume(requester, client_key(), world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn step_sim(n_ticks: u32) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    let requester = BrowserTimeUIID::local_first(world).into();
+    TimeID::global_first(world).step(n_ticks, requester, client_key(), world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn set_sim_fast_forward(fast_forward: bool) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    let requester = BrowserTimeUIID::local_first(world).into();
+    TimeID::global_first(world).set_fast_forward(fast_forward, requester, client_key(), world);
 }
 
 #[derive(Compact, Clone)]
@@ -34,7 +68,19 @@ impl FrameListener for BrowserTimeUI {
 }
 
 impl TimeUI for BrowserTimeUI {
-    fn on_time_info(&mut self, current_instant: Instant, speed: u16, _world: &mut World) {
+    fn on_time_info(
+        &mut self,
+        current_instant: Instant,
+        speed: u16,
+        paused: bool,
+        fast_forward: bool,
+        max_speed: u16,
+        may_control: bool,
+        key: ClientKey,
+        _world: &mut World,
+    ) {
+        set_client_key(key);
+
         js! {
             window.cbReactApp.boundSetState(oldState => update(oldState, {
                 time: {
@@ -42,7 +88,12 @@ impl TimeUI for BrowserTimeUI {
                     time: {"$set": @{
                         Serde(TimeOfDay::from(current_instant).hours_minutes())
                     }},
-                    speed: {"$set": @{speed}}
+                    date: {"$set": @{Date::from(current_instant).to_string()}},
+                    speed: {"$set": @{speed}},
+                    paused: {"$set": @{paused}},
+                    fastForward: {"$set": @{fast_forward}},
+                    maxSpeed: {"$set": @{max_speed}},
+                    mayControl: {"$set": @{may_control}}
                 }
             }))
         }
@@ -78,12 +78,19 @@ impl Into<VegetationUIID> for BrowserVegetationUIID {
     }
 }
 
+impl Into<WeatherListenerID> for BrowserVegetationUIID {
+    fn into(self) -> WeatherListenerID {
+        WeatherListenerID::from_raw(self.as_raw())
+    }
+}
+
 #[allow(unused_variables)]
 #[allow(unused_mut)]
 pub fn auto_setup(system: &mut ActorSystem) {
     
     FrameListenerID::register_implementor::<BrowserVegetationUI>(system);
     VegetationUIID::register_implementor::<BrowserVegetationUI>(system);
+    WeatherListenerID::register_implementor::<BrowserVegetationUI>(system);
     system.add_spawner::<BrowserVegetationUI, _, _>(
         |&MSG_BrowserVegetationUI_spawn(id, ), world| {
             BrowserVegetationUI::spawn(id, world)
@@ -1,6 +1,8 @@
 use kay::{World, ActorSystem, TypedID, RawID, External};
 use environment::vegetation::{PlantID, PlantPrototype, VegetationType};
 use environment::vegetation::ui::{VegetationUI, VegetationUIID};
+use environment::weather::{WeatherID, WeatherListener, WeatherListenerID, WeatherConditions};
+use cb_time::units::DayOfYear;
 use browser_utils::to_js_mesh;
 use descartes::{P2, LinePath, ClosedLinePath, PrimitiveArea};
 use michelangelo::{Sculpture, FlatSurface, Instance};
@@ -32,6 +34,7 @@ pub struct BrowserVegetationUINonPersistedState {
     instances_current: bool,
     trunk_color: [f32; 3],
     canopy_color: [f32; 3],
+    day: Option<DayOfYear>,
 }
 
 impl BrowserVegetationUI {
@@ -135,6 +138,7 @@ impl BrowserVegetationUI {
 
         {
             PlantID::global_broadcast(world).get_render_info(id.into(), world);
+            WeatherID::global_first(world).get_info(id.into(), world);
         }
 
         BrowserVegetationUI {
@@ -144,6 +148,7 @@ impl BrowserVegetationUI {
                 instances_current: true,
                 trunk_color: [0.0, 0.0, 0.0],
                 canopy_color: [0.0, 0.0, 0.0],
+                day: None,
             }),
         }
     }
@@ -170,17 +175,26 @@ impl FrameListener for BrowserVegetationUI {
             self.instances_current = false;
         }
 
-        let new_canopy_color_js: Vec<f64> = js! {
-            return require("../../../src/colors").default.canopies;
+        let canopy_colors_js: Vec<Vec<f64>> = js! {
+            const colors = require("../../../src/colors").default;
+            return [colors.canopies, colors.canopiesAutumn, colors.canopiesWinter];
         }
         .try_into()
         .unwrap();
 
-        let new_canopy_color = [
-            new_canopy_color_js[0] as f32,
-            new_canopy_color_js[1] as f32,
-            new_canopy_color_js[2] as f32,
-        ];
+        let to_color =
+            |color_js: &Vec<f64>| [color_js[0] as f32, color_js[1] as f32, color_js[2] as f32];
+        let summer_color = to_color(&canopy_colors_js[0]);
+
+        let new_canopy_color = match self.day {
+            Some(day) => seasonal_canopy_color(
+                day,
+                summer_color,
+                to_color(&canopy_colors_js[1]),
+                to_color(&canopy_colors_js[2]),
+            ),
+            None => summer_color,
+        };
 
         if self.canopy_color != new_canopy_color {
             self.canopy_color = new_canopy_color;
@@ -323,6 +337,35 @@ impl VegetationUI for BrowserVegetationUI {
     }
 }
 
+impl WeatherListener for BrowserVegetationUI {
+    fn on_weather(&mut self, day: DayOfYear, _today: WeatherConditions, _: &mut World) {
+        self.state.day = Some(day);
+    }
+}
+
+/// Leaves turn in autumn, trees stay bare over winter and sprout again in early spring
+fn seasonal_canopy_color(
+    day: DayOfYear,
+    summer_color: [f32; 3],
+    autumn_color: [f32; 3],
+    winter_color: [f32; 3],
+) -> [f32; 3] {
+    let fraction = day.fraction_of_year();
+    let autumn_amount = (1.0 - ((fraction - 0.6) / 0.1).abs()).max(0.0);
+    let winter_amount = if fraction < 0.06 {
+        1.0 - fraction / 0.06
+    } else {
+        ((fraction - 0.65) / 0.1).max(0.0).min(1.0)
+    };
+
+    let mut color = [0.0; 3];
+    for i in 0..3 {
+        let leaf_color = summer_color[i] + autumn_amount * (autumn_color[i] - summer_color[i]);
+        color[i] = leaf_color + winter_amount * (winter_color[i] - leaf_color);
+    }
+    color
+}
+
 mod kay_auto;
 pub use self::kay_auto::*;
 
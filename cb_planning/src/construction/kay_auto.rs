@@ -54,8 +54,8 @@ impl<PK: PrototypeKind> ConstructableID<PK> {
         world.send(self.as_raw(), MSG_Constructable_morph::<PK>(new_prototype, report_to));
     }
     
-    pub fn destruct(self, report_to: ConstructionID < PK >, world: &mut World) {
-        world.send(self.as_raw(), MSG_Constructable_destruct::<PK>(report_to));
+    pub fn destruct(self, report_to: ConstructionID < PK >, current_instant: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_Constructable_destruct::<PK>(report_to, current_instant));
     }
 
     pub fn register_trait(system: &mut ActorSystem) {
@@ -73,8 +73,8 @@ impl<PK: PrototypeKind> ConstructableID<PK> {
         );
         
         system.add_handler::<Act, _, _>(
-            |&MSG_Constructable_destruct::<PK>(report_to), instance, world| {
-                instance.destruct(report_to, world)
+            |&MSG_Constructable_destruct::<PK>(report_to, current_instant), instance, world| {
+                instance.destruct(report_to, current_instant, world)
             }, false
         );
     }
@@ -83,7 +83,7 @@ impl<PK: PrototypeKind> ConstructableID<PK> {
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Constructable_morph<PK: PrototypeKind>(pub Prototype < PK >, pub ConstructionID < PK >);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_Constructable_destruct<PK: PrototypeKind>(pub ConstructionID < PK >);
+struct MSG_Constructable_destruct<PK: PrototypeKind>(pub ConstructionID < PK >, pub Instant);
 
 impl<PK: PrototypeKind> Actor for Construction<PK> {
     type ID = ConstructionID<PK>;
@@ -147,6 +147,10 @@ impl<PK: PrototypeKind> ConstructionID<PK> {
     pub fn implement(self, actions_to_implement: ActionGroups, new_prototypes: CVec < Prototype < PK > >, world: &mut World) {
         world.send(self.as_raw(), MSG_Construction_implement::<PK>(actions_to_implement, new_prototypes));
     }
+    
+    pub fn get_construction_sites(self, ui: ConstructionUIID < PK >, known_sites_version: u32, world: &mut World) {
+        world.send(self.as_raw(), MSG_Construction_get_construction_sites::<PK>(ui, known_sites_version));
+    }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
@@ -155,6 +159,8 @@ struct MSG_Construction_spawn<PK: PrototypeKind>(pub ConstructionID<PK>, );
 struct MSG_Construction_action_done<PK: PrototypeKind>(pub ConstructableID < PK >);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Construction_implement<PK: PrototypeKind>(pub ActionGroups, pub CVec < Prototype < PK > >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Construction_get_construction_sites<PK: PrototypeKind>(pub ConstructionUIID < PK >, pub u32);
 
 impl<PK: PrototypeKind> Into<TemporalID> for ConstructionID<PK> {
     fn into(self) -> TemporalID {
@@ -162,11 +168,18 @@ impl<PK: PrototypeKind> Into<TemporalID> for ConstructionID<PK> {
     }
 }
 
+impl<PK: PrototypeKind> Into<SleeperID> for ConstructionID<PK> {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
 #[allow(unused_variables)]
 #[allow(unused_mut)]
 pub fn auto_setup<PK: PrototypeKind>(system: &mut ActorSystem) {
     ConstructableID::<PK>::register_trait(system);
     TemporalID::register_implementor::<Construction<PK>>(system);
+    SleeperID::register_implementor::<Construction<PK>>(system);
     system.add_spawner::<Construction<PK>, _, _>(
         |&MSG_Construction_spawn::<PK>(id, ), world| {
             Construction::<PK>::spawn(id, world)
@@ -184,4 +197,10 @@ pub fn auto_setup<PK: PrototypeKind>(system: &mut ActorSystem) {
             instance.implement(actions_to_implement, new_prototypes, world); Fate::Live
         }, false
     );
+    
+    system.add_handler::<Construction<PK>, _, _>(
+        |&MSG_Construction_get_construction_sites::<PK>(ui, known_sites_version), instance, world| {
+            instance.get_construction_sites(ui, known_sites_version, world); Fate::Live
+        }, false
+    );
 }
\ No newline at end of file
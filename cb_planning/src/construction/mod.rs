@@ -1,9 +1,11 @@
 use kay::{World, Fate, ActorSystem};
 use compact::{CVec, CHashMap, Compact};
 use ::{PrototypeID, Prototype, Action, ActionGroups};
-use cb_time::actors::{Temporal, TemporalID};
-use cb_time::units::Instant;
+use cb_time::actors::{Temporal, TemporalID, Sleeper, SleeperID, TimeID};
+use cb_time::units::{Instant, Duration};
 use cb_util::log::debug;
+use descartes::P2;
+use std::collections::HashSet;
 const LOG_T: &str = "Construction";
 
 pub trait PrototypeKind: Compact + 'static {
@@ -15,9 +17,35 @@ pub trait PrototypeKind: Compact + 'static {
     ) -> CVec<ConstructableID<Self>>;
 
     fn morphable_from(&self, other: &Self) -> bool;
+
+    /// What it costs to newly construct a prototype of this kind
+    fn construction_cost(&self) -> f32 {
+        0.0
+    }
+
+    /// Called once construction of a new prototype starts, before it possibly spends
+    /// some time as a construction site, for example to pay its construction cost
+    fn begin_construction(&self, _world: &mut World) {}
+
+    /// What this prototype is counted as when summarizing the impact of a project
+    fn impact_category(&self) -> &'static str {
+        "other"
+    }
+
+    /// How long a newly constructed prototype of this kind stays a construction site
+    /// before it is actually constructed
+    fn construction_duration(&self) -> Duration {
+        Duration(0)
+    }
 }
 
-pub trait GestureIntent: Compact + 'static {}
+pub trait GestureIntent: Compact + 'static {
+    /// The area (min and max corner) in which this gesture can influence planning.
+    /// `None` means the gesture influences the whole plan.
+    fn bounds(&self) -> Option<(P2, P2)> {
+        None
+    }
+}
 
 pub trait Constructable<PK: PrototypeKind> {
     fn morph(
@@ -27,7 +55,12 @@ pub trait Constructable<PK: PrototypeKind> {
         world: &mut World,
     );
 
-    fn destruct(&mut self, report_to: ConstructionID<PK>, world: &mut World) -> Fate;
+    fn destruct(
+        &mut self,
+        report_to: ConstructionID<PK>,
+        current_instant: Instant,
+        world: &mut World,
+    ) -> Fate;
 }
 
 impl<PK: PrototypeKind> Prototype<PK> {
@@ -44,6 +77,13 @@ impl<PK: PrototypeKind> Prototype<PK> {
     }
 }
 
+/// A prototype that is being built and doesn't exist in the simulation yet
+#[derive(Compact, Clone)]
+pub struct ConstructionSite<PK: PrototypeKind> {
+    pub prototype: Prototype<PK>,
+    pub finished_at: Instant,
+}
+
 #[derive(Compact, Clone)]
 //#[derive(Clone)]
 pub struct Construction<PK: PrototypeKind> {
@@ -52,6 +92,12 @@ pub struct Construction<PK: PrototypeKind> {
     pending_constructables: CVec<ConstructableID<PK>>,
     queued_action_groups: ActionGroups,
     new_prototypes: CHashMap<PrototypeID, Prototype<PK>>,
+    sites: CVec<ConstructionSite<PK>>,
+    sites_version: u32,
+    /// Actions of already started groups that morph or destruct prototypes which are
+    /// still construction sites (or results of other waiting actions), in order
+    waiting_actions: CVec<Action>,
+    current_instant: Instant,
 }
 
 //mod compact_workaround;
@@ -64,6 +110,10 @@ impl<PK: PrototypeKind> Construction<PK> {
             pending_constructables: CVec::new(),
             queued_action_groups: ActionGroups(CVec::new()),
             new_prototypes: CHashMap::new(),
+            sites: CVec::new(),
+            sites_version: 1,
+            waiting_actions: CVec::new(),
+            current_instant: Instant::new(0),
         }
     }
 
@@ -80,9 +130,22 @@ impl<PK: PrototypeKind> Construction<PK> {
                     .new_prototypes
                     .remove(prototype_id)
                     .expect("Should have prototype to be constructed");
-                let ids = new_prototype.construct(self.id, world);
-                self.constructed.insert(prototype_id, ids.clone());
-                ids
+                new_prototype.kind.begin_construction(world);
+                let duration = new_prototype.kind.construction_duration();
+                if duration > Duration(0) {
+                    let finished_at = self.current_instant + duration;
+                    TimeID::global_first(world).wake_up_at(finished_at, self.id.into(), world);
+                    self.sites.push(ConstructionSite {
+                        prototype: new_prototype,
+                        finished_at,
+                    });
+                    self.sites_version += 1;
+                    CVec::new()
+                } else {
+                    let ids = new_prototype.construct(self.id, world);
+                    self.constructed.insert(prototype_id, ids.clone());
+                    ids
+                }
             }
             Action::Morph(old_protoype_id, new_prototype_id) => {
                 debug(LOG_T, "M ", self.id, world);
@@ -107,7 +170,7 @@ impl<PK: PrototypeKind> Construction<PK> {
                     .remove(prototype_id)
                     .expect("Tried to destruct non-constructed prototype");
                 for id in &ids {
-                    id.destruct(self.id, world);
+                    id.destruct(self.id, self.current_instant, world);
                 }
                 ids
             }
@@ -117,6 +180,51 @@ impl<PK: PrototypeKind> Construction<PK> {
             .extend(new_pending_constructables);
     }
 
+    /// Prototypes that don't exist yet, but that queued actions might refer to
+    fn not_yet_constructed(&self) -> HashSet<PrototypeID> {
+        self.sites
+            .iter()
+            .map(|site| site.prototype.id)
+            .chain(
+                self.waiting_actions
+                    .iter()
+                    .filter_map(|action| match *action {
+                        Action::Morph(_, new_prototype_id) => Some(new_prototype_id),
+                        _ => None,
+                    }),
+            )
+            .collect()
+    }
+
+    fn start_or_wait(&mut self, action: &Action, world: &mut World) {
+        if refers_to_any(action, &self.not_yet_constructed()) {
+            self.waiting_actions.push(action.clone());
+        } else {
+            self.start_action(action, world);
+        }
+    }
+
+    /// Starts waiting actions whose prototypes got constructed, keeping their order
+    fn start_unblocked_actions(&mut self, world: &mut World) {
+        let mut still_not_constructed = self
+            .sites
+            .iter()
+            .map(|site| site.prototype.id)
+            .collect::<HashSet<_>>();
+        let waiting_actions = ::std::mem::replace(&mut self.waiting_actions, CVec::new());
+
+        for action in waiting_actions.iter() {
+            if refers_to_any(action, &still_not_constructed) {
+                if let Action::Morph(_, new_prototype_id) = *action {
+                    still_not_constructed.insert(new_prototype_id);
+                }
+                self.waiting_actions.push(action.clone());
+            } else {
+                self.start_action(action, world);
+            }
+        }
+    }
+
     pub fn implement(
         &mut self,
         actions_to_implement: &ActionGroups,
@@ -131,16 +239,31 @@ impl<PK: PrototypeKind> Construction<PK> {
                 .insert(new_prototype.id, new_prototype.clone());
         }
     }
+
+    pub fn get_construction_sites(
+        &mut self,
+        ui: ConstructionUIID<PK>,
+        known_sites_version: u32,
+        world: &mut World,
+    ) {
+        if known_sites_version != self.sites_version {
+            ui.on_construction_sites_update(self.sites.clone(), self.sites_version, world);
+        }
+    }
 }
 
 impl<PK: PrototypeKind> Temporal for Construction<PK> {
-    fn tick(&mut self, _dt: f32, _current_instant: Instant, world: &mut World) {
+    fn tick(&mut self, _dt: f32, current_instant: Instant, world: &mut World) {
+        self.current_instant = current_instant;
+
+        // only actions that refer to prototypes that are still construction sites
+        // wait for them, the rest of the next group can start right away
         if self.pending_constructables.is_empty() {
             if !self.queued_action_groups.0.is_empty() {
                 debug(LOG_T, "Starting construction group:", self.id, world);
                 let next_action_group = self.queued_action_groups.0.remove(0);
-                for action in next_action_group.0 {
-                    self.start_action(&action, world);
+                for action in &next_action_group.0 {
+                    self.start_or_wait(action, world);
                 }
                 debug(LOG_T, "Finished construction group:", self.id, world);
             }
@@ -148,8 +271,10 @@ impl<PK: PrototypeKind> Temporal for Construction<PK> {
             debug(
                 LOG_T,
                 format!(
-                    "Construction pending: {} - queued groups: {}",
+                    "Construction pending: {} - sites: {} - waiting: {} - queued groups: {}",
                     self.pending_constructables.len(),
+                    self.sites.len(),
+                    self.waiting_actions.len(),
                     self.queued_action_groups.0.len()
                 ),
                 self.id,
@@ -159,14 +284,52 @@ impl<PK: PrototypeKind> Temporal for Construction<PK> {
     }
 }
 
+impl<PK: PrototypeKind> Sleeper for Construction<PK> {
+    fn wake(&mut self, current_instant: Instant, world: &mut World) {
+        let (finished_sites, unfinished_sites): (Vec<_>, Vec<_>) = self
+            .sites
+            .iter()
+            .cloned()
+            .partition(|site| site.finished_at <= current_instant);
+
+        if finished_sites.is_empty() {
+            return;
+        }
+
+        for site in finished_sites {
+            debug(LOG_T, "Finished construction site", self.id, world);
+            let ids = site.prototype.construct(self.id, world);
+            self.constructed.insert(site.prototype.id, ids.clone());
+            self.pending_constructables.extend(ids);
+        }
+
+        self.sites = unfinished_sites.into();
+        self.sites_version += 1;
+
+        self.start_unblocked_actions(world);
+    }
+}
+
+fn refers_to_any(action: &Action, prototype_ids: &HashSet<PrototypeID>) -> bool {
+    match *action {
+        Action::Construct(_) => false,
+        Action::Morph(old_prototype_id, _) => prototype_ids.contains(&old_prototype_id),
+        Action::Destruct(prototype_id) => prototype_ids.contains(&prototype_id),
+    }
+}
+
 pub fn setup<PK: PrototypeKind>(system: &mut ActorSystem) {
     system.register::<Construction<PK>>();
     auto_setup::<PK>(system);
+    ui::auto_setup::<PK>(system);
 }
 
 pub fn spawn<PK: PrototypeKind>(world: &mut World) {
     ConstructionID::<PK>::spawn(world);
 }
 
+pub mod ui;
+use self::ui::ConstructionUIID;
+
 mod kay_auto;
 pub use self::kay_auto::*;
@@ -0,0 +1,82 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct ConstructionUIID<PK: PrototypeKind> {
+    _raw_id: RawID, _marker: ::std::marker::PhantomData<Box<(PK)>>
+}
+
+impl<PK: PrototypeKind> Copy for ConstructionUIID<PK> {}
+impl<PK: PrototypeKind> Clone for ConstructionUIID<PK> { fn clone(&self) -> Self { *self } }
+impl<PK: PrototypeKind> ::std::fmt::Debug for ConstructionUIID<PK> {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "ConstructionUIID<PK>({:?})", self._raw_id)
+    }
+}
+impl<PK: PrototypeKind> ::std::hash::Hash for ConstructionUIID<PK> {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl<PK: PrototypeKind> PartialEq for ConstructionUIID<PK> {
+    fn eq(&self, other: &ConstructionUIID<PK>) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl<PK: PrototypeKind> Eq for ConstructionUIID<PK> {}
+
+pub struct ConstructionUIRepresentative<PK: PrototypeKind>{ _marker: ::std::marker::PhantomData<Box<(PK)>> }
+
+impl<PK: PrototypeKind> ActorOrActorTrait for ConstructionUIRepresentative<PK> {
+    type ID = ConstructionUIID<PK>;
+}
+
+impl<PK: PrototypeKind> TypedID for ConstructionUIID<PK> {
+    type Target = ConstructionUIRepresentative<PK>;
+
+    fn from_raw(id: RawID) -> Self {
+        ConstructionUIID { _raw_id: id, _marker: ::std::marker::PhantomData }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<PK: PrototypeKind, Act: Actor + ConstructionUI<PK>> TraitIDFrom<Act> for ConstructionUIID<PK> {}
+
+impl<PK: PrototypeKind> ConstructionUIID<PK> {
+    pub fn on_construction_sites_update(self, sites: CVec < ConstructionSite < PK > >, sites_version: u32, world: &mut World) {
+        world.send(self.as_raw(), MSG_ConstructionUI_on_construction_sites_update::<PK>(sites, sites_version));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<ConstructionUIRepresentative<PK>>();
+        system.register_trait_message::<MSG_ConstructionUI_on_construction_sites_update<PK>>();
+    }
+
+    pub fn register_implementor<Act: Actor + ConstructionUI<PK>>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, ConstructionUIRepresentative<PK>>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_ConstructionUI_on_construction_sites_update::<PK>(ref sites, sites_version), instance, world| {
+                instance.on_construction_sites_update(sites, sites_version, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ConstructionUI_on_construction_sites_update<PK: PrototypeKind>(pub CVec < ConstructionSite < PK > >, pub u32);
+
+
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup<PK: PrototypeKind>(system: &mut ActorSystem) {
+    ConstructionUIID::<PK>::register_trait(system);
+    
+}
\ No newline at end of file
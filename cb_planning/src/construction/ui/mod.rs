@@ -0,0 +1,15 @@
+use kay::World;
+use compact::CVec;
+use super::{PrototypeKind, ConstructionSite};
+
+pub trait ConstructionUI<PK: PrototypeKind> {
+    fn on_construction_sites_update(
+        &mut self,
+        sites: &CVec<ConstructionSite<PK>>,
+        sites_version: u32,
+        _world: &mut World,
+    );
+}
+
+pub mod kay_auto;
+pub use self::kay_auto::*;
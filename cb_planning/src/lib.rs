@@ -10,19 +10,24 @@ extern crate serde_derive;
 extern crate descartes;
 extern crate cb_util;
 extern crate cb_time;
+extern crate num_cpus;
+#[macro_use]
+extern crate lazy_static;
 
-use compact::{CVec, COption, CHashMap, Compact};
+use compact::{CVec, COption, CHashMap, CString, Compact};
 use descartes::{N, P2, AreaError};
 use cb_util::random::{seed, RngCore, Uuid, uuid};
 use std::hash::Hash;
+use std::collections::{HashSet, HashMap};
 
 pub mod construction;
 use construction::{PrototypeKind, GestureIntent};
 pub mod plan_manager;
+mod parallel_planning;
+use parallel_planning::PlanningRegions;
 
 // idea for improvement:
 // - everything (Gestures, Prototypes) immutable (helps caching)
-// - everything separated by staggered grid (to save work)
 
 #[derive(Compact, Clone, Debug, Serialize, Deserialize)]
 pub struct Gesture<GI: GestureIntent> {
@@ -149,6 +154,72 @@ impl<GI: GestureIntent> PlanHistory<GI> {
         }
     }
 
+    /// Finds the grid cells touched by gestures that changed since `previous`, grown until
+    /// no other gesture reaches into them, and the history of just the gestures that need
+    /// to be replanned for these cells (including all gestures without bounds).
+    /// Returns `None` if a changed gesture has no bounds, so everything needs replanning.
+    pub fn affected_since(
+        &self,
+        previous: &PlanHistory<GI>,
+    ) -> Option<(HashSet<GridCoords>, PlanHistory<GI>)> {
+        let mut dirty_cells = HashSet::new();
+
+        for (gesture_id, VersionedGesture(gesture, step_id)) in self.gestures.pairs() {
+            match previous.gestures.get(*gesture_id) {
+                Some(VersionedGesture(_, previous_step_id)) if previous_step_id == step_id => {}
+                maybe_previous => {
+                    dirty_cells.extend(grid_coords_within(gesture.intent.bounds()?));
+                    if let Some(VersionedGesture(previous_gesture, _)) = maybe_previous {
+                        dirty_cells.extend(grid_coords_within(previous_gesture.intent.bounds()?));
+                    }
+                }
+            }
+        }
+
+        for (gesture_id, VersionedGesture(previous_gesture, _)) in previous.gestures.pairs() {
+            if !self.gestures.contains_key(*gesture_id) {
+                dirty_cells.extend(grid_coords_within(previous_gesture.intent.bounds()?));
+            }
+        }
+
+        let mut affected = PlanHistory {
+            gestures: CHashMap::new(),
+            steps: self.steps.clone(),
+        };
+
+        loop {
+            let mut grew = false;
+
+            for (gesture_id, versioned_gesture) in self.gestures.pairs() {
+                if affected.gestures.contains_key(*gesture_id) {
+                    continue;
+                }
+
+                if let Some(bounds) = versioned_gesture.0.intent.bounds() {
+                    let cells = grid_coords_within(bounds);
+                    if cells.iter().any(|cell| dirty_cells.contains(cell)) {
+                        affected
+                            .gestures
+                            .insert(*gesture_id, versioned_gesture.clone());
+                        for cell in cells {
+                            grew |= dirty_cells.insert(cell);
+                        }
+                    }
+                } else {
+                    affected
+                        .gestures
+                        .insert(*gesture_id, versioned_gesture.clone());
+                }
+            }
+
+            if !grew {
+                break;
+            }
+        }
+
+        Some((dirty_cells, affected))
+    }
+
     pub fn update_for(&self, known_state: &KnownHistoryState) -> PlanHistoryUpdate<GI> {
         let first_different_index = self
             .steps
@@ -227,11 +298,25 @@ impl<GI: GestureIntent> PlanHistoryUpdate<GI> {
     }
 }
 
+/// A finished step of a project, remembered even after a new edit replaced it
+/// in the project's current line of steps
+#[derive(Compact, Clone, Debug, Serialize, Deserialize)]
+pub struct HistoryStep<GI: GestureIntent> {
+    pub plan: Plan<GI>,
+    pub parent: Option<StepID>,
+    /// Orders steps by when they were made, so the most recent branch can be followed
+    pub created: u32,
+}
+
 #[derive(Compact, Clone, Debug, Serialize, Deserialize)]
 pub struct Project<GI: GestureIntent> {
     undoable_history: CVec<Plan<GI>>,
     ongoing: Plan<GI>,
     redoable_history: CVec<Plan<GI>>,
+    /// All finished steps, branching wherever an edit followed an undo
+    history_tree: CHashMap<StepID, HistoryStep<GI>>,
+    /// The master plan version of each changed gesture that this project's changes are based on
+    based_on: CHashMap<GestureID, StepID>,
 }
 
 impl<GI: GestureIntent + 'static> Project<GI> {
@@ -240,18 +325,253 @@ impl<GI: GestureIntent + 'static> Project<GI> {
             undoable_history: CVec::new(),
             ongoing: Plan::new(),
             redoable_history: CVec::new(),
+            history_tree: CHashMap::new(),
+            based_on: CHashMap::new(),
         }
     }
 
     pub fn from_plan(plan: Plan<GI>) -> Project<GI> {
-        Project {
-            undoable_history: vec![plan].into(),
-            ongoing: Plan::new(),
-            redoable_history: CVec::new(),
+        let mut project = Project::new();
+        project.ongoing = plan;
+        project.start_new_step();
+        project
+    }
+
+    /// A copy with new ids for all gestures and steps, so the same exported project
+    /// can be imported several times without its gestures replacing each other
+    pub fn with_new_ids(&self) -> Project<GI> {
+        let mut new_gesture_ids = HashMap::new();
+        let mut with_new_gesture_ids = |plan: &Plan<GI>| Plan {
+            step_id: plan.step_id,
+            gestures: plan
+                .gestures
+                .pairs()
+                .map(|(gesture_id, gesture)| {
+                    let new_gesture_id = *new_gesture_ids
+                        .entry(*gesture_id)
+                        .or_insert_with(GestureID::new);
+                    (new_gesture_id, gesture.clone())
+                })
+                .collect(),
+        };
+
+        let mut project = Project {
+            undoable_history: self
+                .undoable_history
+                .iter()
+                .map(&mut with_new_gesture_ids)
+                .collect(),
+            ongoing: with_new_gesture_ids(&self.ongoing),
+            redoable_history: self
+                .redoable_history
+                .iter()
+                .map(&mut with_new_gesture_ids)
+                .collect(),
+            history_tree: self
+                .history_tree
+                .pairs()
+                .map(|(step_id, step)| {
+                    (
+                        *step_id,
+                        HistoryStep {
+                            plan: with_new_gesture_ids(&step.plan),
+                            ..step.clone()
+                        },
+                    )
+                })
+                .collect(),
+            based_on: CHashMap::new(),
+        };
+        project.renew_step_ids();
+        project
+    }
+
+    /// Projects from files might have been edited by hand or be corrupted, which would
+    /// make walking the history tree panic or loop forever later
+    pub fn check_history_consistency(&self) -> Result<(), String> {
+        for (step_id, step) in self.history_tree.pairs() {
+            if step.plan.step_id != *step_id {
+                return Err(format!(
+                    "Step {:?} is stored as {:?}",
+                    step.plan.step_id, step_id
+                ));
+            }
+
+            let mut n_ancestors = 0;
+            let mut next_parent = step.parent;
+
+            while let Some(parent) = next_parent {
+                n_ancestors += 1;
+                if n_ancestors > self.history_tree.len() {
+                    return Err(format!("Step {:?} is its own ancestor", step_id));
+                }
+                next_parent = match self.history_tree.get(parent) {
+                    Some(parent_step) => parent_step.parent,
+                    None => return Err(format!("Parent {:?} doesn't exist", parent)),
+                };
+            }
         }
+
+        // undoable steps go forward along the current line, redoable steps backward
+        let current_line = self
+            .undoable_history
+            .iter()
+            .chain(self.redoable_history.iter().rev());
+        let mut expected_parent = None;
+
+        for plan in current_line {
+            match self.history_tree.get(plan.step_id) {
+                Some(step) if step.parent == expected_parent => {}
+                Some(_) => {
+                    return Err(format!(
+                        "Step {:?} doesn't follow the previous step of the current line",
+                        plan.step_id
+                    ))
+                }
+                None => return Err(format!("Step {:?} isn't in the history", plan.step_id)),
+            }
+            expected_parent = Some(plan.step_id);
+        }
+
+        Ok(())
+    }
+
+    /// Gives every step a new id, keeping the history tree intact
+    fn renew_step_ids(&mut self) {
+        let renewed = self
+            .history_tree
+            .keys()
+            .chain(self.undoable_history.iter().map(|plan| &plan.step_id))
+            .chain(Some(&self.ongoing.step_id))
+            .chain(self.redoable_history.iter().map(|plan| &plan.step_id))
+            .map(|step_id| (*step_id, StepID(uuid())))
+            .collect::<HashMap<_, _>>();
+
+        for plan in self
+            .undoable_history
+            .iter_mut()
+            .chain(Some(&mut self.ongoing))
+            .chain(self.redoable_history.iter_mut())
+        {
+            plan.step_id = renewed[&plan.step_id];
+        }
+
+        self.history_tree = self
+            .history_tree
+            .pairs()
+            .map(|(step_id, step)| {
+                let mut step = step.clone();
+                step.plan.step_id = renewed[step_id];
+                step.parent = step.parent.map(|parent| renewed[&parent]);
+                (renewed[step_id], step)
+            })
+            .collect();
+    }
+
+    pub fn changes_gesture(&self, gesture_id: GestureID) -> bool {
+        self.undoable_history
+            .iter()
+            .chain(Some(&self.ongoing))
+            .any(|plan| plan.gestures.contains_key(gesture_id))
+    }
+
+    /// Remembers which master plan version of a gesture the project starts changing
+    pub fn set_based_on(&mut self, gesture_id: GestureID, master_step_id: StepID) {
+        if !self.changes_gesture(gesture_id) {
+            self.based_on.insert(gesture_id, master_step_id);
+        }
+    }
+
+    /// Changed gestures that were changed in the master plan as well in the meantime
+    pub fn conflicts_with(&self, master: &PlanHistory<GI>) -> Vec<GestureID> {
+        self.based_on
+            .pairs()
+            .filter(|&(gesture_id, based_on_step_id)| {
+                self.changes_gesture(*gesture_id)
+                    && master
+                        .gestures
+                        .get(*gesture_id)
+                        .map(|VersionedGesture(_, master_step_id)| {
+                            master_step_id != based_on_step_id
+                        })
+                        .unwrap_or(true)
+            })
+            .map(|(gesture_id, _)| *gesture_id)
+            .collect()
+    }
+
+    /// Resolves a conflict by keeping this project's version of the gesture.
+    /// If the master plan removed the gesture in the meantime (`None`), the project's
+    /// version isn't based on anything anymore and simply adds the gesture again.
+    pub fn rebase_gesture(&mut self, gesture_id: GestureID, master_step_id: Option<StepID>) {
+        match master_step_id {
+            Some(master_step_id) => {
+                self.based_on.insert(gesture_id, master_step_id);
+            }
+            None => {
+                self.based_on.remove(gesture_id);
+            }
+        }
+    }
+
+    /// Resolves a conflict by dropping all of this project's changes to the gesture
+    pub fn drop_gesture(&mut self, gesture_id: GestureID) {
+        for plan in self
+            .undoable_history
+            .iter_mut()
+            .chain(Some(&mut self.ongoing))
+            .chain(self.redoable_history.iter_mut())
+            .chain(self.history_tree.values_mut().map(|step| &mut step.plan))
+        {
+            plan.gestures.remove(gesture_id);
+        }
+        self.prune_empty_steps();
+        // new step ids make UIs notice that the steps changed
+        self.renew_step_ids();
+        self.based_on.remove(gesture_id);
+    }
+
+    /// Removes finished steps that don't change anything (anymore),
+    /// attaching their children to their parents instead
+    fn prune_empty_steps(&mut self) {
+        let parents_of_empty = self
+            .history_tree
+            .pairs()
+            .filter(|&(_, step)| step.plan.gestures.is_empty())
+            .map(|(step_id, step)| (*step_id, step.parent))
+            .collect::<HashMap<_, _>>();
+
+        let closest_non_empty = |mut parent: Option<StepID>| {
+            while let Some(parent_of_empty) = parent.and_then(|id| parents_of_empty.get(&id)) {
+                parent = *parent_of_empty;
+            }
+            parent
+        };
+
+        self.history_tree = self
+            .history_tree
+            .pairs()
+            .filter(|&(step_id, _)| !parents_of_empty.contains_key(step_id))
+            .map(|(step_id, step)| {
+                let mut step = step.clone();
+                step.parent = closest_non_empty(step.parent);
+                (*step_id, step)
+            })
+            .collect();
+
+        self.undoable_history
+            .retain(|plan| !plan.gestures.is_empty());
+        self.redoable_history
+            .retain(|plan| !plan.gestures.is_empty());
     }
 
     pub fn start_new_step(&mut self) {
+        let step = HistoryStep {
+            plan: self.ongoing.clone(),
+            parent: self.undoable_history.last().map(|plan| plan.step_id),
+            created: self.history_tree.len() as u32,
+        };
+        self.history_tree.insert(self.ongoing.step_id, step);
         self.undoable_history.push(self.ongoing.clone());
         self.ongoing = Plan::new();
     }
@@ -279,6 +599,65 @@ impl<GI: GestureIntent + 'static> Project<GI> {
         &self.undoable_history
     }
 
+    /// All steps from the start of the project up to and including the given one
+    pub fn history_up_to(&self, step_id: StepID) -> Option<Vec<Plan<GI>>> {
+        let mut steps = Vec::new();
+        let mut next_step_id = Some(step_id);
+
+        while let Some(step_id) = next_step_id {
+            let step = self.history_tree.get(step_id)?;
+            steps.push(step.plan.clone());
+            next_step_id = step.parent;
+        }
+
+        steps.reverse();
+        Some(steps)
+    }
+
+    /// Makes the given step, possibly on another branch, the most recent one.
+    /// The steps of the most recently made branch after it can be redone
+    pub fn jump_to_step(&mut self, step_id: StepID) -> bool {
+        let undoable_history = match self.history_up_to(step_id) {
+            Some(steps) => steps,
+            None => return false,
+        };
+
+        let mut redoable_history = Vec::new();
+        let mut current_step_id = step_id;
+
+        while let Some(latest_child) = self
+            .history_tree
+            .values()
+            .filter(|step| step.parent == Some(current_step_id))
+            .max_by_key(|step| step.created)
+        {
+            redoable_history.push(latest_child.plan.clone());
+            current_step_id = latest_child.plan.step_id;
+        }
+
+        // redoing takes steps from the end
+        redoable_history.reverse();
+
+        self.undoable_history = undoable_history.into();
+        self.redoable_history = redoable_history.into();
+        self.ongoing = Plan::new();
+        true
+    }
+
+    /// The grid cells this project's gestures affect, or `None` if one of them
+    /// affects the whole plan
+    pub fn touched_cells(&self) -> Option<HashSet<GridCoords>> {
+        let mut cells = HashSet::new();
+
+        for plan in self.undoable_history.iter().chain(Some(&self.ongoing)) {
+            for gesture in plan.gestures.values() {
+                cells.extend(grid_coords_within(gesture.intent.bounds()?));
+            }
+        }
+
+        Some(cells)
+    }
+
     fn apply_to(&self, base: &PlanHistory<GI>) -> PlanHistory<GI> {
         base.and_then(&self.undoable_history)
     }
@@ -361,6 +740,23 @@ pub struct PrototypesSpatialCell {
 
 const PROTO_SPATIAL_GRID_CELL_SIZE: N = 100.0;
 
+pub type GridCoords = (i32, i32);
+
+fn grid_coords_of(pos: P2) -> GridCoords {
+    (
+        (pos.x / PROTO_SPATIAL_GRID_CELL_SIZE) as i32,
+        (pos.y / PROTO_SPATIAL_GRID_CELL_SIZE) as i32,
+    )
+}
+
+fn grid_coords_within((min, max): (P2, P2)) -> Vec<GridCoords> {
+    let (min_x, min_y) = grid_coords_of(min);
+    let (max_x, max_y) = grid_coords_of(max);
+    (min_x..=max_x)
+        .flat_map(|x| (min_y..=max_y).map(move |y| (x, y)))
+        .collect()
+}
+
 #[derive(Compact, Clone, Serialize, Deserialize, Debug)]
 pub struct PrototypesSpatialGrid<PK: PrototypeKind> {
     cells: CHashMap<(i32, i32), PrototypesSpatialCell>,
@@ -376,11 +772,7 @@ impl<PK: PrototypeKind> PrototypesSpatialGrid<PK> {
     }
 
     pub fn add_protoype(&mut self, proto: &Prototype<PK>) {
-        let pos = proto.representative_position;
-        let grid_coords = (
-            (pos.x / PROTO_SPATIAL_GRID_CELL_SIZE) as i32,
-            (pos.y / PROTO_SPATIAL_GRID_CELL_SIZE) as i32,
-        );
+        let grid_coords = grid_coords_of(proto.representative_position);
         let found_cell = if let Some(grid_cell) = self.cells.get_mut(grid_coords) {
             match grid_cell.members.binary_search(&proto.id) {
                 Err(empty_pos) => grid_cell.members.insert(empty_pos, proto.id),
@@ -403,11 +795,7 @@ impl<PK: PrototypeKind> PrototypesSpatialGrid<PK> {
     }
 
     pub fn remove_prototype(&mut self, proto: &Prototype<PK>) {
-        let pos = proto.representative_position;
-        let grid_coords = (
-            (pos.x / PROTO_SPATIAL_GRID_CELL_SIZE) as i32,
-            (pos.y / PROTO_SPATIAL_GRID_CELL_SIZE) as i32,
-        );
+        let grid_coords = grid_coords_of(proto.representative_position);
         let grid_cell = self
             .cells
             .get_mut(grid_coords)
@@ -486,6 +874,110 @@ impl<PK: PrototypeKind> PlanResult<PK> {
         }
     }
 
+    pub fn add_prototype(&mut self, prototype: Prototype<PK>) {
+        self.grid.add_protoype(&prototype);
+        self.prototypes.insert(prototype.id, prototype);
+    }
+
+    /// A result with only the prototypes located in cells for which `predicate` holds
+    pub fn filtered_by_cell<F: Fn(GridCoords) -> bool>(&self, predicate: F) -> PlanResult<PK> {
+        let mut filtered = PlanResult::new();
+
+        for (grid_coords, cell) in self.grid.cells.pairs() {
+            if predicate(*grid_coords) {
+                for member in &cell.members {
+                    filtered.add_prototype(
+                        self.prototypes
+                            .get(*member)
+                            .expect("Grid member should exist")
+                            .clone(),
+                    );
+                }
+            }
+        }
+
+        filtered
+    }
+
+    /// A result with only the prototypes located in `cells`
+    pub fn within_cells(&self, cells: &HashSet<GridCoords>) -> PlanResult<PK> {
+        let mut within = PlanResult::new();
+
+        for grid_coords in cells {
+            if let Some(cell) = self.grid.cells.get(*grid_coords) {
+                for member in &cell.members {
+                    within.add_prototype(
+                        self.prototypes
+                            .get(*member)
+                            .expect("Grid member should exist")
+                            .clone(),
+                    );
+                }
+            }
+        }
+
+        within
+    }
+
+    /// Sums up the construction costs of all prototypes that `actions` would newly construct
+    pub fn construction_cost_of(&self, actions: &ActionGroups) -> f32 {
+        actions
+            .0
+            .iter()
+            .flat_map(|action_group| action_group.0.iter())
+            .filter_map(|action| match *action {
+                Action::Construct(prototype_id) => self.prototypes.get(prototype_id),
+                _ => None,
+            })
+            .map(|prototype| prototype.kind.construction_cost())
+            .sum()
+    }
+
+    /// Summarizes what `actions` from this result to `other` would change
+    pub fn impact_of(&self, other: &PlanResult<PK>, actions: &ActionGroups) -> ProjectImpact {
+        let mut impact = ProjectImpact {
+            categories: CVec::new(),
+            construction_cost: other.construction_cost_of(actions),
+            destroyed_prototypes: CVec::new(),
+            overlapping_projects: CVec::new(),
+            conflicting_gestures: CVec::new(),
+            planning_failed: false,
+        };
+
+        for action in actions
+            .0
+            .iter()
+            .flat_map(|action_group| action_group.0.iter())
+        {
+            match *action {
+                Action::Construct(id) => {
+                    if let Some(prototype) = other.prototypes.get(id) {
+                        impact
+                            .category_mut(prototype.kind.impact_category())
+                            .constructed += 1;
+                    }
+                }
+                Action::Morph(_, new_id) => {
+                    if let Some(prototype) = other.prototypes.get(new_id) {
+                        impact
+                            .category_mut(prototype.kind.impact_category())
+                            .morphed += 1;
+                    }
+                }
+                Action::Destruct(id) => {
+                    if let Some(prototype) = self.prototypes.get(id) {
+                        impact
+                            .category_mut(prototype.kind.impact_category())
+                            .destroyed += 1;
+                    }
+                    impact.destroyed_prototypes.push(id);
+                }
+            }
+        }
+
+        impact
+    }
+
     pub fn actions_to(&self, other: &PlanResult<PK>) -> (ActionGroups, CVec<Prototype<PK>>) {
         let mut to_be_morphed = CVec::new();
         let mut new_prototypes = CVec::new();
@@ -608,6 +1100,62 @@ impl<PK: PrototypeKind> PlanResult<PK> {
     }
 }
 
+/// How many prototypes of one category a project would construct, morph and destroy
+#[derive(Compact, Clone, Debug)]
+pub struct CategoryImpact {
+    pub category: CString,
+    pub constructed: u32,
+    pub morphed: u32,
+    pub destroyed: u32,
+}
+
+/// What implementing a project would change, so it can be reviewed beforehand
+#[derive(Compact, Clone, Debug)]
+pub struct ProjectImpact {
+    pub categories: CVec<CategoryImpact>,
+    pub construction_cost: f32,
+    pub destroyed_prototypes: CVec<PrototypeID>,
+    /// Other pending projects that affect the same area
+    pub overlapping_projects: CVec<plan_manager::ProjectID>,
+    /// Gestures that were changed in the master plan since the project started changing them
+    pub conflicting_gestures: CVec<GestureID>,
+    /// The project can't be planned in its current state, so nothing else is known about it
+    pub planning_failed: bool,
+}
+
+impl ProjectImpact {
+    pub fn of_failed_planning() -> ProjectImpact {
+        ProjectImpact {
+            categories: CVec::new(),
+            construction_cost: 0.0,
+            destroyed_prototypes: CVec::new(),
+            overlapping_projects: CVec::new(),
+            conflicting_gestures: CVec::new(),
+            planning_failed: true,
+        }
+    }
+
+    fn category_mut(&mut self, category: &str) -> &mut CategoryImpact {
+        let idx = match self
+            .categories
+            .iter()
+            .position(|impact| &*impact.category == category)
+        {
+            Some(idx) => idx,
+            None => {
+                self.categories.push(CategoryImpact {
+                    category: category.to_owned().into(),
+                    constructed: 0,
+                    morphed: 0,
+                    destroyed: 0,
+                });
+                self.categories.len() - 1
+            }
+        };
+        &mut self.categories[idx]
+    }
+}
+
 #[derive(Compact, Clone, Serialize, Deserialize, Debug)]
 pub struct Prototype<PK: PrototypeKind> {
     pub id: PrototypeID,
@@ -751,27 +1299,252 @@ pub type PlanningStepFn<PL: PlanningLogic> =
     ) -> Result<Vec<Prototype<PL::PrototypeKind>>, AreaError>;
 
 pub trait PlanningLogic: Compact + 'static {
-    type GestureIntent: GestureIntent;
-    type PrototypeKind: PrototypeKind;
+    /// Gestures and prototypes are planned on worker threads, see `parallel_planning`
+    type GestureIntent: GestureIntent + Send;
+    type PrototypeKind: PrototypeKind + Send;
 
     fn planning_step_functions() -> &'static [PlanningStepFn<Self>];
     fn calculate_result(
         history: &PlanHistory<Self::GestureIntent>,
     ) -> Result<PlanResult<Self::PrototypeKind>, AreaError> {
         let mut result = PlanResult::new();
+        let regions = PlanningRegions::of(history);
 
         for prototype_fn in Self::planning_step_functions() {
-            let new_prototypes = prototype_fn(history, &result)?;
+            let new_prototypes = parallel_planning::calculate_step::<Self>(
+                *prototype_fn,
+                history,
+                &regions,
+                &result,
+            )?;
+
+            for prototype in new_prototypes {
+                result.add_prototype(prototype);
+            }
+        }
 
-            for (id, prototype) in new_prototypes
-                .into_iter()
-                .map(|prototype| (prototype.id, prototype))
-            {
-                result.grid.add_protoype(&prototype);
-                result.prototypes.insert(id, prototype);
+        Ok(result)
+    }
+
+    /// Like `calculate_result`, but only replans the gestures around those that changed
+    /// since `previous_history` and reuses the prototypes of `previous_result` elsewhere.
+    /// Only meant for previews, implemented results should always be calculated fully.
+    fn calculate_result_incrementally(
+        previous_history: &PlanHistory<Self::GestureIntent>,
+        previous_result: &PlanResult<Self::PrototypeKind>,
+        history: &PlanHistory<Self::GestureIntent>,
+    ) -> Result<PlanResult<Self::PrototypeKind>, AreaError> {
+        let (dirty_cells, affected_history) = match history.affected_since(previous_history) {
+            Some(affected) => affected,
+            None => return Self::calculate_result(history),
+        };
+
+        if affected_history.gestures.len() * 2 > history.gestures.len() {
+            return Self::calculate_result(history);
+        }
+
+        let is_dirty = |grid_coords: GridCoords| dirty_cells.contains(&grid_coords);
+        let borders_dirty = |(x, y): GridCoords| {
+            (-1..=1).any(|dx| (-1..=1).any(|dy| dirty_cells.contains(&(x + dx, y + dy))))
+        };
+
+        let mut result = previous_result.filtered_by_cell(|grid_coords| !is_dirty(grid_coords));
+        // step functions see the unchanged surroundings of dirty cells, like in a full run
+        let mut context = previous_result
+            .filtered_by_cell(|grid_coords| !is_dirty(grid_coords) && borders_dirty(grid_coords));
+
+        let regions = PlanningRegions::of(&affected_history);
+
+        for prototype_fn in Self::planning_step_functions() {
+            let new_prototypes = parallel_planning::calculate_step::<Self>(
+                *prototype_fn,
+                &affected_history,
+                &regions,
+                &context,
+            )?;
+
+            for prototype in new_prototypes {
+                if is_dirty(grid_coords_of(prototype.representative_position))
+                    && !result.prototypes.contains_key(prototype.id)
+                {
+                    context.add_prototype(prototype.clone());
+                    result.add_prototype(prototype);
+                }
             }
         }
 
         Ok(result)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use descartes::V2;
+
+    /// A point gesture, or one without bounds if `None`
+    #[derive(Copy, Clone)]
+    struct MockIntent(Option<P2>);
+
+    impl GestureIntent for MockIntent {
+        fn bounds(&self) -> Option<(P2, P2)> {
+            self.0.map(|position| {
+                (
+                    position - V2::new(50.0, 50.0),
+                    position + V2::new(50.0, 50.0),
+                )
+            })
+        }
+    }
+
+    fn project_with_steps(n_steps: usize) -> (Project<MockIntent>, Vec<StepID>) {
+        let mut project = Project::new();
+        let step_ids = (0..n_steps).map(|_| add_step(&mut project)).collect();
+        (project, step_ids)
+    }
+
+    fn add_step(project: &mut Project<MockIntent>) -> StepID {
+        let plan = Plan::from_gestures(Some((GestureID::new(), Gesture::new(MockIntent(None)))));
+        let step_id = plan.step_id;
+        project.set_ongoing_step(plan);
+        project.start_new_step();
+        step_id
+    }
+
+    fn set_parent(project: &mut Project<MockIntent>, step_id: StepID, parent: Option<StepID>) {
+        project.history_tree.get_mut(step_id).unwrap().parent = parent;
+    }
+
+    #[test]
+    fn branching_history_is_consistent() {
+        let (mut project, step_ids) = project_with_steps(2);
+        project.undo();
+        let branch = add_step(&mut project);
+        assert!(project.check_history_consistency().is_ok());
+
+        assert!(project.jump_to_step(step_ids[0]));
+        assert_eq!(
+            project.redoable_history.last().map(|plan| plan.step_id),
+            Some(branch)
+        );
+        assert!(project.check_history_consistency().is_ok());
+
+        assert!(project.jump_to_step(step_ids[1]));
+        assert!(project.check_history_consistency().is_ok());
+    }
+
+    #[test]
+    fn dangling_parents_are_rejected() {
+        let (mut project, step_ids) = project_with_steps(2);
+        set_parent(&mut project, step_ids[1], Some(StepID(uuid())));
+
+        let error = project.check_history_consistency().unwrap_err();
+        assert!(error.contains("doesn't exist"), "{}", error);
+    }
+
+    #[test]
+    fn cycles_are_rejected() {
+        let (mut project, step_ids) = project_with_steps(3);
+        set_parent(&mut project, step_ids[0], Some(step_ids[2]));
+
+        let error = project.check_history_consistency().unwrap_err();
+        assert!(error.contains("own ancestor"), "{}", error);
+
+        let (mut project, step_ids) = project_with_steps(1);
+        set_parent(&mut project, step_ids[0], Some(step_ids[0]));
+
+        let error = project.check_history_consistency().unwrap_err();
+        assert!(error.contains("own ancestor"), "{}", error);
+    }
+
+    #[test]
+    fn current_line_has_to_follow_the_history_tree() {
+        let (mut project, _) = project_with_steps(3);
+        let reordered = project
+            .undoable_history
+            .iter()
+            .rev()
+            .cloned()
+            .collect::<Vec<_>>();
+        project.undoable_history = reordered.into();
+
+        assert!(project.check_history_consistency().is_err());
+
+        let (mut project, step_ids) = project_with_steps(2);
+        let step = project.history_tree.remove(step_ids[0]).unwrap();
+        project.history_tree.insert(StepID(uuid()), step);
+
+        assert!(project.check_history_consistency().is_err());
+    }
+
+    fn history_with(gestures: &[(GestureID, Option<P2>)]) -> PlanHistory<MockIntent> {
+        PlanHistory::new().and_then(&[Plan::from_gestures(
+            gestures
+                .iter()
+                .map(|&(gesture_id, position)| (gesture_id, Gesture::new(MockIntent(position)))),
+        )])
+    }
+
+    fn sorted_ids(history: &PlanHistory<MockIntent>) -> Vec<GestureID> {
+        let mut ids = history.gestures.keys().cloned().collect::<Vec<_>>();
+        ids.sort_by_key(|gesture_id| gesture_id.0);
+        ids
+    }
+
+    #[test]
+    fn changed_gestures_affect_the_gestures_reaching_into_their_cells() {
+        let (changed, neighbour, far_away, unbounded) = (
+            GestureID::new(),
+            GestureID::new(),
+            GestureID::new(),
+            GestureID::new(),
+        );
+        let previous = history_with(&[
+            (changed, Some(P2::new(50.0, 50.0))),
+            (neighbour, Some(P2::new(150.0, 150.0))),
+            (far_away, Some(P2::new(450.0, 50.0))),
+            (unbounded, None),
+        ]);
+        let history = previous.and_then(&[Plan::from_gestures(Some((
+            changed,
+            Gesture::new(MockIntent(Some(P2::new(60.0, 50.0)))),
+        )))]);
+
+        let (dirty_cells, affected) = history.affected_since(&previous).unwrap();
+
+        assert!(dirty_cells.contains(&(0, 0)));
+        assert!(dirty_cells.contains(&(2, 2)));
+        assert!(!dirty_cells.contains(&(4, 0)));
+        let mut expected = vec![changed, neighbour, unbounded];
+        expected.sort_by_key(|gesture_id| gesture_id.0);
+        assert_eq!(sorted_ids(&affected), expected);
+    }
+
+    #[test]
+    fn removed_gestures_leave_their_cells_dirty() {
+        let (removed, neighbour) = (GestureID::new(), GestureID::new());
+        let previous = history_with(&[
+            (removed, Some(P2::new(50.0, 50.0))),
+            (neighbour, Some(P2::new(150.0, 150.0))),
+        ]);
+        let mut history = previous.clone();
+        history.gestures.remove(removed);
+
+        let (dirty_cells, affected) = history.affected_since(&previous).unwrap();
+
+        assert!(dirty_cells.contains(&(0, 0)));
+        assert_eq!(sorted_ids(&affected), vec![neighbour]);
+    }
+
+    #[test]
+    fn changed_gestures_without_bounds_affect_everything() {
+        let previous = history_with(&[(GestureID::new(), Some(P2::new(50.0, 50.0)))]);
+        let history = previous.and_then(&[Plan::from_gestures(Some((
+            GestureID::new(),
+            Gesture::new(MockIntent(None)),
+        )))]);
+
+        assert!(history.affected_since(&previous).is_none());
+        assert!(history.affected_since(&history).unwrap().0.is_empty());
+    }
+}
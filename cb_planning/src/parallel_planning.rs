@@ -0,0 +1,476 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::sync::mpsc::{channel, Sender};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use compact::CHashMap;
+use descartes::AreaError;
+use ::{PlanHistory, PlanResult, Prototype, PrototypeID, PlanningLogic, PlanningStepFn, GridCoords,
+grid_coords_of, grid_coords_within};
+use ::construction::GestureIntent;
+
+type PlanningTask = Box<dyn FnOnce() + Send>;
+
+/// Worker threads that are started once and then run the planning jobs of all steps
+struct PlanningPool {
+    task_sender: Mutex<Sender<PlanningTask>>,
+}
+
+impl PlanningPool {
+    fn new(n_threads: usize) -> PlanningPool {
+        let (task_sender, task_receiver) = channel::<PlanningTask>();
+        let task_receiver = Arc::new(Mutex::new(task_receiver));
+
+        for thread_idx in 0..n_threads {
+            let task_receiver = task_receiver.clone();
+            ::std::thread::Builder::new()
+                .name(format!("Planning {}", thread_idx))
+                .spawn(move || loop {
+                    let next_task = task_receiver
+                        .lock()
+                        .expect("Planning task queue poisoned")
+                        .recv();
+                    match next_task {
+                        // a panicking job only loses its own result, the worker stays
+                        Ok(task) => {
+                            let _ = catch_unwind(AssertUnwindSafe(task));
+                        }
+                        // the pool was dropped
+                        Err(_) => break,
+                    }
+                })
+                .expect("Couldn't start planning thread");
+        }
+
+        PlanningPool {
+            task_sender: Mutex::new(task_sender),
+        }
+    }
+
+    fn run(&self, task: PlanningTask) {
+        self.task_sender
+            .lock()
+            .expect("Planning task queue poisoned")
+            .send(task)
+            .expect("Planning threads should be running");
+    }
+}
+
+lazy_static! {
+    /// Shared by all threads that plan, `None` if there is only one CPU to plan on anyways
+    static ref PLANNING_POOL: Option<PlanningPool> = match ::num_cpus::get() {
+        n_threads if n_threads > 1 => Some(PlanningPool::new(n_threads)),
+        _ => None,
+    };
+}
+
+/// A history split into groups of gestures whose bounds share no or neighbouring grid
+/// cells, which can be planned independently of each other, and the gestures without bounds
+pub struct PlanningRegions<GI: GestureIntent> {
+    bounded: Vec<(HashSet<GridCoords>, PlanHistory<GI>)>,
+    unbounded: PlanHistory<GI>,
+}
+
+impl<GI: GestureIntent> PlanningRegions<GI> {
+    pub fn of(history: &PlanHistory<GI>) -> PlanningRegions<GI> {
+        let mut regions: Vec<Option<(HashSet<GridCoords>, PlanHistory<GI>)>> = Vec::new();
+        let mut region_of_cell = HashMap::<GridCoords, usize>::new();
+        let mut unbounded = PlanHistory {
+            gestures: CHashMap::new(),
+            steps: history.steps.clone(),
+        };
+
+        for (gesture_id, versioned_gesture) in history.gestures.pairs() {
+            let cells = match versioned_gesture.0.intent.bounds() {
+                Some(bounds) => grid_coords_within(bounds),
+                None => {
+                    unbounded
+                        .gestures
+                        .insert(*gesture_id, versioned_gesture.clone());
+                    continue;
+                }
+            };
+
+            // prototypes can reach a little beyond the bounds of their gestures, so
+            // gestures in neighbouring cells are planned together as well
+            let mut touched_regions = cells
+                .iter()
+                .flat_map(|&cell| cell_with_neighbours(cell))
+                .filter_map(|cell| region_of_cell.get(&cell).cloned())
+                .collect::<Vec<_>>();
+            touched_regions.sort();
+            touched_regions.dedup();
+
+            // merge all touched regions into the first one (or a new one)
+            let target_idx = match touched_regions.first() {
+                Some(first_idx) => *first_idx,
+                None => {
+                    regions.push(Some((
+                        HashSet::new(),
+                        PlanHistory {
+                            gestures: CHashMap::new(),
+                            steps: history.steps.clone(),
+                        },
+                    )));
+                    regions.len() - 1
+                }
+            };
+
+            for other_idx in touched_regions.into_iter().skip(1) {
+                let (other_cells, other_history) = regions[other_idx]
+                    .take()
+                    .expect("Merged regions are only merged once");
+                let target = regions[target_idx]
+                    .as_mut()
+                    .expect("Target region should exist");
+                for cell in other_cells {
+                    region_of_cell.insert(cell, target_idx);
+                    target.0.insert(cell);
+                }
+                for (other_gesture_id, other_gesture) in other_history.gestures.pairs() {
+                    target
+                        .1
+                        .gestures
+                        .insert(*other_gesture_id, other_gesture.clone());
+                }
+            }
+
+            let target = regions[target_idx]
+                .as_mut()
+                .expect("Target region should exist");
+            for cell in cells {
+                region_of_cell.insert(cell, target_idx);
+                target.0.insert(cell);
+            }
+            target
+                .1
+                .gestures
+                .insert(*gesture_id, versioned_gesture.clone());
+        }
+
+        PlanningRegions {
+            bounded: regions.into_iter().filter_map(|region| region).collect(),
+            unbounded,
+        }
+    }
+}
+
+fn cell_with_neighbours((x, y): GridCoords) -> impl Iterator<Item = GridCoords> {
+    (-1..=1).flat_map(move |dx| (-1..=1).map(move |dy| (x + dx, y + dy)))
+}
+
+struct PlanningJob<PL: PlanningLogic> {
+    history: PlanHistory<PL::GestureIntent>,
+    context: PlanResult<PL::PrototypeKind>,
+}
+
+#[allow(type_alias_bounds)]
+type JobResult<PL: PlanningLogic> = Result<Vec<Prototype<PL::PrototypeKind>>, AreaError>;
+
+impl<PL: PlanningLogic> PlanningJob<PL> {
+    fn run(&self, step_fn: PlanningStepFn<PL>) -> JobResult<PL> {
+        step_fn(&self.history, &self.context)
+    }
+}
+
+/// Runs a planning step separately for each independent region, on a pool of as many
+/// threads as there are CPUs. Since gestures only influence planning within their bounds
+/// and prototypes are merged in region order, the result is the same as when running
+/// the step on the whole history at once. If a prototype turns out to depend on more
+/// than one region after all, the step is run on the whole history instead.
+pub fn calculate_step<PL: PlanningLogic>(
+    step_fn: PlanningStepFn<PL>,
+    history: &PlanHistory<PL::GestureIntent>,
+    regions: &PlanningRegions<PL::GestureIntent>,
+    result: &PlanResult<PL::PrototypeKind>,
+) -> JobResult<PL> {
+    if let Some(ref pool) = *PLANNING_POOL {
+        if regions.bounded.len() > 1 {
+            if let Some(prototypes) =
+                calculate_step_in_parallel::<PL>(pool, step_fn, regions, result)?
+            {
+                return Ok(prototypes);
+            }
+        }
+    }
+
+    step_fn(history, result)
+}
+
+/// The prototypes planned for all regions, or `None` if some of them span regions
+fn calculate_step_in_parallel<PL: PlanningLogic>(
+    pool: &PlanningPool,
+    step_fn: PlanningStepFn<PL>,
+    regions: &PlanningRegions<PL::GestureIntent>,
+    result: &PlanResult<PL::PrototypeKind>,
+) -> Result<Option<Vec<Prototype<PL::PrototypeKind>>>, AreaError> {
+    let mut jobs = regions
+        .bounded
+        .iter()
+        .map(|(cells, region_history)| {
+            let cells_with_surroundings = cells
+                .iter()
+                .flat_map(|&cell| cell_with_neighbours(cell))
+                .collect::<HashSet<_>>();
+
+            PlanningJob::<PL> {
+                history: region_history.clone(),
+                context: result.within_cells(&cells_with_surroundings),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    if !regions.unbounded.gestures.is_empty() {
+        jobs.push(PlanningJob {
+            history: regions.unbounded.clone(),
+            context: result.clone(),
+        });
+    }
+
+    let n_jobs = jobs.len();
+    let (output_sender, output_receiver) = channel::<(usize, JobResult<PL>)>();
+
+    for (job_idx, job) in jobs.into_iter().enumerate() {
+        let output_sender = output_sender.clone();
+        pool.run(Box::new(move || {
+            let job_result = job.run(step_fn);
+            // the planning thread waits for all outputs, so it can't be gone
+            let _ = output_sender.send((job_idx, job_result));
+        }));
+    }
+
+    // only the senders of running jobs are left, so a panicked job ends the iteration
+    drop(output_sender);
+
+    let mut job_results = output_receiver.iter().collect::<Vec<_>>();
+
+    if job_results.len() < n_jobs {
+        return Err(AreaError::LeftOver("Planning job panicked".to_owned()));
+    }
+
+    job_results.sort_by_key(|&(job_idx, _)| job_idx);
+
+    let region_of_cell = regions
+        .bounded
+        .iter()
+        .enumerate()
+        .flat_map(|(region_idx, (cells, _))| cells.iter().map(move |&cell| (cell, region_idx)))
+        .collect::<HashMap<_, _>>();
+
+    let mut seen_ids = HashSet::<PrototypeID>::new();
+    let mut prototypes = Vec::new();
+    // prototypes that one region planned in the cells of another one
+    let mut foreign_ids = Vec::new();
+
+    for (job_idx, job_result) in job_results {
+        for prototype in job_result? {
+            let cell = grid_coords_of(prototype.representative_position);
+            let is_foreign = match region_of_cell.get(&cell) {
+                // the job for gestures without bounds comes after those of all regions
+                Some(&region_idx) => region_idx != job_idx && job_idx < regions.bounded.len(),
+                None => false,
+            };
+
+            if is_foreign {
+                foreign_ids.push(prototype.id);
+            } else if seen_ids.insert(prototype.id) {
+                prototypes.push(prototype);
+            }
+        }
+    }
+
+    // unless the region it lies in planned the same prototype,
+    // a foreign prototype depends on more than one region
+    if foreign_ids.iter().all(|id| seen_ids.contains(id)) {
+        Ok(Some(prototypes))
+    } else {
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kay::World;
+    use compact::CVec;
+    use descartes::{P2, V2};
+    use ::{GestureID, Gesture, VersionedGesture};
+    use ::construction::{PrototypeKind, ConstructionID, ConstructableID};
+
+    /// A point gesture, or one without bounds if `None`
+    #[derive(Copy, Clone)]
+    struct MockIntent(Option<P2>);
+
+    impl GestureIntent for MockIntent {
+        fn bounds(&self) -> Option<(P2, P2)> {
+            self.0.map(|position| {
+                (
+                    position - V2::new(50.0, 50.0),
+                    position + V2::new(50.0, 50.0),
+                )
+            })
+        }
+    }
+
+    #[derive(Copy, Clone)]
+    struct MockKind;
+
+    impl PrototypeKind for MockKind {
+        fn construct(
+            &self,
+            _prototype_id: PrototypeID,
+            _report_to: ConstructionID<Self>,
+            _world: &mut World,
+        ) -> CVec<ConstructableID<Self>> {
+            CVec::new()
+        }
+
+        fn morphable_from(&self, _other: &Self) -> bool {
+            true
+        }
+    }
+
+    #[derive(Copy, Clone)]
+    struct MockLogic;
+
+    /// One prototype per gesture, influenced by all prototypes it can see in the context
+    fn connect_step(
+        history: &PlanHistory<MockIntent>,
+        context: &PlanResult<MockKind>,
+    ) -> JobResult<MockLogic> {
+        Ok(history
+            .gestures
+            .pairs()
+            .map(|(gesture_id, VersionedGesture(gesture, _))| {
+                let (visible, position) = match gesture.intent.bounds() {
+                    Some((min, max)) => (
+                        context
+                            .prototypes
+                            .values()
+                            .filter(|prototype| {
+                                let pos = prototype.representative_position;
+                                pos.x >= min.x && pos.y >= min.y && pos.x <= max.x && pos.y <= max.y
+                            })
+                            .map(|prototype| prototype.id)
+                            .collect::<Vec<_>>(),
+                        gesture.intent.0.unwrap() + V2::new(1.0, 1.0),
+                    ),
+                    None => (
+                        context.prototypes.keys().cloned().collect::<Vec<_>>(),
+                        P2::new(-1000.0, -1000.0),
+                    ),
+                };
+                let mut visible = visible;
+                visible.sort();
+                Prototype::new_with_influences((gesture_id, visible), MockKind, position)
+            })
+            .collect())
+    }
+
+    impl PlanningLogic for MockLogic {
+        type GestureIntent = MockIntent;
+        type PrototypeKind = MockKind;
+
+        fn planning_step_functions() -> &'static [PlanningStepFn<Self>] {
+            &[connect_step]
+        }
+    }
+
+    #[test]
+    fn parallel_step_plans_the_same_prototypes_as_sequential_step() {
+        let positions = vec![
+            Some(P2::new(50.0, 50.0)),
+            Some(P2::new(120.0, 60.0)),
+            Some(P2::new(1050.0, 50.0)),
+            Some(P2::new(1020.0, 1040.0)),
+            Some(P2::new(50.0, 2050.0)),
+            None,
+        ];
+
+        let mut history = PlanHistory::<MockIntent>::new();
+        let step_id = history.latest_step_id();
+        let mut context = PlanResult::<MockKind>::new();
+
+        for (idx, position) in positions.into_iter().enumerate() {
+            history.gestures.insert(
+                GestureID::new(),
+                VersionedGesture(Gesture::new(MockIntent(position)), step_id),
+            );
+            if let Some(position) = position {
+                context.add_prototype(Prototype::new_with_influences(
+                    idx,
+                    MockKind,
+                    position + V2::new(10.0, -10.0),
+                ));
+            }
+        }
+
+        let regions = PlanningRegions::of(&history);
+        assert!(regions.bounded.len() > 1);
+
+        let ids_of = |prototypes: Vec<Prototype<MockKind>>| {
+            let mut ids = prototypes
+                .into_iter()
+                .map(|prototype| prototype.id)
+                .collect::<Vec<_>>();
+            ids.sort();
+            ids
+        };
+
+        let sequential = connect_step(&history, &context).unwrap();
+        let parallel = calculate_step_in_parallel::<MockLogic>(
+            &PlanningPool::new(2),
+            connect_step,
+            &regions,
+            &context,
+        )
+        .unwrap()
+        .expect("No prototype should span regions");
+
+        assert_eq!(sequential.len(), 6);
+        assert_eq!(ids_of(sequential), ids_of(parallel));
+    }
+
+    /// One prototype per gesture, far to the right of it
+    fn offset_step(
+        history: &PlanHistory<MockIntent>,
+        _context: &PlanResult<MockKind>,
+    ) -> JobResult<MockLogic> {
+        Ok(history
+            .gestures
+            .pairs()
+            .map(|(gesture_id, VersionedGesture(gesture, _))| {
+                let position = gesture.intent.0.unwrap() + V2::new(400.0, 0.0);
+                Prototype::new_with_influences(gesture_id, MockKind, position)
+            })
+            .collect())
+    }
+
+    #[test]
+    fn prototypes_in_other_regions_are_planned_sequentially() {
+        let mut history = PlanHistory::<MockIntent>::new();
+        let step_id = history.latest_step_id();
+
+        for &position in &[P2::new(50.0, 50.0), P2::new(450.0, 50.0)] {
+            history.gestures.insert(
+                GestureID::new(),
+                VersionedGesture(Gesture::new(MockIntent(Some(position))), step_id),
+            );
+        }
+
+        let regions = PlanningRegions::of(&history);
+        assert_eq!(regions.bounded.len(), 2);
+
+        let context = PlanResult::<MockKind>::new();
+        let parallel = calculate_step_in_parallel::<MockLogic>(
+            &PlanningPool::new(2),
+            offset_step,
+            &regions,
+            &context,
+        )
+        .unwrap();
+        assert!(parallel.is_none());
+
+        let prototypes = calculate_step::<MockLogic>(offset_step, &history, &regions, &context);
+        assert_eq!(prototypes.unwrap().len(), 2);
+    }
+}
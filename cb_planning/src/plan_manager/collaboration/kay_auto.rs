@@ -0,0 +1,68 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+
+
+impl<Logic: PlanningLogic> PlanManagerID<Logic> {
+    pub fn set_project_locked(self, project_id: ProjectID, locked: bool, requester: PlanningUIID < Logic >, key: ClientKey, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_set_project_locked::<Logic>(project_id, locked, requester, key));
+    }
+    
+    pub fn approve_project(self, project_id: ProjectID, requester: PlanningUIID < Logic >, key: ClientKey, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_approve_project::<Logic>(project_id, requester, key));
+    }
+    
+    pub fn resolve_conflict(self, project_id: ProjectID, gesture_id: GestureID, keep_project_version: bool, requester: PlanningUIID < Logic >, key: ClientKey, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_resolve_conflict::<Logic>(project_id, gesture_id, keep_project_version, requester, key));
+    }
+    
+    pub fn set_approval_required(self, approval_required: bool, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_set_approval_required(approval_required));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_set_project_locked<Logic: PlanningLogic>(pub ProjectID, pub bool, pub PlanningUIID < Logic >, pub ClientKey);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_approve_project<Logic: PlanningLogic>(pub ProjectID, pub PlanningUIID < Logic >, pub ClientKey);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_resolve_conflict<Logic: PlanningLogic>(pub ProjectID, pub GestureID, pub bool, pub PlanningUIID < Logic >, pub ClientKey);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_set_approval_required(pub bool);
+
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup<Logic: PlanningLogic>(system: &mut ActorSystem) {
+    
+    
+    system.add_handler::<PlanManager<Logic>, _, _>(
+        |&MSG_PlanManager_set_project_locked::<Logic>(project_id, locked, requester, key), instance, world| {
+            instance.set_project_locked(project_id, locked, requester, key, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<PlanManager<Logic>, _, _>(
+        |&MSG_PlanManager_approve_project::<Logic>(project_id, requester, key), instance, world| {
+            instance.approve_project(project_id, requester, key, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<PlanManager<Logic>, _, _>(
+        |&MSG_PlanManager_resolve_conflict::<Logic>(project_id, gesture_id, keep_project_version, requester, key), instance, world| {
+            instance.resolve_conflict(project_id, gesture_id, keep_project_version, requester, key, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<PlanManager<Logic>, _, _>(
+        |&MSG_PlanManager_set_approval_required(approval_required), instance, world| {
+            instance.set_approval_required(approval_required, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,282 @@
+use kay::World;
+use compact::CVec;
+use ::{PlanningLogic, GestureID, VersionedGesture};
+use super::{PlanManager, PlanManagerID, ProjectID};
+use super::ui::PlanningUIID;
+use cb_util::client_keys::{ClientID, ClientKey, client_of};
+use cb_util::log::info;
+const LOG_T: &str = "Planning Collaboration";
+
+/// Who started a project, who else may edit it and who approved implementing it
+#[derive(Compact, Clone, Serialize, Deserialize, Debug)]
+pub struct ProjectAccess {
+    pub owner: ClientID,
+    /// Only the owner may edit or implement a locked project
+    pub locked: bool,
+    /// Cleared whenever the project is edited
+    pub approved_by: CVec<ClientID>,
+}
+
+impl ProjectAccess {
+    pub fn new(owner: ClientID) -> ProjectAccess {
+        ProjectAccess {
+            owner,
+            locked: false,
+            approved_by: CVec::new(),
+        }
+    }
+
+    pub fn may_edit(&self, client: ClientID) -> bool {
+        !self.locked || client == self.owner
+    }
+
+    pub fn is_approved(&self) -> bool {
+        self.approved_by
+            .iter()
+            .any(|&approver| approver != self.owner)
+    }
+}
+
+impl<Logic: PlanningLogic> PlanManager<Logic> {
+    /// The client a request came from, if the request carries the key issued to that client
+    pub fn verified_client(
+        &self,
+        requester: PlanningUIID<Logic>,
+        key: ClientKey,
+        world: &mut World,
+    ) -> Option<ClientID> {
+        let client = client_of(requester);
+
+        if key.is_valid_for(client) {
+            Some(client)
+        } else {
+            info(
+                LOG_T,
+                format!("Rejected request with an invalid key for client {}", client),
+                self.id,
+                world,
+            );
+            None
+        }
+    }
+
+    /// Clients might refer to projects that were never created or already implemented
+    fn project_exists(&self, project_id: ProjectID, client: ClientID, world: &mut World) -> bool {
+        if self.projects.contains_key(project_id) {
+            true
+        } else {
+            info(
+                LOG_T,
+                format!(
+                    "Client {} referred to unknown project {:?}",
+                    client, project_id
+                ),
+                self.id,
+                world,
+            );
+            false
+        }
+    }
+
+    pub fn check_edit_access(
+        &self,
+        project_id: ProjectID,
+        requester: PlanningUIID<Logic>,
+        key: ClientKey,
+        world: &mut World,
+    ) -> bool {
+        let client = match self.verified_client(requester, key, world) {
+            Some(client) => client,
+            None => return false,
+        };
+
+        if !self.project_exists(project_id, client, world) {
+            return false;
+        }
+
+        // projects without access info were started by the simulation itself
+        match self.project_access.get(project_id) {
+            Some(access) if !access.may_edit(client) => {
+                info(
+                    LOG_T,
+                    format!(
+                        "Client {} tried to edit project locked by client {}",
+                        client, access.owner
+                    ),
+                    self.id,
+                    world,
+                );
+                false
+            }
+            _ => true,
+        }
+    }
+
+    /// Approvals were given for an earlier version of the project
+    pub fn withdraw_approvals(&mut self, project_id: ProjectID) {
+        if let Some(access) = self.project_access.get_mut(project_id) {
+            if !access.approved_by.is_empty() {
+                access.approved_by.clear();
+                self.access_version += 1;
+            }
+        }
+    }
+
+    pub fn check_implement_access(
+        &self,
+        project_id: ProjectID,
+        requester: PlanningUIID<Logic>,
+        key: ClientKey,
+        world: &mut World,
+    ) -> bool {
+        let client = match self.verified_client(requester, key, world) {
+            Some(client) => client,
+            None => return false,
+        };
+
+        if !self.project_exists(project_id, client, world) {
+            return false;
+        }
+
+        if let Some(access) = self.project_access.get(project_id) {
+            if !access.may_edit(client) {
+                info(
+                    LOG_T,
+                    format!(
+                        "Client {} tried to implement project locked by client {}",
+                        client, access.owner
+                    ),
+                    self.id,
+                    world,
+                );
+                return false;
+            }
+
+            if self.approval_required && !access.is_approved() {
+                info(
+                    LOG_T,
+                    "Project needs to be approved by another client before implementing it",
+                    self.id,
+                    world,
+                );
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Pending projects that touch any of the same grid cells as the given one
+    pub fn overlapping_projects(&self, project_id: ProjectID) -> Vec<ProjectID> {
+        let project_cells = match self.projects.get(project_id) {
+            Some(project) => project.touched_cells(),
+            None => return Vec::new(),
+        };
+
+        self.projects
+            .pairs()
+            .filter(|&(other_id, _)| *other_id != project_id)
+            .filter(
+                |&(_, other_project)| match (&project_cells, other_project.touched_cells()) {
+                    (Some(cells), Some(other_cells)) => !cells.is_disjoint(&other_cells),
+                    _ => true,
+                },
+            )
+            .map(|(other_id, _)| *other_id)
+            .collect()
+    }
+
+    pub fn set_project_locked(
+        &mut self,
+        project_id: ProjectID,
+        locked: bool,
+        requester: PlanningUIID<Logic>,
+        key: ClientKey,
+        world: &mut World,
+    ) {
+        let client = match self.verified_client(requester, key, world) {
+            Some(client) => client,
+            None => return,
+        };
+
+        if let Some(access) = self.project_access.get_mut(project_id) {
+            if client == access.owner {
+                access.locked = locked;
+                self.access_version += 1;
+            } else {
+                info(
+                    LOG_T,
+                    format!(
+                        "Client {} tried to (un)lock project of client {}",
+                        client, access.owner
+                    ),
+                    self.id,
+                    world,
+                );
+            }
+        }
+    }
+
+    pub fn approve_project(
+        &mut self,
+        project_id: ProjectID,
+        requester: PlanningUIID<Logic>,
+        key: ClientKey,
+        world: &mut World,
+    ) {
+        let client = match self.verified_client(requester, key, world) {
+            Some(client) => client,
+            None => return,
+        };
+
+        if let Some(access) = self.project_access.get_mut(project_id) {
+            if !access.approved_by.contains(&client) {
+                access.approved_by.push(client);
+                self.access_version += 1;
+            }
+        }
+    }
+
+    /// Either keeps the project's version of a gesture that was changed in the master plan
+    /// in the meantime, or drops the project's changes to it in favor of the master plan
+    pub fn resolve_conflict(
+        &mut self,
+        project_id: ProjectID,
+        gesture_id: GestureID,
+        keep_project_version: bool,
+        requester: PlanningUIID<Logic>,
+        key: ClientKey,
+        world: &mut World,
+    ) {
+        if !self.check_edit_access(project_id, requester, key, world) {
+            return;
+        }
+        self.withdraw_approvals(project_id);
+
+        let project = self
+            .projects
+            .get_mut(project_id)
+            .expect("Project should exist");
+
+        if keep_project_version {
+            let master_step_id = self
+                .master_plan
+                .gestures
+                .get(gesture_id)
+                .map(|&VersionedGesture(_, master_step_id)| master_step_id);
+            project.rebase_gesture(gesture_id, master_step_id);
+        } else {
+            project.drop_gesture(gesture_id);
+        }
+
+        self.ui_state.invalidate(project_id);
+    }
+
+    pub fn set_approval_required(&mut self, approval_required: bool, _: &mut World) {
+        self.approval_required = approval_required;
+        self.access_version += 1;
+    }
+}
+
+pub mod kay_auto;
+pub use self::kay_auto::*;
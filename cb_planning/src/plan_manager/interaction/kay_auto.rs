@@ -10,43 +10,67 @@ use super::*;
 
 
 impl<Logic: PlanningLogic> PlanManagerID<Logic> {
-    pub fn get_all_plans(self, ui: PlanningUIID < Logic >, known_master: KnownHistoryState, known_projects: CHashMap < ProjectID , KnownProjectState >, world: &mut World) {
-        world.send(self.as_raw(), MSG_PlanManager_get_all_plans::<Logic>(ui, known_master, known_projects));
+    pub fn get_all_plans(self, ui: PlanningUIID < Logic >, known_master: KnownHistoryState, known_projects: CHashMap < ProjectID , KnownProjectState >, known_access_version: u32, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_get_all_plans::<Logic>(ui, known_master, known_projects, known_access_version));
     }
     
     pub fn get_project_preview_update(self, ui: PlanningUIID < Logic >, project_id: ProjectID, known_result: KnownPlanResultState < Logic :: PrototypeKind >, world: &mut World) {
         world.send(self.as_raw(), MSG_PlanManager_get_project_preview_update::<Logic>(ui, project_id, known_result));
     }
     
-    pub fn start_new_gesture(self, project_id: ProjectID, new_gesture_id: GestureID, intent: Logic :: GestureIntent, world: &mut World) {
-        world.send(self.as_raw(), MSG_PlanManager_start_new_gesture::<Logic>(project_id, new_gesture_id, intent));
+    pub fn get_project_impact(self, ui: PlanningUIID < Logic >, project_id: ProjectID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_get_project_impact::<Logic>(ui, project_id));
     }
     
-    pub fn set_intent(self, project_id: ProjectID, gesture_id: GestureID, new_intent: Logic :: GestureIntent, is_move_finished: bool, world: &mut World) {
-        world.send(self.as_raw(), MSG_PlanManager_set_intent::<Logic>(project_id, gesture_id, new_intent, is_move_finished));
+    pub fn get_step_impact(self, ui: PlanningUIID < Logic >, project_id: ProjectID, step_id: StepID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_get_step_impact::<Logic>(ui, project_id, step_id));
     }
     
-    pub fn undo(self, project_id: ProjectID, world: &mut World) {
-        world.send(self.as_raw(), MSG_PlanManager_undo(project_id));
+    pub fn start_new_gesture(self, project_id: ProjectID, new_gesture_id: GestureID, intent: Logic :: GestureIntent, requester: PlanningUIID < Logic >, key: ClientKey, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_start_new_gesture::<Logic>(project_id, new_gesture_id, intent, requester, key));
     }
     
-    pub fn redo(self, project_id: ProjectID, world: &mut World) {
-        world.send(self.as_raw(), MSG_PlanManager_redo(project_id));
+    pub fn start_new_gestures(self, project_id: ProjectID, new_gestures: CHashMap < GestureID , Logic :: GestureIntent >, requester: PlanningUIID < Logic >, key: ClientKey, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_start_new_gestures::<Logic>(project_id, new_gestures, requester, key));
+    }
+    
+    pub fn set_intent(self, project_id: ProjectID, gesture_id: GestureID, new_intent: Logic :: GestureIntent, is_move_finished: bool, requester: PlanningUIID < Logic >, key: ClientKey, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_set_intent::<Logic>(project_id, gesture_id, new_intent, is_move_finished, requester, key));
+    }
+    
+    pub fn undo(self, project_id: ProjectID, requester: PlanningUIID < Logic >, key: ClientKey, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_undo::<Logic>(project_id, requester, key));
+    }
+    
+    pub fn redo(self, project_id: ProjectID, requester: PlanningUIID < Logic >, key: ClientKey, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_redo::<Logic>(project_id, requester, key));
+    }
+    
+    pub fn jump_to_step(self, project_id: ProjectID, step_id: StepID, requester: PlanningUIID < Logic >, key: ClientKey, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_jump_to_step::<Logic>(project_id, step_id, requester, key));
     }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_PlanManager_get_all_plans<Logic: PlanningLogic>(pub PlanningUIID < Logic >, pub KnownHistoryState, pub CHashMap < ProjectID , KnownProjectState >);
+struct MSG_PlanManager_get_all_plans<Logic: PlanningLogic>(pub PlanningUIID < Logic >, pub KnownHistoryState, pub CHashMap < ProjectID , KnownProjectState >, pub u32);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_PlanManager_get_project_preview_update<Logic: PlanningLogic>(pub PlanningUIID < Logic >, pub ProjectID, pub KnownPlanResultState < Logic :: PrototypeKind >);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_PlanManager_start_new_gesture<Logic: PlanningLogic>(pub ProjectID, pub GestureID, pub Logic :: GestureIntent);
+struct MSG_PlanManager_get_project_impact<Logic: PlanningLogic>(pub PlanningUIID < Logic >, pub ProjectID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_get_step_impact<Logic: PlanningLogic>(pub PlanningUIID < Logic >, pub ProjectID, pub StepID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_start_new_gesture<Logic: PlanningLogic>(pub ProjectID, pub GestureID, pub Logic :: GestureIntent, pub PlanningUIID < Logic >, pub ClientKey);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_start_new_gestures<Logic: PlanningLogic>(pub ProjectID, pub CHashMap < GestureID , Logic :: GestureIntent >, pub PlanningUIID < Logic >, pub ClientKey);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_PlanManager_set_intent<Logic: PlanningLogic>(pub ProjectID, pub GestureID, pub Logic :: GestureIntent, pub bool);
+struct MSG_PlanManager_set_intent<Logic: PlanningLogic>(pub ProjectID, pub GestureID, pub Logic :: GestureIntent, pub bool, pub PlanningUIID < Logic >, pub ClientKey);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_PlanManager_undo(pub ProjectID);
+struct MSG_PlanManager_undo<Logic: PlanningLogic>(pub ProjectID, pub PlanningUIID < Logic >, pub ClientKey);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_PlanManager_redo(pub ProjectID);
+struct MSG_PlanManager_redo<Logic: PlanningLogic>(pub ProjectID, pub PlanningUIID < Logic >, pub ClientKey);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_jump_to_step<Logic: PlanningLogic>(pub ProjectID, pub StepID, pub PlanningUIID < Logic >, pub ClientKey);
 
 
 #[allow(unused_variables)]
@@ -55,8 +79,8 @@ pub fn auto_setup<Logic: PlanningLogic>(system: &mut ActorSystem) {
     
     
     system.add_handler::<PlanManager<Logic>, _, _>(
-        |&MSG_PlanManager_get_all_plans::<Logic>(ui, ref known_master, ref known_projects), instance, world| {
-            instance.get_all_plans(ui, known_master, known_projects, world); Fate::Live
+        |&MSG_PlanManager_get_all_plans::<Logic>(ui, ref known_master, ref known_projects, known_access_version), instance, world| {
+            instance.get_all_plans(ui, known_master, known_projects, known_access_version, world); Fate::Live
         }, false
     );
     
@@ -67,26 +91,50 @@ pub fn auto_setup<Logic: PlanningLogic>(system: &mut ActorSystem) {
     );
     
     system.add_handler::<PlanManager<Logic>, _, _>(
-        |&MSG_PlanManager_start_new_gesture::<Logic>(project_id, new_gesture_id, ref intent), instance, world| {
-            instance.start_new_gesture(project_id, new_gesture_id, intent, world); Fate::Live
+        |&MSG_PlanManager_get_project_impact::<Logic>(ui, project_id), instance, world| {
+            instance.get_project_impact(ui, project_id, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<PlanManager<Logic>, _, _>(
+        |&MSG_PlanManager_get_step_impact::<Logic>(ui, project_id, step_id), instance, world| {
+            instance.get_step_impact(ui, project_id, step_id, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<PlanManager<Logic>, _, _>(
+        |&MSG_PlanManager_start_new_gesture::<Logic>(project_id, new_gesture_id, ref intent, requester, key), instance, world| {
+            instance.start_new_gesture(project_id, new_gesture_id, intent, requester, key, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<PlanManager<Logic>, _, _>(
+        |&MSG_PlanManager_start_new_gestures::<Logic>(project_id, ref new_gestures, requester, key), instance, world| {
+            instance.start_new_gestures(project_id, new_gestures, requester, key, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<PlanManager<Logic>, _, _>(
+        |&MSG_PlanManager_set_intent::<Logic>(project_id, gesture_id, ref new_intent, is_move_finished, requester, key), instance, world| {
+            instance.set_intent(project_id, gesture_id, new_intent, is_move_finished, requester, key, world); Fate::Live
         }, false
     );
     
     system.add_handler::<PlanManager<Logic>, _, _>(
-        |&MSG_PlanManager_set_intent::<Logic>(project_id, gesture_id, ref new_intent, is_move_finished), instance, world| {
-            instance.set_intent(project_id, gesture_id, new_intent, is_move_finished, world); Fate::Live
+        |&MSG_PlanManager_undo::<Logic>(project_id, requester, key), instance, world| {
+            instance.undo(project_id, requester, key, world); Fate::Live
         }, false
     );
     
     system.add_handler::<PlanManager<Logic>, _, _>(
-        |&MSG_PlanManager_undo(project_id), instance, world| {
-            instance.undo(project_id, world); Fate::Live
+        |&MSG_PlanManager_redo::<Logic>(project_id, requester, key), instance, world| {
+            instance.redo(project_id, requester, key, world); Fate::Live
         }, false
     );
     
     system.add_handler::<PlanManager<Logic>, _, _>(
-        |&MSG_PlanManager_redo(project_id), instance, world| {
-            instance.redo(project_id, world); Fate::Live
+        |&MSG_PlanManager_jump_to_step::<Logic>(project_id, step_id, requester, key), instance, world| {
+            instance.jump_to_step(project_id, step_id, requester, key, world); Fate::Live
         }, false
     );
 }
\ No newline at end of file
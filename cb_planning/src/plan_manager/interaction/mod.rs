@@ -2,9 +2,11 @@ use kay::{World};
 use compact::{CHashMap, COption};
 use descartes::AreaError;
 use ::{PlanHistory, PlanResult, ActionGroups, KnownHistoryState, KnownProjectState, ProjectUpdate,
-PlanningLogic, GestureID, Gesture, Plan, KnownPlanResultState};
+PlanningLogic, GestureID, Gesture, Plan, KnownPlanResultState, VersionedGesture, StepID,
+ProjectImpact};
 use super::{PlanManager, PlanManagerID, ProjectID};
 use super::ui::PlanningUIID;
+use cb_util::client_keys::{ClientKey, client_of};
 use cb_util::log::error;
 const LOG_T: &str = "Planning Interaction";
 
@@ -42,6 +44,7 @@ impl<Logic: PlanningLogic> PlanManager<Logic> {
         ui: PlanningUIID<Logic>,
         known_master: &KnownHistoryState,
         known_projects: &CHashMap<ProjectID, KnownProjectState>,
+        known_access_version: u32,
         world: &mut World,
     ) {
         let master_update = self.master_plan.update_for(known_master);
@@ -74,6 +77,19 @@ impl<Logic: PlanningLogic> PlanManager<Logic> {
             )
             .collect();
         ui.on_plans_update(master_update, project_updates_with_removals, world);
+
+        if known_access_version != self.access_version {
+            // the key is only ever sent to the client it is issued for
+            let client = client_of(ui);
+            ui.on_project_access_update(
+                self.project_access.clone(),
+                self.access_version,
+                self.approval_required,
+                client,
+                ClientKey::issue_for(client),
+                world,
+            );
+        }
     }
 
     pub fn get_project_preview_update(
@@ -92,11 +108,92 @@ impl<Logic: PlanningLogic> PlanManager<Logic> {
                 plan_history.clone(),
                 result.update_for(known_result),
                 actions.clone(),
+                result.construction_cost_of(actions),
                 world,
             );
         }
     }
 
+    pub fn get_project_impact(
+        &mut self,
+        ui: PlanningUIID<Logic>,
+        project_id: ProjectID,
+        world: &mut World,
+    ) {
+        self.try_ensure_preview(project_id, world);
+
+        let preview_set = self
+            .ui_state
+            .previews
+            .get(project_id)
+            .expect("Should have previews by now.");
+
+        // the requester still has to learn about overlaps and conflicts when planning failed,
+        // since resolving conflicts might be what makes the project plannable again
+        let mut impact = if let (Some(result), Some(actions)) =
+            (preview_set.result.as_ref(), preview_set.actions.as_ref())
+        {
+            self.master_result.impact_of(result, actions)
+        } else {
+            ProjectImpact::of_failed_planning()
+        };
+
+        impact.overlapping_projects = self.overlapping_projects(project_id).into();
+        impact.conflicting_gestures = self
+            .projects
+            .get(project_id)
+            .expect("Project should exist")
+            .conflicts_with(&self.master_plan)
+            .into();
+        ui.on_project_impact(project_id, impact, world);
+    }
+
+    /// The impact of a project as of one of its steps, to compare alternative branches
+    pub fn get_step_impact(
+        &mut self,
+        ui: PlanningUIID<Logic>,
+        project_id: ProjectID,
+        step_id: StepID,
+        world: &mut World,
+    ) {
+        let steps = match self
+            .projects
+            .get(project_id)
+            .and_then(|project| project.history_up_to(step_id))
+        {
+            Some(steps) => steps,
+            None => return,
+        };
+
+        let step_history = self.master_plan.and_then(&steps);
+
+        match Logic::calculate_result_incrementally(
+            &self.master_plan,
+            &self.master_result,
+            &step_history,
+        ) {
+            Ok(step_result) => {
+                let actions = self.master_result.actions_to(&step_result).0;
+                let impact = self.master_result.impact_of(&step_result, &actions);
+                ui.on_step_impact(project_id, step_id, impact, world);
+            }
+            Err(err) => {
+                error(
+                    LOG_T,
+                    format!("Step Impact Error: {:?}", err),
+                    self.id,
+                    world,
+                );
+                ui.on_step_impact(
+                    project_id,
+                    step_id,
+                    ProjectImpact::of_failed_planning(),
+                    world,
+                );
+            }
+        }
+    }
+
     #[allow(clippy::type_complexity)]
     fn try_ensure_preview(
         &mut self,
@@ -114,7 +211,11 @@ impl<Logic: PlanningLogic> PlanManager<Logic> {
                 .unwrap()
                 .apply_to_with_ongoing(&self.master_plan);
 
-            let maybe_preview_result = match Logic::calculate_result(&preview_history) {
+            let maybe_preview_result = match Logic::calculate_result_incrementally(
+                &self.master_plan,
+                &self.master_result,
+                &preview_history,
+            ) {
                 Ok(preview_plan_result) => Some(preview_plan_result),
                 Err(err) => {
                     let err_str = match err {
@@ -158,8 +259,15 @@ impl<Logic: PlanningLogic> PlanManager<Logic> {
         project_id: ProjectID,
         new_gesture_id: GestureID,
         intent: &Logic::GestureIntent,
-        _: &mut World,
+        requester: PlanningUIID<Logic>,
+        key: ClientKey,
+        world: &mut World,
     ) {
+        if !self.check_edit_access(project_id, requester, key, world) {
+            return;
+        }
+        self.withdraw_approvals(project_id);
+
         let new_gesture = Gesture::new(intent.clone());
 
         let new_step = Plan::from_gestures(Some((new_gesture_id, new_gesture)));
@@ -173,14 +281,50 @@ impl<Logic: PlanningLogic> PlanManager<Logic> {
         self.ui_state.invalidate(project_id);
     }
 
+    /// Adds several gestures at once, as a single step that can be undone
+    pub fn start_new_gestures(
+        &mut self,
+        project_id: ProjectID,
+        new_gestures: &CHashMap<GestureID, Logic::GestureIntent>,
+        requester: PlanningUIID<Logic>,
+        key: ClientKey,
+        world: &mut World,
+    ) {
+        if !self.check_edit_access(project_id, requester, key, world) {
+            return;
+        }
+        self.withdraw_approvals(project_id);
+
+        let new_step = Plan::from_gestures(
+            new_gestures
+                .pairs()
+                .map(|(gesture_id, intent)| (*gesture_id, Gesture::new(intent.clone()))),
+        );
+
+        self.projects
+            .get_mut(project_id)
+            .unwrap()
+            .set_ongoing_step(new_step);
+        self.projects.get_mut(project_id).unwrap().start_new_step();
+
+        self.ui_state.invalidate(project_id);
+    }
+
     pub fn set_intent(
         &mut self,
         project_id: ProjectID,
         gesture_id: GestureID,
         new_intent: &Logic::GestureIntent,
         is_move_finished: bool,
-        _: &mut World,
+        requester: PlanningUIID<Logic>,
+        key: ClientKey,
+        world: &mut World,
     ) {
+        if !self.check_edit_access(project_id, requester, key, world) {
+            return;
+        }
+        self.withdraw_approvals(project_id);
+
         let current_change = {
             let current_gesture = self.get_current_version_of(gesture_id, project_id);
 
@@ -192,6 +336,15 @@ impl<Logic: PlanningLogic> PlanManager<Logic> {
             Plan::from_gestures(Some((gesture_id, new_gesture)))
         };
 
+        if let Some(&VersionedGesture(_, master_step_id)) =
+            self.master_plan.gestures.get(gesture_id)
+        {
+            self.projects
+                .get_mut(project_id)
+                .unwrap()
+                .set_based_on(gesture_id, master_step_id);
+        }
+
         self.projects
             .get_mut(project_id)
             .unwrap()
@@ -206,15 +359,60 @@ impl<Logic: PlanningLogic> PlanManager<Logic> {
         }
     }
 
-    pub fn undo(&mut self, project_id: ProjectID, _: &mut World) {
+    pub fn undo(
+        &mut self,
+        project_id: ProjectID,
+        requester: PlanningUIID<Logic>,
+        key: ClientKey,
+        world: &mut World,
+    ) {
+        if !self.check_edit_access(project_id, requester, key, world) {
+            return;
+        }
+        self.withdraw_approvals(project_id);
+
         self.projects.get_mut(project_id).unwrap().undo();
         self.ui_state.invalidate(project_id);
     }
 
-    pub fn redo(&mut self, project_id: ProjectID, _: &mut World) {
+    pub fn redo(
+        &mut self,
+        project_id: ProjectID,
+        requester: PlanningUIID<Logic>,
+        key: ClientKey,
+        world: &mut World,
+    ) {
+        if !self.check_edit_access(project_id, requester, key, world) {
+            return;
+        }
+        self.withdraw_approvals(project_id);
+
         self.projects.get_mut(project_id).unwrap().redo();
         self.ui_state.invalidate(project_id);
     }
+
+    pub fn jump_to_step(
+        &mut self,
+        project_id: ProjectID,
+        step_id: StepID,
+        requester: PlanningUIID<Logic>,
+        key: ClientKey,
+        world: &mut World,
+    ) {
+        if !self.check_edit_access(project_id, requester, key, world) {
+            return;
+        }
+        self.withdraw_approvals(project_id);
+
+        if self
+            .projects
+            .get_mut(project_id)
+            .unwrap()
+            .jump_to_step(step_id)
+        {
+            self.ui_state.invalidate(project_id);
+        }
+    }
 }
 
 pub mod kay_auto;
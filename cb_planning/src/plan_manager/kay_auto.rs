@@ -62,12 +62,16 @@ impl<Logic: PlanningLogic + 'static> PlanManagerID<Logic> {
         id
     }
     
-    pub fn start_new_project(self, project_id: ProjectID, world: &mut World) {
-        world.send(self.as_raw(), MSG_PlanManager_start_new_project(project_id));
+    pub fn start_new_project(self, project_id: ProjectID, requester: PlanningUIID < Logic >, key: ClientKey, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_start_new_project::<Logic>(project_id, requester, key));
     }
     
-    pub fn implement(self, project_id: ProjectID, world: &mut World) {
-        world.send(self.as_raw(), MSG_PlanManager_implement(project_id));
+    pub fn import_project(self, project_id: ProjectID, project: Project < Logic :: GestureIntent >, requester: PlanningUIID < Logic >, key: ClientKey, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_import_project::<Logic>(project_id, project, requester, key));
+    }
+    
+    pub fn implement(self, project_id: ProjectID, confirmed_overlaps: CVec < ProjectID >, requester: PlanningUIID < Logic >, key: ClientKey, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_implement::<Logic>(project_id, confirmed_overlaps, requester, key));
     }
     
     pub fn implement_artificial_project(self, project: Project < Logic :: GestureIntent >, based_on: CVec < PrototypeID >, world: &mut World) {
@@ -78,9 +82,11 @@ impl<Logic: PlanningLogic + 'static> PlanManagerID<Logic> {
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_PlanManager_spawn<Logic: PlanningLogic + 'static>(pub PlanManagerID<Logic>, );
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_PlanManager_start_new_project(pub ProjectID);
+struct MSG_PlanManager_start_new_project<Logic: PlanningLogic + 'static>(pub ProjectID, pub PlanningUIID < Logic >, pub ClientKey);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_PlanManager_implement(pub ProjectID);
+struct MSG_PlanManager_import_project<Logic: PlanningLogic + 'static>(pub ProjectID, pub Project < Logic :: GestureIntent >, pub PlanningUIID < Logic >, pub ClientKey);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_implement<Logic: PlanningLogic + 'static>(pub ProjectID, pub CVec < ProjectID >, pub PlanningUIID < Logic >, pub ClientKey);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_PlanManager_implement_artificial_project<Logic: PlanningLogic + 'static>(pub Project < Logic :: GestureIntent >, pub CVec < PrototypeID >);
 
@@ -97,14 +103,20 @@ pub fn auto_setup<Logic: PlanningLogic + 'static>(system: &mut ActorSystem) {
     );
     
     system.add_handler::<PlanManager<Logic>, _, _>(
-        |&MSG_PlanManager_start_new_project(project_id), instance, world| {
-            instance.start_new_project(project_id, world); Fate::Live
+        |&MSG_PlanManager_start_new_project::<Logic>(project_id, requester, key), instance, world| {
+            instance.start_new_project(project_id, requester, key, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<PlanManager<Logic>, _, _>(
+        |&MSG_PlanManager_import_project::<Logic>(project_id, ref project, requester, key), instance, world| {
+            instance.import_project(project_id, project, requester, key, world); Fate::Live
         }, false
     );
     
     system.add_handler::<PlanManager<Logic>, _, _>(
-        |&MSG_PlanManager_implement(project_id), instance, world| {
-            instance.implement(project_id, world); Fate::Live
+        |&MSG_PlanManager_implement::<Logic>(project_id, ref confirmed_overlaps, requester, key), instance, world| {
+            instance.implement(project_id, confirmed_overlaps, requester, key, world); Fate::Live
         }, false
     );
     
@@ -10,6 +10,10 @@ const LOG_T: &str = "Planning";
 pub mod interaction;
 use self::interaction::PlanManagerUIState;
 pub mod ui;
+use self::ui::PlanningUIID;
+pub mod collaboration;
+use self::collaboration::ProjectAccess;
+use cb_util::client_keys::ClientKey;
 
 #[derive(Copy, Clone, Hash, PartialEq, Eq, Serialize, Deserialize, Debug)]
 pub struct ProjectID(pub Uuid);
@@ -28,6 +32,9 @@ pub struct PlanManager<Logic: PlanningLogic + 'static> {
     master_result: PlanResult<Logic::PrototypeKind>,
     projects: CHashMap<ProjectID, Project<Logic::GestureIntent>>,
     implemented_projects: CHashMap<ProjectID, Project<Logic::GestureIntent>>,
+    project_access: CHashMap<ProjectID, ProjectAccess>,
+    access_version: u32,
+    approval_required: bool,
     ui_state: PlanManagerUIState<Logic>,
 }
 
@@ -41,6 +48,10 @@ impl<Logic: PlanningLogic + 'static> PlanManager<Logic> {
             master_result: PlanResult::new(),
             projects: CHashMap::new(),
             implemented_projects: CHashMap::new(),
+            project_access: CHashMap::new(),
+            // UIs start out knowing version 0
+            access_version: 1,
+            approval_required: false,
             ui_state: PlanManagerUIState::new(),
         }
     }
@@ -69,18 +80,138 @@ impl<Logic: PlanningLogic + 'static> PlanManager<Logic> {
             .expect("Expected gesture (that point should be added to) to exist!")
     }
 
-    pub fn start_new_project(&mut self, project_id: ProjectID, _: &mut World) {
+    /// Lets the requester know if the project couldn't be created, so it doesn't keep
+    /// a project that doesn't exist
+    pub fn start_new_project(
+        &mut self,
+        project_id: ProjectID,
+        requester: PlanningUIID<Logic>,
+        key: ClientKey,
+        world: &mut World,
+    ) {
+        let client = match self.verified_client(requester, key, world) {
+            Some(client) => client,
+            None => {
+                requester.on_project_rejected(
+                    project_id,
+                    "Not connected to the server yet".to_owned().into(),
+                    world,
+                );
+                return;
+            }
+        };
+
+        if self.projects.contains_key(project_id)
+            || self.implemented_projects.contains_key(project_id)
+        {
+            requester.on_project_rejected(
+                project_id,
+                "Project already exists".to_owned().into(),
+                world,
+            );
+            return;
+        }
+
         self.projects.insert(project_id, Project::new());
+        self.project_access
+            .insert(project_id, ProjectAccess::new(client));
+        self.access_version += 1;
+    }
+
+    /// Adds a project exported from this or another city as a new project
+    pub fn import_project(
+        &mut self,
+        project_id: ProjectID,
+        project: &Project<Logic::GestureIntent>,
+        requester: PlanningUIID<Logic>,
+        key: ClientKey,
+        world: &mut World,
+    ) {
+        if let Err(inconsistency) = project.check_history_consistency() {
+            error(
+                LOG_T,
+                format!("Rejected imported project: {}", inconsistency),
+                self.id,
+                world,
+            );
+            requester.on_project_rejected(project_id, inconsistency.into(), world);
+            return;
+        }
+
+        self.start_new_project(project_id, requester, key, world);
+        if self.projects.contains_key(project_id) {
+            self.projects.insert(project_id, project.with_new_ids());
+        }
     }
 
-    pub fn implement(&mut self, project_id: ProjectID, world: &mut World) {
+    /// Projects overlapping with other pending projects are only implemented if the requester
+    /// confirmed all of the overlaps, which it learns about from the project's impact
+    pub fn implement(
+        &mut self,
+        project_id: ProjectID,
+        confirmed_overlaps: &CVec<ProjectID>,
+        requester: PlanningUIID<Logic>,
+        key: ClientKey,
+        world: &mut World,
+    ) {
+        if !self.check_implement_access(project_id, requester, key, world) {
+            return;
+        }
+
+        let conflicting_gestures = self
+            .projects
+            .get(project_id)
+            .expect("Project should exist")
+            .conflicts_with(&self.master_plan);
+        if !conflicting_gestures.is_empty() {
+            info(
+                LOG_T,
+                format!(
+                    "Project changes gestures that were changed in the master plan since: {:?}",
+                    conflicting_gestures
+                ),
+                self.id,
+                world,
+            );
+            // lets the requester know which conflicts need to be resolved
+            self.get_project_impact(requester, project_id, world);
+            return;
+        }
+
+        let unconfirmed_overlaps = self
+            .overlapping_projects(project_id)
+            .into_iter()
+            .filter(|other_id| !confirmed_overlaps.contains(other_id))
+            .collect::<Vec<_>>();
+        if !unconfirmed_overlaps.is_empty() {
+            info(
+                LOG_T,
+                format!(
+                    "Project overlaps with pending projects that weren't confirmed: {:?}",
+                    unconfirmed_overlaps
+                ),
+                self.id,
+                world,
+            );
+            // lets the requester know which overlaps need to be confirmed
+            self.get_project_impact(requester, project_id, world);
+            return;
+        }
+
+        self.implement_unchecked(project_id, world);
+    }
+
+    fn implement_unchecked(&mut self, project_id: ProjectID, world: &mut World) {
         let project = self
             .projects
             .remove(project_id)
             .expect("Project should exist");
 
+        let previous_master_plan = self.master_plan.clone();
         self.master_plan = project.apply_to(&self.master_plan);
 
+        // implemented results are what the world is built from, so they are
+        // always calculated fully, only previews are calculated incrementally
         match Logic::calculate_result(&self.master_plan) {
             Ok(result) => {
                 let (actions, new_prototypes) = self.master_result.actions_to(&result);
@@ -91,6 +222,9 @@ impl<Logic: PlanningLogic + 'static> PlanManager<Logic> {
                 );
                 self.implemented_projects.insert(project_id, project);
                 self.master_result = result;
+                if self.project_access.remove(project_id).is_some() {
+                    self.access_version += 1;
+                }
 
                 self.ui_state.invalidate_all();
             }
@@ -102,6 +236,10 @@ impl<Logic: PlanningLogic + 'static> PlanManager<Logic> {
                     _ => format!("Implement Plan Error: {:?}", err),
                 };
                 error(LOG_T, err_str, self.id, world);
+
+                // keep master plan and result consistent, since later results build on both
+                self.master_plan = previous_master_plan;
+                self.projects.insert(project_id, project);
             }
         }
     }
@@ -118,7 +256,7 @@ impl<Logic: PlanningLogic + 'static> PlanManager<Logic> {
         {
             let project_id = ProjectID::new();
             self.projects.insert(project_id, project.clone());
-            self.implement(project_id, world);
+            self.implement_unchecked(project_id, world);
         } else {
             info(
                 LOG_T,
@@ -134,6 +272,7 @@ pub fn setup<Logic: PlanningLogic + 'static>(system: &mut ActorSystem) {
     system.register::<PlanManager<Logic>>();
     auto_setup::<Logic>(system);
     interaction::auto_setup::<Logic>(system);
+    collaboration::auto_setup::<Logic>(system);
     ui::auto_setup::<Logic>(system);
 }
 
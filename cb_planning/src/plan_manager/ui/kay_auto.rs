@@ -54,14 +54,34 @@ impl<Logic: PlanningLogic> PlanningUIID<Logic> {
         world.send(self.as_raw(), MSG_PlanningUI_on_plans_update::<Logic>(master_update, project_updates));
     }
     
-    pub fn on_project_preview_update(self, project_id: ProjectID, effective_history: PlanHistory < Logic :: GestureIntent >, result_update: PlanResultUpdate < Logic :: PrototypeKind >, new_actions: ActionGroups, world: &mut World) {
-        world.send(self.as_raw(), MSG_PlanningUI_on_project_preview_update::<Logic>(project_id, effective_history, result_update, new_actions));
+    pub fn on_project_preview_update(self, project_id: ProjectID, effective_history: PlanHistory < Logic :: GestureIntent >, result_update: PlanResultUpdate < Logic :: PrototypeKind >, new_actions: ActionGroups, construction_cost: f32, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanningUI_on_project_preview_update::<Logic>(project_id, effective_history, result_update, new_actions, construction_cost));
+    }
+    
+    pub fn on_project_impact(self, project_id: ProjectID, impact: ProjectImpact, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanningUI_on_project_impact(project_id, impact));
+    }
+    
+    pub fn on_step_impact(self, project_id: ProjectID, step_id: StepID, impact: ProjectImpact, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanningUI_on_step_impact(project_id, step_id, impact));
+    }
+    
+    pub fn on_project_access_update(self, project_access: CHashMap < ProjectID , ProjectAccess >, access_version: u32, approval_required: bool, own_client: ClientID, own_key: ClientKey, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanningUI_on_project_access_update(project_access, access_version, approval_required, own_client, own_key));
+    }
+    
+    pub fn on_project_rejected(self, project_id: ProjectID, reason: CString, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanningUI_on_project_rejected(project_id, reason));
     }
 
     pub fn register_trait(system: &mut ActorSystem) {
         system.register_trait::<PlanningUIRepresentative<Logic>>();
         system.register_trait_message::<MSG_PlanningUI_on_plans_update<Logic>>();
         system.register_trait_message::<MSG_PlanningUI_on_project_preview_update<Logic>>();
+        system.register_trait_message::<MSG_PlanningUI_on_project_impact>();
+        system.register_trait_message::<MSG_PlanningUI_on_step_impact>();
+        system.register_trait_message::<MSG_PlanningUI_on_project_access_update>();
+        system.register_trait_message::<MSG_PlanningUI_on_project_rejected>();
     }
 
     pub fn register_implementor<Act: Actor + PlanningUI<Logic>>(system: &mut ActorSystem) {
@@ -73,8 +93,32 @@ impl<Logic: PlanningLogic> PlanningUIID<Logic> {
         );
         
         system.add_handler::<Act, _, _>(
-            |&MSG_PlanningUI_on_project_preview_update::<Logic>(project_id, ref effective_history, ref result_update, ref new_actions), instance, world| {
-                instance.on_project_preview_update(project_id, effective_history, result_update, new_actions, world); Fate::Live
+            |&MSG_PlanningUI_on_project_preview_update::<Logic>(project_id, ref effective_history, ref result_update, ref new_actions, construction_cost), instance, world| {
+                instance.on_project_preview_update(project_id, effective_history, result_update, new_actions, construction_cost, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_PlanningUI_on_project_impact(project_id, ref impact), instance, world| {
+                instance.on_project_impact(project_id, impact, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_PlanningUI_on_step_impact(project_id, step_id, ref impact), instance, world| {
+                instance.on_step_impact(project_id, step_id, impact, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_PlanningUI_on_project_access_update(ref project_access, access_version, approval_required, own_client, own_key), instance, world| {
+                instance.on_project_access_update(project_access, access_version, approval_required, own_client, own_key, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_PlanningUI_on_project_rejected(project_id, ref reason), instance, world| {
+                instance.on_project_rejected(project_id, reason, world); Fate::Live
             }, false
         );
     }
@@ -83,7 +127,15 @@ impl<Logic: PlanningLogic> PlanningUIID<Logic> {
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_PlanningUI_on_plans_update<Logic: PlanningLogic>(pub PlanHistoryUpdate < Logic :: GestureIntent >, pub CHashMap < ProjectID , ProjectUpdate < Logic :: GestureIntent > >);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_PlanningUI_on_project_preview_update<Logic: PlanningLogic>(pub ProjectID, pub PlanHistory < Logic :: GestureIntent >, pub PlanResultUpdate < Logic :: PrototypeKind >, pub ActionGroups);
+struct MSG_PlanningUI_on_project_preview_update<Logic: PlanningLogic>(pub ProjectID, pub PlanHistory < Logic :: GestureIntent >, pub PlanResultUpdate < Logic :: PrototypeKind >, pub ActionGroups, pub f32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanningUI_on_project_impact(pub ProjectID, pub ProjectImpact);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanningUI_on_step_impact(pub ProjectID, pub StepID, pub ProjectImpact);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanningUI_on_project_access_update(pub CHashMap < ProjectID , ProjectAccess >, pub u32, pub bool, pub ClientID, pub ClientKey);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanningUI_on_project_rejected(pub ProjectID, pub CString);
 
 
 
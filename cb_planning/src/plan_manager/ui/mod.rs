@@ -1,8 +1,10 @@
 use kay::World;
-use compact::CHashMap;
+use compact::{CHashMap, CString};
 use ::{PlanHistory, PlanHistoryUpdate, ProjectUpdate, PlanResultUpdate, ActionGroups,
-PlanningLogic};
+PlanningLogic, ProjectImpact, StepID};
 use super::ProjectID;
+use super::collaboration::ProjectAccess;
+use cb_util::client_keys::{ClientID, ClientKey};
 
 pub trait PlanningUI<Logic: PlanningLogic> {
     fn on_plans_update(
@@ -18,8 +20,37 @@ pub trait PlanningUI<Logic: PlanningLogic> {
         effective_history: &PlanHistory<Logic::GestureIntent>,
         result_update: &PlanResultUpdate<Logic::PrototypeKind>,
         new_actions: &ActionGroups,
+        construction_cost: f32,
         _world: &mut World,
     );
+
+    fn on_project_impact(
+        &mut self,
+        _project_id: ProjectID,
+        impact: &ProjectImpact,
+        _world: &mut World,
+    );
+
+    fn on_step_impact(
+        &mut self,
+        _project_id: ProjectID,
+        _step_id: StepID,
+        impact: &ProjectImpact,
+        _world: &mut World,
+    );
+
+    fn on_project_access_update(
+        &mut self,
+        project_access: &CHashMap<ProjectID, ProjectAccess>,
+        access_version: u32,
+        approval_required: bool,
+        own_client: ClientID,
+        own_key: ClientKey,
+        _world: &mut World,
+    );
+
+    /// A project the UI started or imported wasn't created
+    fn on_project_rejected(&mut self, project_id: ProjectID, reason: &CString, _world: &mut World);
 }
 
 pub mod kay_auto;
@@ -35,7 +35,22 @@ pub struct NetworkConfig {
     pub skip_ratio: usize,
 }
 
-pub fn match_cmd_line_args(version: &str) -> (NetworkConfig, String) {
+#[derive(Clone)]
+pub struct TimeControlConfig {
+    pub max_speed: u16,
+    /// Machine ids of clients that may control time, everyone if `None`
+    pub controllers: Option<Vec<u8>>,
+}
+
+#[derive(Clone)]
+pub struct PlanningConfig {
+    /// Whether projects need approval from a client other than their owner
+    pub approval_required: bool,
+}
+
+pub fn match_cmd_line_args(
+    version: &str,
+) -> (NetworkConfig, TimeControlConfig, PlanningConfig, String) {
     use self::clap::{Arg, App};
     let matches = App::new("citybound")
         .version(version.trim())
@@ -103,8 +118,36 @@ pub fn match_cmd_line_args(version: &str) -> (NetworkConfig, String) {
                 .default_value("5")
                 .help("How many network turns to skip if server/client are ahead"),
         )
+        .arg(
+            Arg::with_name("max-speed")
+                .long("max-speed")
+                .value_name("n")
+                .default_value("32")
+                .help("The highest simulation speed clients can choose"),
+        )
+        .arg(
+            Arg::with_name("time-controllers")
+                .long("time-controllers")
+                .value_name("all/client ids")
+                .default_value("all")
+                .help("Which clients may pause or change the speed of time, comma-separated"),
+        )
+        .arg(
+            Arg::with_name("require-project-approval")
+                .long("require-project-approval")
+                .help("Only implement projects that another client has approved"),
+        )
         .get_matches();
 
+    let controllers = match matches.value_of("time-controllers").unwrap() {
+        "all" => None,
+        ids => Some(
+            ids.split(',')
+                .map(|id| id.trim().parse().expect("Invalid client id"))
+                .collect(),
+        ),
+    };
+
     (
         NetworkConfig {
             serve_host_port: matches.value_of("bind").unwrap().to_owned(),
@@ -114,6 +157,13 @@ pub fn match_cmd_line_args(version: &str) -> (NetworkConfig, String) {
             ok_turn_dist: matches.value_of("ok-turn-dist").unwrap().parse().unwrap(),
             skip_ratio: matches.value_of("skip-ratio").unwrap().parse().unwrap(),
         },
+        TimeControlConfig {
+            max_speed: matches.value_of("max-speed").unwrap().parse().unwrap(),
+            controllers,
+        },
+        PlanningConfig {
+            approval_required: matches.is_present("require-project-approval"),
+        },
         matches.value_of("CITY_FOLDER").unwrap().to_owned(),
     )
 }
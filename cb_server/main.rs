@@ -15,7 +15,8 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 fn main() {
-    let (network_config, city_folder) = init::match_cmd_line_args(VERSION);
+    let (network_config, time_control_config, planning_config, city_folder) =
+        init::match_cmd_line_args(VERSION);
 
     init::print_start_message(VERSION, &network_config);
 
@@ -71,6 +72,20 @@ fn main() {
         } else {
             cb_simulation::spawn_for_server(world)
         };
+        time.configure_control(
+            cb_simulation::cb_time::actors::ui::TimeControlSettings {
+                max_speed: time_control_config.max_speed,
+                allow_all: time_control_config.controllers.is_none(),
+                controllers: time_control_config
+                    .controllers
+                    .clone()
+                    .unwrap_or_default()
+                    .into(),
+            },
+            world,
+        );
+        cb_simulation::planning::CBPlanManagerID::global_first(world)
+            .set_approval_required(planning_config.approval_required, world);
         println!(
             "Simulation running.\n(You can stop this process at any point and the savegame should \
              be fine)"
@@ -0,0 +1,140 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for CityBudget {
+    type ID = CityBudgetID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct CityBudgetID {
+    _raw_id: RawID
+}
+
+impl Copy for CityBudgetID {}
+impl Clone for CityBudgetID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for CityBudgetID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "CityBudgetID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for CityBudgetID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for CityBudgetID {
+    fn eq(&self, other: &CityBudgetID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for CityBudgetID {}
+
+impl TypedID for CityBudgetID {
+    type Target = CityBudget;
+
+    fn from_raw(id: RawID) -> Self {
+        CityBudgetID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl CityBudgetID {
+    pub fn spawn(time: TimeID, world: &mut World) -> Self {
+        let id = CityBudgetID::from_raw(world.allocate_instance_id::<CityBudget>());
+        let swarm = world.local_broadcast::<CityBudget>();
+        world.send(swarm, MSG_CityBudget_spawn(id, time));
+        id
+    }
+    
+    pub fn levy_on_deal(self, money: ResourceAmount, provider: HouseholdID, requester: HouseholdID, world: &mut World) {
+        world.send(self.as_raw(), MSG_CityBudget_levy_on_deal(money, provider, requester));
+    }
+    
+    pub fn receive(self, item: BudgetItem, amount: ResourceAmount, world: &mut World) {
+        world.send(self.as_raw(), MSG_CityBudget_receive(item, amount));
+    }
+    
+    pub fn charge(self, item: BudgetItem, amount: ResourceAmount, world: &mut World) {
+        world.send(self.as_raw(), MSG_CityBudget_charge(item, amount));
+    }
+    
+    pub fn add_maintained_lane_length(self, length: f32, world: &mut World) {
+        world.send(self.as_raw(), MSG_CityBudget_add_maintained_lane_length(length));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_CityBudget_spawn(pub CityBudgetID, pub TimeID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_CityBudget_levy_on_deal(pub ResourceAmount, pub HouseholdID, pub HouseholdID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_CityBudget_receive(pub BudgetItem, pub ResourceAmount);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_CityBudget_charge(pub BudgetItem, pub ResourceAmount);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_CityBudget_add_maintained_lane_length(pub f32);
+
+impl Into<ConfigUserID<TaxRates>> for CityBudgetID {
+    fn into(self) -> ConfigUserID<TaxRates> {
+        ConfigUserID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<SleeperID> for CityBudgetID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    ConfigUserID::<TaxRates>::register_implementor::<CityBudget>(system);
+    SleeperID::register_implementor::<CityBudget>(system);
+    system.add_spawner::<CityBudget, _, _>(
+        |&MSG_CityBudget_spawn(id, time), world| {
+            CityBudget::spawn(id, time, world)
+        }, false
+    );
+    
+    system.add_handler::<CityBudget, _, _>(
+        |&MSG_CityBudget_levy_on_deal(money, provider, requester), instance, world| {
+            instance.levy_on_deal(money, provider, requester, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<CityBudget, _, _>(
+        |&MSG_CityBudget_receive(item, amount), instance, world| {
+            instance.receive(item, amount, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<CityBudget, _, _>(
+        |&MSG_CityBudget_charge(item, amount), instance, world| {
+            instance.charge(item, amount, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<CityBudget, _, _>(
+        |&MSG_CityBudget_add_maintained_lane_length(length), instance, world| {
+            instance.add_maintained_lane_length(length, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,222 @@
+use kay::{ActorSystem, World};
+use compact::{CHashMap, COption};
+use cb_time::actors::{TimeID, Sleeper, SleeperID};
+use cb_time::units::{Duration, Instant};
+use economy::resources::ResourceAmount;
+use economy::households::HouseholdID;
+use land_use::buildings::BuildingID;
+use cb_util::config_manager::{Config, Name, ConfigManager, ConfigManagerID, ConfigUser, ConfigUserID};
+use cb_util::log::debug;
+const LOG_T: &str = "City Budget";
+
+/// Construction cost of a road lane, per meter
+pub const LANE_CONSTRUCTION_COST_PER_METER: ResourceAmount = 20.0;
+/// Construction cost of paved areas like intersections, per square meter
+pub const PAVING_CONSTRUCTION_COST_PER_SQUARE_METER: ResourceAmount = 2.0;
+/// Cost of servicing newly zoned land, per square meter
+pub const LOT_CONSTRUCTION_COST_PER_SQUARE_METER: ResourceAmount = 0.5;
+/// Ongoing maintenance cost of a road lane, per meter and day
+pub const LANE_MAINTENANCE_COST_PER_METER_AND_DAY: ResourceAmount = 0.05;
+
+const INITIAL_TREASURY: ResourceAmount = 1_000_000.0;
+const BUDGET_PACE: Duration = Duration(60 * 60);
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct TaxRates {
+    /// Levied on buildings per square meter of lot and per day,
+    /// split among their households
+    pub property_per_square_meter_and_day: ResourceAmount,
+    /// Share of wages paid by the household receiving them
+    pub income: f32,
+    /// Share of the price of goods paid by the household selling them
+    pub sales: f32,
+}
+
+impl Config for TaxRates {}
+
+/// The config entry holding the tax rates of the city
+pub fn city_tax_rates_name() -> Name {
+    Name::from("City").unwrap()
+}
+
+impl Default for TaxRates {
+    fn default() -> Self {
+        TaxRates {
+            property_per_square_meter_and_day: 0.01,
+            income: 0.1,
+            sales: 0.05,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum BudgetItem {
+    PropertyTax,
+    IncomeTax,
+    SalesTax,
+    Construction,
+    Maintenance,
+}
+
+/// Income (positive) and expenses (negative) per item since the city was founded
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize)]
+pub struct Ledger {
+    pub property_tax: ResourceAmount,
+    pub income_tax: ResourceAmount,
+    pub sales_tax: ResourceAmount,
+    pub construction: ResourceAmount,
+    pub maintenance: ResourceAmount,
+}
+
+impl Ledger {
+    fn book(&mut self, item: BudgetItem, amount: ResourceAmount) {
+        *match item {
+            BudgetItem::PropertyTax => &mut self.property_tax,
+            BudgetItem::IncomeTax => &mut self.income_tax,
+            BudgetItem::SalesTax => &mut self.sales_tax,
+            BudgetItem::Construction => &mut self.construction,
+            BudgetItem::Maintenance => &mut self.maintenance,
+        } += amount;
+    }
+}
+
+#[derive(Compact, Clone)]
+pub struct CityBudget {
+    id: CityBudgetID,
+    time: TimeID,
+    pub treasury: ResourceAmount,
+    pub tax_rates: TaxRates,
+    tax_rates_config: CHashMap<Name, TaxRates>,
+    pub ledger: Ledger,
+    maintained_lane_length: f32,
+}
+
+impl CityBudget {
+    pub fn spawn(id: CityBudgetID, time: TimeID, world: &mut World) -> CityBudget {
+        time.wake_up_in(BUDGET_PACE.into(), id.into(), world);
+
+        let budget = CityBudget {
+            id,
+            time,
+            treasury: INITIAL_TREASURY,
+            tax_rates: TaxRates::default(),
+            tax_rates_config: CHashMap::new(),
+            ledger: Ledger::default(),
+            maintained_lane_length: 0.0,
+        };
+        budget.get_initial_config(world);
+        budget
+    }
+
+    fn book(&mut self, item: BudgetItem, amount: ResourceAmount) {
+        self.treasury += amount;
+        self.ledger.book(item, amount);
+    }
+
+    /// Levies income or sales tax on a deal, depending on which way its money flows.
+    /// The paying household books the tax with us, unless it is exempt.
+    pub fn levy_on_deal(
+        &mut self,
+        money: ResourceAmount,
+        provider: HouseholdID,
+        requester: HouseholdID,
+        world: &mut World,
+    ) {
+        let (item, tax, payer) = if money > 0.0 {
+            (
+                BudgetItem::IncomeTax,
+                money * self.tax_rates.income,
+                requester,
+            )
+        } else {
+            (
+                BudgetItem::SalesTax,
+                -money * self.tax_rates.sales,
+                provider,
+            )
+        };
+
+        if tax > 0.0 {
+            payer.pay_tax(tax, item, self.id, world);
+        }
+    }
+
+    pub fn receive(&mut self, item: BudgetItem, amount: ResourceAmount, _: &mut World) {
+        self.book(item, amount);
+    }
+
+    pub fn charge(&mut self, item: BudgetItem, amount: ResourceAmount, world: &mut World) {
+        self.book(item, -amount);
+        debug(
+            LOG_T,
+            format!(
+                "Charged {:.0} for {:?}, treasury: {:.0}",
+                amount, item, self.treasury
+            ),
+            self.id,
+            world,
+        );
+    }
+
+    /// Lanes report their length when they are built (positive) and removed (negative)
+    pub fn add_maintained_lane_length(&mut self, length: f32, _: &mut World) {
+        self.maintained_lane_length += length;
+    }
+}
+
+impl ConfigUser<TaxRates> for CityBudget {
+    fn local_cache(&mut self) -> &mut CHashMap<Name, TaxRates> {
+        &mut self.tax_rates_config
+    }
+
+    fn on_config_change(&mut self, name: Name, maybe_value: &COption<TaxRates>, world: &mut World) {
+        self.apply_config_change(name, maybe_value, world);
+        self.tax_rates = self
+            .tax_rates_config
+            .get(city_tax_rates_name())
+            .cloned()
+            .unwrap_or_default();
+    }
+}
+
+impl Sleeper for CityBudget {
+    fn wake(&mut self, _current_instant: Instant, world: &mut World) {
+        let days = BUDGET_PACE.as_days();
+
+        let maintenance =
+            self.maintained_lane_length * LANE_MAINTENANCE_COST_PER_METER_AND_DAY * days;
+        if maintenance > 0.0 {
+            self.charge(BudgetItem::Maintenance, maintenance, world);
+        }
+
+        BuildingID::global_broadcast(world).levy_property_tax(
+            self.tax_rates.property_per_square_meter_and_day * days,
+            self.id,
+            world,
+        );
+
+        self.time
+            .wake_up_in(BUDGET_PACE.into(), self.id.into(), world);
+    }
+}
+
+pub fn default_tax_rates() -> CHashMap<Name, TaxRates> {
+    Some((city_tax_rates_name(), TaxRates::default()))
+        .into_iter()
+        .collect()
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<ConfigManager<TaxRates>>();
+    ::cb_util::config_manager::auto_setup::<TaxRates>(system);
+    system.register::<CityBudget>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World, time: TimeID) {
+    ConfigManagerID::<TaxRates>::spawn(default_tax_rates(), world);
+    CityBudgetID::spawn(time, world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
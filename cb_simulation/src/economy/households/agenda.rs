@@ -0,0 +1,227 @@
+use compact::CVec;
+use cb_time::units::{Duration, Instant, TimeOfDay};
+use economy::resources::Resource;
+use ordered_float::OrderedFloat;
+
+/// Granularity in which a day is planned
+const AGENDA_SLOT: Duration = Duration(2 * 60 * 60);
+const AGENDA_SLOTS_PER_DAY: u32 = 12;
+
+/// Something a member plans to take care of, as soon as `not_before` has passed
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct AgendaItem {
+    pub resource: Resource,
+    pub not_before: Instant,
+}
+
+/// The plan of a member for one sim-day, as a chain of tasks in the order
+/// in which they should be done. Members only fall back to dealing with
+/// whatever their most grave problem is when nothing on their agenda is due.
+#[derive(Compact, Clone, Debug, Serialize, Deserialize)]
+pub struct Agenda {
+    /// The day (in midnights since the start) this agenda was planned for,
+    /// `None` if it needs to be (re)planned
+    pub planned_for_day: Option<usize>,
+    pub items: CVec<AgendaItem>,
+    /// The slot of the item that was taken care of last. Items of the slot right
+    /// after it form a trip chain with it, so a member who is still out takes care
+    /// of them on the way instead of going home first (like shopping after work)
+    pub chained_from: Option<Instant>,
+}
+
+impl Default for Agenda {
+    fn default() -> Self {
+        Agenda {
+            planned_for_day: None,
+            items: CVec::new(),
+            chained_from: None,
+        }
+    }
+}
+
+impl Agenda {
+    /// Plans the rest of the current day by scheduling each resource whenever its
+    /// importance rises, which gives work in the morning, meals, shopping and sleep at night
+    pub fn plan<F: Fn(Resource, TimeOfDay) -> f32>(
+        instant: Instant,
+        resources: &[Resource],
+        importance: F,
+    ) -> Agenda {
+        let (hours, minutes) = TimeOfDay::from(instant).hours_minutes();
+        let seconds_into_day = (hours * 60 + minutes) as u32 * 60;
+        let mut items = Vec::new();
+
+        for &resource in resources {
+            let mut previous_importance =
+                importance(resource, TimeOfDay::new(0, 0).earlier_by(AGENDA_SLOT));
+
+            for slot in 0..AGENDA_SLOTS_PER_DAY {
+                let slot_start = Duration(slot * AGENDA_SLOT.0);
+                let slot_importance =
+                    importance(resource, TimeOfDay::new(0, 0).later_by(slot_start));
+
+                // slots that are already over today are skipped, but still
+                // determine whether importance rises in the following slot
+                if slot_importance > previous_importance
+                    && slot_start.0 + AGENDA_SLOT.0 > seconds_into_day
+                {
+                    let not_before = if slot_start.0 > seconds_into_day {
+                        instant + Duration(slot_start.0 - seconds_into_day)
+                    } else {
+                        instant
+                    };
+                    items.push((
+                        AgendaItem {
+                            resource,
+                            not_before,
+                        },
+                        slot_importance,
+                    ));
+                }
+
+                previous_importance = slot_importance;
+            }
+        }
+
+        items.sort_by_key(|&(item, importance)| (item.not_before, OrderedFloat(-importance)));
+
+        Agenda {
+            planned_for_day: Some(instant.days_since_start()),
+            items: items.into_iter().map(|(item, _)| item).collect(),
+            chained_from: None,
+        }
+    }
+
+    pub fn needs_planning(&self, instant: Instant) -> bool {
+        self.planned_for_day != Some(instant.days_since_start())
+    }
+
+    /// Called when the agenda was broken by a failed trip or a withdrawn offer
+    pub fn invalidate(&mut self) {
+        self.planned_for_day = None;
+    }
+
+    /// Takes the first item that is due and still a problem. Due items that aren't
+    /// a problem right now stay on the agenda until the day is over. A member who is
+    /// away from home also takes items that are chained to the last one taken.
+    pub fn take_due(
+        &mut self,
+        instant: Instant,
+        away_from_home: bool,
+        problems: &[(Resource, f32)],
+    ) -> Option<Resource> {
+        let due_until = match self.chained_from {
+            Some(chained_from) if away_from_home => {
+                ::std::cmp::max(instant, chained_from + AGENDA_SLOT)
+            }
+            _ => instant,
+        };
+
+        let maybe_position = self
+            .items
+            .iter()
+            .take_while(|item| item.not_before <= due_until)
+            .position(|item| {
+                problems
+                    .iter()
+                    .any(|&(resource, _)| resource == item.resource)
+            });
+
+        maybe_position.map(|position| {
+            let item = self.items.remove(position);
+            self.chained_from = Some(item.not_before);
+            item.resource
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use economy::resources::Resource::{Wakefulness, Satiety, Money, Groceries};
+
+    const RESOURCES: [Resource; 4] = [Wakefulness, Satiety, Money, Groceries];
+
+    fn importance(resource: Resource, time: TimeOfDay) -> f32 {
+        match (resource, time.hours_minutes().0) {
+            (Wakefulness, 2..=3) | (Wakefulness, 6..=7) => 2.0,
+            (Satiety, 8..=9) => 3.0,
+            (Money, 8..=11) => 1.0,
+            (Groceries, 10..=11) | (Groceries, 18..=19) => 0.5,
+            _ => 0.0,
+        }
+    }
+
+    fn hours_after_start(hours: usize) -> Instant {
+        Instant::new(0) + Duration::from_hours(hours)
+    }
+
+    #[test]
+    fn plan_schedules_resources_whenever_their_importance_rises() {
+        // the simulation starts at 7:00
+        let agenda = Agenda::plan(Instant::new(0), &RESOURCES, importance);
+
+        let planned = agenda
+            .items
+            .iter()
+            .map(|item| (item.resource, item.not_before))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            planned,
+            vec![
+                (Wakefulness, hours_after_start(0)),
+                (Satiety, hours_after_start(1)),
+                (Money, hours_after_start(1)),
+                (Groceries, hours_after_start(3)),
+                (Groceries, hours_after_start(11)),
+            ]
+        );
+        assert!(!agenda.needs_planning(hours_after_start(16)));
+        assert!(agenda.needs_planning(hours_after_start(17)));
+    }
+
+    #[test]
+    fn take_due_skips_items_that_are_no_problem_right_now() {
+        let mut agenda = Agenda::plan(Instant::new(0), &RESOURCES, importance);
+        let problems = [(Money, 1.0), (Satiety, 0.5)];
+
+        assert_eq!(
+            agenda.take_due(hours_after_start(0), false, &problems),
+            None
+        );
+        assert_eq!(
+            agenda.take_due(hours_after_start(1), false, &problems),
+            Some(Satiety)
+        );
+        assert_eq!(
+            agenda.take_due(hours_after_start(1), false, &problems),
+            Some(Money)
+        );
+        assert_eq!(
+            agenda.take_due(hours_after_start(2), false, &problems),
+            None
+        );
+        assert_eq!(agenda.items.len(), 3);
+    }
+
+    #[test]
+    fn take_due_chains_the_next_slot_while_away_from_home() {
+        let mut agenda = Agenda::plan(Instant::new(0), &RESOURCES, importance);
+        let problems = [(Money, 1.0), (Groceries, 0.5)];
+
+        assert_eq!(
+            agenda.take_due(hours_after_start(1), true, &problems),
+            Some(Money)
+        );
+        assert_eq!(
+            agenda.take_due(hours_after_start(2), false, &problems),
+            None
+        );
+        assert_eq!(
+            agenda.take_due(hours_after_start(2), true, &problems),
+            Some(Groceries)
+        );
+        assert_eq!(agenda.take_due(hours_after_start(4), true, &problems), None);
+    }
+}
@@ -0,0 +1,52 @@
+use compact::CVec;
+use cb_time::units::{Duration, Instant};
+use economy::resources::Resource;
+use super::{MemberIdx, OfferID};
+
+/// How many past decisions are kept per household
+const MAX_TRACED_DECISIONS: usize = 10;
+
+/// An offer that was evaluated while a member was deciding on a task
+#[derive(Copy, Clone, Debug, Serialize)]
+pub struct DecisionCandidate {
+    pub resource: Resource,
+    pub offer: OfferID,
+    pub usefulness: f32,
+    pub travel_time: Duration,
+    pub waiting_time: Duration,
+    /// Whether the offer was open at the time of the decision, closed offers are never chosen
+    pub open: bool,
+}
+
+/// Everything a member considered for one decision, and what was chosen in the end
+#[derive(Compact, Clone, Debug, Serialize)]
+pub struct DecisionTrace {
+    pub member: MemberIdx,
+    pub instant: Instant,
+    pub top_problems: CVec<(Resource, f32)>,
+    pub candidates: CVec<DecisionCandidate>,
+    pub chosen: Option<OfferID>,
+}
+
+/// The most recent decisions of all members of a household, newest last
+#[derive(Compact, Clone, Debug, Serialize)]
+pub struct DecisionHistory {
+    pub traces: CVec<DecisionTrace>,
+}
+
+impl Default for DecisionHistory {
+    fn default() -> Self {
+        DecisionHistory {
+            traces: CVec::new(),
+        }
+    }
+}
+
+impl DecisionHistory {
+    pub fn record(&mut self, trace: DecisionTrace) {
+        if self.traces.len() >= MAX_TRACED_DECISIONS {
+            self.traces.remove(0);
+        }
+        self.traces.push(trace);
+    }
+}
@@ -0,0 +1,146 @@
+use cb_time::units::{Duration, Instant, Ticks};
+use economy::resources::ResourceAmount;
+use super::satisfaction::UNMET_NEEDS_GRAVENESS;
+
+/// Over how long money changes are smoothed into the rolling balance
+const ROLLING_BALANCE_WINDOW: Duration = Duration(3 * 24 * 60 * 60);
+/// How long a household may stay over its debt limit before going bankrupt
+const BANKRUPTCY_GRACE_PERIOD: Duration = Duration(24 * 60 * 60);
+/// How many days of (net) grave unmet needs a household puts up with before emigrating
+const EMIGRATION_PATIENCE_DAYS: f32 = 2.0;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize)]
+pub enum FinancialFailure {
+    Bankruptcy,
+    UnmetNeeds,
+}
+
+#[derive(Compact, Clone, Debug, Serialize)]
+pub struct Finances {
+    /// Smoothed change of money per sim-day
+    pub rolling_balance: ResourceAmount,
+    pub debt: ResourceAmount,
+    pub over_debt_limit_since: Option<Instant>,
+    /// Days of grave unmet needs, slowly forgotten again while needs are met
+    pub unmet_needs_days: f32,
+    last_money: ResourceAmount,
+}
+
+impl Default for Finances {
+    fn default() -> Self {
+        Finances {
+            rolling_balance: 0.0,
+            debt: 0.0,
+            over_debt_limit_since: None,
+            unmet_needs_days: 0.0,
+            last_money: 0.0,
+        }
+    }
+}
+
+impl Finances {
+    pub fn update(
+        &mut self,
+        money: ResourceAmount,
+        max_debt: ResourceAmount,
+        worst_graveness: f32,
+        dt: Duration,
+        now: Instant,
+    ) -> Option<FinancialFailure> {
+        let smoothing = dt.as_seconds() / (dt.as_seconds() + ROLLING_BALANCE_WINDOW.as_seconds());
+        let daily_change = (money - self.last_money) / dt.as_days();
+        self.rolling_balance += smoothing * (daily_change - self.rolling_balance);
+        self.last_money = money;
+        self.debt = (-money).max(0.0);
+
+        if self.debt <= max_debt {
+            self.over_debt_limit_since = None;
+        } else if self.over_debt_limit_since.is_none() {
+            self.over_debt_limit_since = Some(now);
+        }
+
+        self.unmet_needs_days = if worst_graveness > UNMET_NEEDS_GRAVENESS {
+            self.unmet_needs_days + dt.as_days()
+        } else {
+            (self.unmet_needs_days - dt.as_days()).max(0.0)
+        };
+
+        let bankrupt = self.rolling_balance <= 0.0
+            && self
+                .over_debt_limit_since
+                .map(|since| {
+                    now.ticks() >= since.ticks() + Ticks::from(BANKRUPTCY_GRACE_PERIOD).0 as usize
+                })
+                .unwrap_or(false);
+
+        if bankrupt {
+            Some(FinancialFailure::Bankruptcy)
+        } else if self.unmet_needs_days > EMIGRATION_PATIENCE_DAYS {
+            Some(FinancialFailure::UnmetNeeds)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: Duration = Duration(60 * 60);
+
+    fn at_hour(hour: usize) -> Instant {
+        Instant::new(0) + Duration::from_hours(hour)
+    }
+
+    #[test]
+    fn bankruptcy_follows_a_grace_period_over_the_debt_limit() {
+        let mut finances = Finances::default();
+
+        for hour in 0..48 {
+            // briefly getting back under the debt limit restarts the grace period
+            let money = if hour == 12 { -40.0 } else { -100.0 };
+            let failure = finances.update(money, 50.0, 0.0, HOUR, at_hour(hour));
+            let expected = if hour >= 13 + 24 {
+                Some(FinancialFailure::Bankruptcy)
+            } else {
+                None
+            };
+            assert_eq!(failure, expected, "at hour {}", hour);
+        }
+    }
+
+    #[test]
+    fn paying_off_debt_prevents_bankruptcy() {
+        let mut finances = Finances::default();
+        finances.last_money = -1000.0;
+
+        for hour in 0..48 {
+            let money = -1000.0 + 10.0 * (hour + 1) as f32;
+            let failure = finances.update(money, 50.0, 0.0, HOUR, at_hour(hour));
+            assert_eq!(failure, None, "at hour {}", hour);
+        }
+
+        assert!(finances.rolling_balance > 0.0);
+        assert_eq!(finances.over_debt_limit_since, Some(at_hour(0)));
+    }
+
+    #[test]
+    fn unmet_needs_are_slowly_forgotten_while_needs_are_met() {
+        let mut finances = Finances::default();
+        let mut spend_hours = |hours: usize, graveness: f32| {
+            (0..hours)
+                .map(|hour| finances.update(0.0, 0.0, graveness, HOUR, at_hour(hour)))
+                .last()
+                .unwrap()
+        };
+
+        assert_eq!(spend_hours(40, UNMET_NEEDS_GRAVENESS + 1.0), None);
+        assert_eq!(spend_hours(24, 0.0), None);
+        assert_eq!(spend_hours(24, UNMET_NEEDS_GRAVENESS + 1.0), None);
+        assert_eq!(
+            spend_hours(12, UNMET_NEEDS_GRAVENESS + 1.0),
+            Some(FinancialFailure::UnmetNeeds)
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use compact::{CVec, CString};
+use cb_util::config_manager::Config;
+use cb_time::units::{TimeOfDay, OpeningHours, Weekday};
+use economy::resources::{Resource, ResourceAmount, Inventory};
+use economy::market::Deal;
+use economy::households::{MemberIdx, Offer};
+use land_use::buildings::{UnitType, BuildingStyle};
+use land_use::buildings::architecture::materials_and_props::BuildingMaterial;
+use super::harvest::SeasonalYield;
+
+/// Everything that distinguishes one kind of business household from another,
+/// so new kinds can be added as config entries instead of as new actor types
+#[derive(Compact, Clone)]
+pub struct HouseholdKindDefinition {
+    pub household_name: CString,
+    pub member_title: CString,
+    pub n_members: u32,
+    pub unit_type: UnitType,
+    pub building_style: BuildingStyle,
+    /// What the fields of this kind are planted with, if its building style has any
+    pub field_material: Option<BuildingMaterial>,
+    /// Relative likelihood of the development manager picking this kind
+    /// among all kinds that could meet the same unmet demand
+    pub development_share: f32,
+    pub offers: CVec<OfferDefinition>,
+    pub needs: CVec<NeedDefinition>,
+    /// Change of each shared resource per sim-day, positive for production
+    pub daily_rates: Inventory,
+    /// How production varies over the year, on average it matches `daily_rates`
+    pub seasonal_yield: SeasonalYield,
+    /// How far into debt the business may go before risking bankruptcy
+    pub max_debt: ResourceAmount,
+    pub interesting_resources: CVec<Resource>,
+}
+
+impl Config for HouseholdKindDefinition {}
+
+#[derive(Compact, Clone)]
+pub struct OfferDefinition {
+    pub offering_member: MemberIdx,
+    pub opening_hours: OpeningHours,
+    pub deal: Deal,
+    pub max_users: u32,
+    /// How many users can be served at once, the rest has to queue
+    pub service_capacity: u32,
+    pub is_internal: bool,
+}
+
+impl OfferDefinition {
+    pub fn new<H: Into<OpeningHours>>(
+        opening_hours: H,
+        deal: Deal,
+        max_users: u32,
+    ) -> OfferDefinition {
+        OfferDefinition {
+            offering_member: MemberIdx::new(0),
+            opening_hours: opening_hours.into(),
+            deal,
+            max_users,
+            service_capacity: max_users,
+            is_internal: false,
+        }
+    }
+
+    pub fn with_service_capacity(mut self, service_capacity: u32) -> OfferDefinition {
+        self.service_capacity = service_capacity;
+        self
+    }
+
+    pub fn closed_on(mut self, weekday: Weekday) -> OfferDefinition {
+        self.opening_hours = self.opening_hours.closed_on(weekday);
+        self
+    }
+
+    pub fn to_offer(&self) -> Offer {
+        Offer::new(
+            self.offering_member,
+            self.opening_hours,
+            self.deal.clone(),
+            self.max_users as usize,
+            self.is_internal,
+        )
+        .with_service_capacity(self.service_capacity)
+    }
+}
+
+#[derive(Compact, Clone)]
+pub struct NeedDefinition {
+    pub resource: Resource,
+    pub bihourly_importance: [f32; 12],
+}
+
+impl NeedDefinition {
+    pub fn new(resource: Resource, bihourly_importance: [f32; 12]) -> NeedDefinition {
+        NeedDefinition {
+            resource,
+            bihourly_importance,
+        }
+    }
+}
+
+impl HouseholdKindDefinition {
+    pub fn with_seasonal_yield(mut self, seasonal_yield: SeasonalYield) -> HouseholdKindDefinition {
+        self.seasonal_yield = seasonal_yield;
+        self
+    }
+
+    pub fn with_field_material(
+        mut self,
+        field_material: BuildingMaterial,
+    ) -> HouseholdKindDefinition {
+        self.field_material = Some(field_material);
+        self
+    }
+
+    pub fn importance(&self, resource: Resource, time: TimeOfDay) -> f32 {
+        let hour = time.hours_minutes().0;
+
+        self.needs
+            .iter()
+            .find(|need| need.resource == resource)
+            .map(|need| need.bihourly_importance[hour / 2])
+            .unwrap_or(0.0)
+    }
+}
@@ -0,0 +1,100 @@
+use cb_time::units::{DayOfYear, DAYS_PER_YEAR};
+use environment::weather::WeatherConditions;
+
+/// Keeps pasture yields from swinging wildly when grass barely grows (like in winter)
+const PASTURE_BASE_GROWTH: f32 = 0.5;
+
+#[derive(Copy, Clone, Debug)]
+pub struct CropCycle {
+    pub planting: DayOfYear,
+    pub harvest_start: DayOfYear,
+    pub harvest_end: DayOfYear,
+}
+
+/// How the production of a business depends on the seasons and the weather
+#[derive(Copy, Clone, Debug)]
+pub enum SeasonalYield {
+    /// Produces the same every day
+    Constant,
+    /// Produces all year round, more when grass grows better than usual for the season
+    Pasture,
+    /// Only produces during harvest, but all of the year's production,
+    /// depending on how well the crops grew since planting compared to an average year
+    Crop(CropCycle),
+}
+
+/// Tracks growth since planting to determine how much a business produces today
+#[derive(Copy, Clone, Debug)]
+pub struct HarvestState {
+    day: Option<DayOfYear>,
+    production_factor: f32,
+    growth: f32,
+    normal_growth: f32,
+}
+
+impl Default for HarvestState {
+    fn default() -> Self {
+        HarvestState {
+            day: None,
+            production_factor: 1.0,
+            growth: 0.0,
+            normal_growth: 0.0,
+        }
+    }
+}
+
+impl HarvestState {
+    /// Factor on the daily production rates of a business
+    pub fn production_factor(&self) -> f32 {
+        self.production_factor
+    }
+
+    pub fn update(
+        &mut self,
+        seasonal_yield: SeasonalYield,
+        day: DayOfYear,
+        today: WeatherConditions,
+    ) {
+        if self.day == Some(day) {
+            return;
+        }
+        self.day = Some(day);
+
+        let growing_conditions = today.growing_conditions();
+        let normal_growing_conditions = WeatherConditions::normal(day).growing_conditions();
+
+        self.production_factor = match seasonal_yield {
+            SeasonalYield::Constant => 1.0,
+            SeasonalYield::Pasture => {
+                (growing_conditions + PASTURE_BASE_GROWTH)
+                    / (normal_growing_conditions + PASTURE_BASE_GROWTH)
+            }
+            SeasonalYield::Crop(cycle) => {
+                if day == cycle.planting {
+                    self.growth = 0.0;
+                    self.normal_growth = 0.0;
+                }
+
+                if day.is_within(cycle.planting, cycle.harvest_end) {
+                    self.growth += growing_conditions;
+                    self.normal_growth += normal_growing_conditions;
+                }
+
+                if day.is_within(cycle.harvest_start, cycle.harvest_end) {
+                    // businesses that only moved in during harvest get an average one
+                    let relative_growth = if self.normal_growth > 0.0 {
+                        self.growth / self.normal_growth
+                    } else {
+                        1.0
+                    };
+                    let harvest_days =
+                        DayOfYear::days_between(cycle.harvest_start, cycle.harvest_end);
+
+                    relative_growth * DAYS_PER_YEAR as f32 / harvest_days as f32
+                } else {
+                    0.0
+                }
+            }
+        };
+    }
+}
@@ -0,0 +1,127 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for ConfigurableHousehold {
+    type ID = ConfigurableHouseholdID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct ConfigurableHouseholdID {
+    _raw_id: RawID
+}
+
+impl Copy for ConfigurableHouseholdID {}
+impl Clone for ConfigurableHouseholdID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for ConfigurableHouseholdID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "ConfigurableHouseholdID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for ConfigurableHouseholdID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for ConfigurableHouseholdID {
+    fn eq(&self, other: &ConfigurableHouseholdID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for ConfigurableHouseholdID {}
+
+impl TypedID for ConfigurableHouseholdID {
+    type Target = ConfigurableHousehold;
+
+    fn from_raw(id: RawID) -> Self {
+        ConfigurableHouseholdID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl ConfigurableHouseholdID {
+    pub fn move_into(kind: Name, definition: HouseholdKindDefinition, site: BuildingID, site_position: P2, time: TimeID, world: &mut World) -> Self {
+        let id = ConfigurableHouseholdID::from_raw(world.allocate_instance_id::<ConfigurableHousehold>());
+        let swarm = world.local_broadcast::<ConfigurableHousehold>();
+        world.send(swarm, MSG_ConfigurableHousehold_move_into(id, kind, definition, site, site_position, time));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ConfigurableHousehold_move_into(pub ConfigurableHouseholdID, pub Name, pub HouseholdKindDefinition, pub BuildingID, pub P2, pub TimeID);
+
+impl Into<HouseholdID> for ConfigurableHouseholdID {
+    fn into(self) -> HouseholdID {
+        HouseholdID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<TemporalID> for ConfigurableHouseholdID {
+    fn into(self) -> TemporalID {
+        TemporalID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<SleeperID> for ConfigurableHouseholdID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<EvaluationRequesterID> for ConfigurableHouseholdID {
+    fn into(self) -> EvaluationRequesterID {
+        EvaluationRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<RoughLocationID> for ConfigurableHouseholdID {
+    fn into(self) -> RoughLocationID {
+        RoughLocationID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<TripListenerID> for ConfigurableHouseholdID {
+    fn into(self) -> TripListenerID {
+        TripListenerID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<WeatherListenerID> for ConfigurableHouseholdID {
+    fn into(self) -> WeatherListenerID {
+        WeatherListenerID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    HouseholdID::register_implementor::<ConfigurableHousehold>(system);
+    TemporalID::register_implementor::<ConfigurableHousehold>(system);
+    SleeperID::register_implementor::<ConfigurableHousehold>(system);
+    EvaluationRequesterID::register_implementor::<ConfigurableHousehold>(system);
+    RoughLocationID::register_implementor::<ConfigurableHousehold>(system);
+    TripListenerID::register_implementor::<ConfigurableHousehold>(system);
+    WeatherListenerID::register_implementor::<ConfigurableHousehold>(system);
+    system.add_spawner::<ConfigurableHousehold, _, _>(
+        |&MSG_ConfigurableHousehold_move_into(id, kind, ref definition, site, site_position, time), world| {
+            ConfigurableHousehold::move_into(id, kind, definition, site, site_position, time, world)
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,429 @@
+use kay::{ActorSystem, World, Actor};
+use compact::CHashMap;
+use cb_util::config_manager::{Name, ConfigManager, ConfigManagerID};
+use cb_time::units::{TimeOfDay, TimeOfDayRange, Duration, Ticks, Weekday};
+use cb_time::actors::TimeID;
+use economy::resources::{Resource, ResourceAmount, Entry};
+use economy::resources::Resource::*;
+use economy::market::{Deal, EvaluationRequester, EvaluationRequesterID, EvaluatedSearchResult};
+use descartes::P2;
+use land_use::buildings::{BuildingID, UnitType, BuildingStyle};
+use land_use::buildings::architecture::materials_and_props::BuildingMaterial;
+use environment::weather::WeatherID;
+
+use economy::households::{Household, HouseholdID, HouseholdCore, MemberIdx};
+
+pub mod definition;
+pub use self::definition::{HouseholdKindDefinition, OfferDefinition, NeedDefinition};
+pub mod harvest;
+use self::harvest::{SeasonalYield, CropCycle, HarvestState};
+
+#[derive(Compact, Clone)]
+pub struct ConfigurableHousehold {
+    id: ConfigurableHouseholdID,
+    site: BuildingID,
+    kind: Name,
+    definition: HouseholdKindDefinition,
+    harvest: HarvestState,
+    core: HouseholdCore,
+}
+
+impl ConfigurableHousehold {
+    pub fn move_into(
+        id: ConfigurableHouseholdID,
+        kind: Name,
+        definition: &HouseholdKindDefinition,
+        site: BuildingID,
+        site_position: P2,
+        time: TimeID,
+        world: &mut World,
+    ) -> ConfigurableHousehold {
+        time.wake_up_in(Ticks(0), id.into(), world);
+        WeatherID::global_first(world).get_info(id.into(), world);
+
+        ConfigurableHousehold {
+            id,
+            site,
+            kind,
+            definition: definition.clone(),
+            harvest: HarvestState::default(),
+            core: HouseholdCore::new(
+                id.into(),
+                world,
+                definition.n_members as usize,
+                site.into(),
+                site_position,
+                definition
+                    .offers
+                    .iter()
+                    .map(OfferDefinition::to_offer)
+                    .collect(),
+            ),
+        }
+    }
+
+    pub fn kind(&self) -> Name {
+        self.kind
+    }
+}
+
+impl Household for ConfigurableHousehold {
+    fn core(&self) -> &HouseholdCore {
+        &self.core
+    }
+
+    fn core_mut(&mut self) -> &mut HouseholdCore {
+        &mut self.core
+    }
+
+    fn site(&self) -> RoughLocationID {
+        self.site.into()
+    }
+
+    fn building(&self) -> BuildingID {
+        self.site
+    }
+
+    fn set_building(&mut self, building: BuildingID) {
+        self.site = building;
+    }
+
+    fn field_material(&self) -> Option<BuildingMaterial> {
+        self.definition.field_material
+    }
+
+    fn is_shared(&self, _: Resource) -> bool {
+        true
+    }
+
+    fn supplier_shared(&self, _: Resource) -> bool {
+        true
+    }
+
+    fn importance(&self, resource: Resource, time: TimeOfDay) -> f32 {
+        self.definition.importance(resource, time)
+    }
+
+    fn interesting_resources(&self) -> &[Resource] {
+        &self.definition.interesting_resources
+    }
+
+    fn decay(&mut self, dt: Duration, _: &mut World) {
+        for &Entry(resource, daily_rate) in self.definition.daily_rates.iter() {
+            let rate = if daily_rate > 0.0 {
+                daily_rate * self.harvest.production_factor()
+            } else {
+                daily_rate
+            };
+            let amount = self.core.resources.mut_entry_or(resource, 0.0);
+            *amount += rate * dt.as_days();
+        }
+    }
+
+    fn max_debt(&self) -> Option<ResourceAmount> {
+        Some(self.definition.max_debt)
+    }
+
+    fn on_destroy(&mut self, world: &mut World) {
+        self.site.remove_household(self.id_as(), world);
+    }
+
+    fn household_name(&self) -> String {
+        self.definition.household_name.to_string()
+    }
+
+    fn member_name(&self, member: MemberIdx) -> String {
+        format!("{} {}", self.definition.member_title, member.0 + 1)
+    }
+}
+
+use cb_time::units::DayOfYear;
+use environment::weather::{WeatherListener, WeatherListenerID, WeatherConditions};
+
+impl WeatherListener for ConfigurableHousehold {
+    fn on_weather(&mut self, day: DayOfYear, today: WeatherConditions, _: &mut World) {
+        self.harvest
+            .update(self.definition.seasonal_yield, day, today);
+    }
+}
+
+use economy::households::ResultAspect;
+
+impl EvaluationRequester for ConfigurableHousehold {
+    fn expect_n_results(&mut self, resource: Resource, n: u32, world: &mut World) {
+        self.update_results(resource, &ResultAspect::SetTarget(n), world);
+    }
+
+    fn on_result(&mut self, result: &EvaluatedSearchResult, world: &mut World) {
+        let &EvaluatedSearchResult {
+            resource,
+            ref evaluated_deals,
+            ..
+        } = result;
+        self.update_results(
+            resource,
+            &ResultAspect::AddDeals(evaluated_deals.clone()),
+            world,
+        );
+    }
+}
+
+use cb_time::units::Instant;
+use cb_time::actors::{Temporal, TemporalID, Sleeper, SleeperID};
+
+impl Temporal for ConfigurableHousehold {
+    fn tick(&mut self, dt: f32, current_instant: Instant, world: &mut World) {
+        self.on_tick(dt, current_instant, world);
+    }
+}
+
+impl Sleeper for ConfigurableHousehold {
+    fn wake(&mut self, current_instant: Instant, world: &mut World) {
+        self.update_core(current_instant, world);
+    }
+}
+
+use transport::pathfinding::{RoughLocationID, RoughLocation, RoughLocationResolve};
+
+impl RoughLocation for ConfigurableHousehold {
+    fn resolve(&self) -> RoughLocationResolve {
+        RoughLocationResolve::SameAs(self.site())
+    }
+}
+
+use transport::pathfinding::trip::{TripListener, TripListenerID, TripID, TripResult};
+
+impl TripListener for ConfigurableHousehold {
+    fn trip_created(&mut self, trip: TripID, world: &mut World) {
+        self.on_trip_created(trip, world);
+    }
+
+    fn trip_result(
+        &mut self,
+        trip: TripID,
+        result: TripResult,
+        rough_source: RoughLocationID,
+        rough_destination: RoughLocationID,
+        world: &mut World,
+    ) {
+        self.on_trip_result(trip, result, rough_source, rough_destination, world);
+    }
+}
+
+const BUSINESS_MAX_DEBT: ResourceAmount = 10_000.0;
+
+const DAYTIME_NEED: [f32; 12] = [0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0];
+
+#[allow(clippy::too_many_arguments)]
+fn business(
+    household_name: &str,
+    member_title: &str,
+    unit_type: UnitType,
+    building_style: BuildingStyle,
+    development_share: f32,
+    offers: Vec<OfferDefinition>,
+    needs: Vec<Resource>,
+    daily_rates: Vec<(Resource, f32)>,
+) -> HouseholdKindDefinition {
+    let mut interesting_resources = vec![Money];
+
+    for resource in offers
+        .iter()
+        .flat_map(|offer| offer.deal.delta.iter().map(|&Entry(resource, _)| resource))
+        .chain(needs.iter().cloned())
+        .chain(daily_rates.iter().map(|&(resource, _)| resource))
+    {
+        if !interesting_resources.contains(&resource) {
+            interesting_resources.push(resource);
+        }
+    }
+
+    HouseholdKindDefinition {
+        household_name: household_name.to_owned().into(),
+        member_title: member_title.to_owned().into(),
+        n_members: 1,
+        unit_type,
+        building_style,
+        field_material: None,
+        development_share,
+        offers: offers.into(),
+        needs: needs
+            .into_iter()
+            .map(|resource| NeedDefinition::new(resource, DAYTIME_NEED))
+            .collect(),
+        daily_rates: daily_rates.into_iter().collect(),
+        seasonal_yield: SeasonalYield::Constant,
+        max_debt: BUSINESS_MAX_DEBT,
+        interesting_resources: interesting_resources.into(),
+    }
+}
+
+fn selling(resource: Resource, amount: f32, price: f32, max_users: u32) -> OfferDefinition {
+    OfferDefinition::new(
+        TimeOfDayRange::new(7, 0, 20, 0),
+        Deal::new(
+            vec![(resource, amount), (Money, -amount * price)],
+            Duration::from_minutes(10),
+        ),
+        max_users,
+    )
+}
+
+fn job(start_h: usize, hours: usize, wage: f32, max_users: u32) -> OfferDefinition {
+    OfferDefinition::new(
+        TimeOfDayRange::new(start_h, 0, start_h + 10, 0),
+        Deal::new(Some((Money, wage)), Duration::from_hours(hours)),
+        max_users,
+    )
+}
+
+pub fn default_kinds() -> CHashMap<Name, HouseholdKindDefinition> {
+    vec![
+        (
+            "GroceryShop",
+            business(
+                "Grocery Shop",
+                "Retail Worker",
+                UnitType::Retail,
+                BuildingStyle::GroceryShop,
+                0.2,
+                vec![
+                    OfferDefinition::new(
+                        TimeOfDayRange::new(7, 0, 20, 0),
+                        Deal::new(
+                            vec![(Groceries, 30.0), (Money, -30.0 * 2.7)],
+                            Duration::from_minutes(30),
+                        ),
+                        16,
+                    )
+                    .with_service_capacity(4)
+                    .closed_on(Weekday::Sunday),
+                    OfferDefinition::new(
+                        TimeOfDayRange::new(7, 0, 15, 0),
+                        Deal::new(Some((Money, 50.0)), Duration::from_hours(5)),
+                        5,
+                    )
+                    .closed_on(Weekday::Sunday),
+                ],
+                vec![BakedGoods, Produce, Grain, Flour, Meat, DairyGoods],
+                vec![
+                    (Groceries, 500.0),
+                    (Meat, -500.0 * 0.2),
+                    (DairyGoods, -500.0 * 0.1),
+                    (Produce, -500.0 * 0.1),
+                    (Grain, -500.0 * 0.05),
+                    (Flour, -500.0 * 0.01),
+                    (BakedGoods, -500.0 * 0.3),
+                ],
+            ),
+        ),
+        (
+            "CowFarm",
+            business(
+                "Cow Farm",
+                "Farmer",
+                UnitType::Agriculture,
+                BuildingStyle::Field,
+                0.3,
+                vec![
+                    selling(Meat, 5.0, 3.0, 4),
+                    selling(DairyGoods, 10.0, 1.31, 4),
+                    job(5, 4, 40.0, 2),
+                ],
+                vec![Grain],
+                vec![
+                    (Meat, 20.0),
+                    (DairyGoods, 40.0),
+                    (Grain, -(20.0 * 0.2 + 40.0 * 0.1)),
+                ],
+            )
+            .with_seasonal_yield(SeasonalYield::Pasture)
+            .with_field_material(BuildingMaterial::FieldMeadow),
+        ),
+        (
+            "VegetableFarm",
+            business(
+                "Vegetable Farm",
+                "Farmer",
+                UnitType::Agriculture,
+                BuildingStyle::Field,
+                0.26,
+                vec![selling(Produce, 20.0, 1.3, 4), job(5, 4, 40.0, 2)],
+                vec![],
+                vec![(Produce, 80.0)],
+            )
+            .with_seasonal_yield(SeasonalYield::Crop(CropCycle {
+                planting: DayOfYear(14),
+                harvest_start: DayOfYear(70),
+                harvest_end: DayOfYear(250),
+            }))
+            .with_field_material(BuildingMaterial::FieldPlant),
+        ),
+        (
+            "GrainFarm",
+            business(
+                "Grain Farm",
+                "Farmer",
+                UnitType::Agriculture,
+                BuildingStyle::Field,
+                0.2,
+                vec![selling(Grain, 200.0, 0.13, 4), job(5, 4, 40.0, 2)],
+                vec![],
+                vec![(Grain, 800.0)],
+            )
+            .with_seasonal_yield(SeasonalYield::Crop(CropCycle {
+                planting: DayOfYear(14),
+                harvest_start: DayOfYear(140),
+                harvest_end: DayOfYear(196),
+            }))
+            .with_field_material(BuildingMaterial::FieldWheat),
+        ),
+        (
+            "Mill",
+            business(
+                "Mill",
+                "Miller",
+                UnitType::Mill,
+                BuildingStyle::Mill,
+                0.2,
+                vec![selling(Flour, 200.0, 0.3, 4), job(5, 4, 40.0, 3)],
+                vec![Grain],
+                vec![(Flour, 800.0), (Grain, -800.0)],
+            ),
+        ),
+        (
+            "Bakery",
+            business(
+                "Bakery",
+                "Baker",
+                UnitType::Bakery,
+                BuildingStyle::Bakery,
+                0.2,
+                vec![selling(BakedGoods, 100.0, 2.5, 30), job(5, 5, 50.0, 3)],
+                vec![Flour, DairyGoods],
+                vec![
+                    (BakedGoods, 300.0),
+                    (Flour, 300.0 * 0.1),
+                    (DairyGoods, 300.0 * 0.05),
+                ],
+            ),
+        ),
+    ]
+    .into_iter()
+    .map(|(name, definition)| (Name::from(name).unwrap(), definition))
+    .collect()
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<ConfigurableHousehold>();
+    system.register::<ConfigManager<HouseholdKindDefinition>>();
+    ::cb_util::config_manager::auto_setup::<HouseholdKindDefinition>(system);
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World) {
+    ConfigManagerID::<HouseholdKindDefinition>::spawn(default_kinds(), world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
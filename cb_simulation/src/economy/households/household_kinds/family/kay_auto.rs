@@ -55,16 +55,16 @@ impl TypedID for FamilyID {
 }
 
 impl FamilyID {
-    pub fn move_into(n_members: u32, home: BuildingID, time: TimeID, world: &mut World) -> Self {
+    pub fn move_into(n_members: u32, home: BuildingID, home_position: P2, time: TimeID, world: &mut World) -> Self {
         let id = FamilyID::from_raw(world.allocate_instance_id::<Family>());
         let swarm = world.local_broadcast::<Family>();
-        world.send(swarm, MSG_Family_move_into(id, n_members, home, time));
+        world.send(swarm, MSG_Family_move_into(id, n_members, home, home_position, time));
         id
     }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_Family_move_into(pub FamilyID, pub u32, pub BuildingID, pub TimeID);
+struct MSG_Family_move_into(pub FamilyID, pub u32, pub BuildingID, pub P2, pub TimeID);
 
 impl Into<SleeperID> for FamilyID {
     fn into(self) -> SleeperID {
@@ -113,8 +113,8 @@ pub fn auto_setup(system: &mut ActorSystem) {
     TemporalID::register_implementor::<Family>(system);
     RoughLocationID::register_implementor::<Family>(system);
     system.add_spawner::<Family, _, _>(
-        |&MSG_Family_move_into(id, n_members, home, time), world| {
-            Family::move_into(id, n_members, home, time, world)
+        |&MSG_Family_move_into(id, n_members, home, home_position, time), world| {
+            Family::move_into(id, n_members, home, home_position, time, world)
         }, false
     );
 }
\ No newline at end of file
@@ -6,6 +6,7 @@ use cb_time::units::{TimeOfDay, TimeOfDayRange, Instant, Duration, Ticks};
 use economy::resources::Resource;
 use economy::resources::Resource::*;
 use economy::market::{Deal, EvaluationRequester, EvaluationRequesterID, EvaluatedSearchResult};
+use descartes::P2;
 use land_use::buildings::BuildingID;
 use transport::pathfinding::trip::{TripResult, TripListenerID};
 use transport::pathfinding::RoughLocationID;
@@ -28,6 +29,7 @@ impl Family {
         id: FamilyID,
         n_members: u32,
         home: BuildingID,
+        home_position: P2,
         time: TimeID,
         world: &mut World,
     ) -> Family {
@@ -38,6 +40,7 @@ impl Family {
             world,
             n_members as usize,
             home.into(),
+            home_position,
             vec![Offer::new(
                 MemberIdx(0),
                 TimeOfDayRange::new(16, 0, 11, 0),
@@ -121,7 +124,15 @@ impl Household for Family {
         self.home.into()
     }
 
-    fn is_shared(resource: Resource) -> bool {
+    fn building(&self) -> BuildingID {
+        self.home
+    }
+
+    fn set_building(&mut self, building: BuildingID) {
+        self.home = building;
+    }
+
+    fn is_shared(&self, resource: Resource) -> bool {
         match resource {
             Wakefulness | Satiety => false,
             Money | Groceries => true,
@@ -129,7 +140,7 @@ impl Household for Family {
         }
     }
 
-    fn supplier_shared(resource: Resource) -> bool {
+    fn supplier_shared(&self, resource: Resource) -> bool {
         match resource {
             Money => false,
             Wakefulness | Satiety | Groceries => true,
@@ -137,7 +148,7 @@ impl Household for Family {
         }
     }
 
-    fn importance(resource: Resource, time: TimeOfDay) -> f32 {
+    fn importance(&self, resource: Resource, time: TimeOfDay) -> f32 {
         let hour = time.hours_minutes().0;
 
         let bihourly_importance = match resource {
@@ -153,7 +164,7 @@ impl Household for Family {
             .unwrap_or(0.0)
     }
 
-    fn interesting_resources() -> &'static [Resource] {
+    fn interesting_resources(&self) -> &[Resource] {
         &[
             Wakefulness,
             Satiety, //Entertainment,
@@ -166,7 +177,7 @@ impl Household for Family {
         ]
     }
 
-    fn decay(&mut self, dt: Duration, _: &mut World) {
+    fn decay(&mut self, dt: Duration, _world: &mut World) {
         for (i, member_resources) in self.core.member_resources.iter_mut().enumerate() {
             {
                 let individuality = seed((self.id, i)).gen_range(0.8, 1.2);
@@ -206,6 +217,10 @@ impl Household for Family {
         // }
     }
 
+    fn reports_family_satisfaction(&self) -> bool {
+        true
+    }
+
     fn on_destroy(&mut self, world: &mut World) {
         self.home.remove_household(self.id_as(), world);
     }
@@ -220,8 +235,8 @@ impl Household for Family {
 }
 
 impl Temporal for Family {
-    fn tick(&mut self, _dt: f32, current_instant: Instant, world: &mut World) {
-        self.on_tick(current_instant, world);
+    fn tick(&mut self, dt: f32, current_instant: Instant, world: &mut World) {
+        self.on_tick(dt, current_instant, world);
     }
 }
 
@@ -1,8 +1,3 @@
 pub mod family;
-pub mod grocery_shop;
-pub mod grain_farm;
-pub mod cow_farm;
-pub mod vegetable_farm;
-pub mod mill;
-pub mod bakery;
+pub mod configurable;
 pub mod neighboring_town_trade;
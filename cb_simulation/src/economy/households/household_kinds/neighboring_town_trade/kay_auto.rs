@@ -55,16 +55,16 @@ impl TypedID for NeighboringTownTradeID {
 }
 
 impl NeighboringTownTradeID {
-    pub fn move_into(town: BuildingID, time: TimeID, world: &mut World) -> Self {
+    pub fn move_into(town: BuildingID, town_position: P2, time: TimeID, world: &mut World) -> Self {
         let id = NeighboringTownTradeID::from_raw(world.allocate_instance_id::<NeighboringTownTrade>());
         let swarm = world.local_broadcast::<NeighboringTownTrade>();
-        world.send(swarm, MSG_NeighboringTownTrade_move_into(id, town, time));
+        world.send(swarm, MSG_NeighboringTownTrade_move_into(id, town, town_position, time));
         id
     }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_NeighboringTownTrade_move_into(pub NeighboringTownTradeID, pub BuildingID, pub TimeID);
+struct MSG_NeighboringTownTrade_move_into(pub NeighboringTownTradeID, pub BuildingID, pub P2, pub TimeID);
 
 impl Into<HouseholdID> for NeighboringTownTradeID {
     fn into(self) -> HouseholdID {
@@ -113,8 +113,8 @@ pub fn auto_setup(system: &mut ActorSystem) {
     TemporalID::register_implementor::<NeighboringTownTrade>(system);
     RoughLocationID::register_implementor::<NeighboringTownTrade>(system);
     system.add_spawner::<NeighboringTownTrade, _, _>(
-        |&MSG_NeighboringTownTrade_move_into(id, town, time), world| {
-            NeighboringTownTrade::move_into(id, town, time, world)
+        |&MSG_NeighboringTownTrade_move_into(id, town, town_position, time), world| {
+            NeighboringTownTrade::move_into(id, town, town_position, time, world)
         }, false
     );
 }
\ No newline at end of file
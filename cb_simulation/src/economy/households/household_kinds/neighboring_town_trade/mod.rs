@@ -1,9 +1,10 @@
 use kay::{ActorSystem, World, Actor};
 use cb_time::actors::{Temporal, TemporalID, TimeID};
 use cb_time::units::{TimeOfDay, TimeOfDayRange, Duration, Instant, Ticks};
-use economy::resources::Resource;
+use economy::resources::{Resource, ResourceAmount};
 use economy::resources::Resource::*;
 use economy::market::{Deal, EvaluationRequester, EvaluationRequesterID, EvaluatedSearchResult};
+use descartes::P2;
 use land_use::buildings::BuildingID;
 use transport::pathfinding::RoughLocationID;
 use transport::pathfinding::trip::{TripListener, TripListenerID, TripID, TripResult};
@@ -21,6 +22,7 @@ impl NeighboringTownTrade {
     pub fn move_into(
         id: NeighboringTownTradeID,
         town: BuildingID,
+        town_position: P2,
         time: TimeID,
         world: &mut World,
     ) -> Self {
@@ -185,7 +187,14 @@ impl NeighboringTownTrade {
         NeighboringTownTrade {
             id,
             town,
-            core: HouseholdCore::new(id.into(), world, 10, town.into(), offers.into()),
+            core: HouseholdCore::new(
+                id.into(),
+                world,
+                10,
+                town.into(),
+                town_position,
+                offers.into(),
+            ),
         }
     }
 }
@@ -203,19 +212,27 @@ impl Household for NeighboringTownTrade {
         self.town.into()
     }
 
-    fn is_shared(_: Resource) -> bool {
+    fn building(&self) -> BuildingID {
+        self.town
+    }
+
+    fn set_building(&mut self, building: BuildingID) {
+        self.town = building;
+    }
+
+    fn is_shared(&self, _: Resource) -> bool {
         true
     }
 
-    fn supplier_shared(_: Resource) -> bool {
+    fn supplier_shared(&self, _: Resource) -> bool {
         true
     }
 
-    fn importance(_: Resource, _: TimeOfDay) -> f32 {
+    fn importance(&self, _: Resource, _: TimeOfDay) -> f32 {
         1.0
     }
 
-    fn interesting_resources() -> &'static [Resource] {
+    fn interesting_resources(&self) -> &[Resource] {
         &[
             //Entertainment,
             //Services,
@@ -231,6 +248,14 @@ impl Household for NeighboringTownTrade {
 
     fn decay(&mut self, _dt: Duration, _: &mut World) {}
 
+    fn max_debt(&self) -> Option<ResourceAmount> {
+        None
+    }
+
+    fn pays_taxes(&self) -> bool {
+        false
+    }
+
     fn household_name(&self) -> String {
         "Neighboring Town".to_owned()
     }
@@ -291,8 +316,8 @@ impl TripListener for NeighboringTownTrade {
 }
 
 impl Temporal for NeighboringTownTrade {
-    fn tick(&mut self, _dt: f32, current_instant: Instant, world: &mut World) {
-        self.on_tick(current_instant, world);
+    fn tick(&mut self, dt: f32, current_instant: Instant, world: &mut World) {
+        self.on_tick(dt, current_instant, world);
     }
 }
 
@@ -86,6 +86,10 @@ impl HouseholdID {
         world.send(self.as_raw(), MSG_Household_on_destroy());
     }
     
+    pub fn relocate(self, new_building: BuildingID, unit: UnitIdx, new_site_position: P2, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_relocate(new_building, unit, new_site_position));
+    }
+    
     pub fn update_core(self, current_instant: Instant, world: &mut World) {
         world.send(self.as_raw(), MSG_Household_update_core(current_instant));
     }
@@ -122,8 +126,12 @@ impl HouseholdID {
         world.send(self.as_raw(), MSG_Household_stop_task(member, location));
     }
     
-    pub fn on_tick(self, current_instant: Instant, world: &mut World) {
-        world.send(self.as_raw(), MSG_Household_on_tick(current_instant));
+    pub fn on_tick(self, dt: f32, current_instant: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_on_tick(dt, current_instant));
+    }
+    
+    pub fn update_finances(self, dt: Duration, current_instant: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_update_finances(dt, current_instant));
     }
     
     pub fn evaluate(self, offer_idx: OfferIdx, instant: Instant, location: RoughLocationID, requester: EvaluationRequesterID, world: &mut World) {
@@ -134,6 +142,10 @@ impl HouseholdID {
         world.send(self.as_raw(), MSG_Household_request_receive_deal(offer_idx, requester, requester_member));
     }
     
+    pub fn pay_tax(self, amount: ResourceAmount, item: BudgetItem, budget: CityBudgetID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_pay_tax(amount, item, budget));
+    }
+    
     pub fn request_receive_undo_deal(self, offer_idx: OfferIdx, requester: HouseholdID, requester_member: MemberIdx, world: &mut World) {
         world.send(self.as_raw(), MSG_Household_request_receive_undo_deal(offer_idx, requester, requester_member));
     }
@@ -146,12 +158,20 @@ impl HouseholdID {
         world.send(self.as_raw(), MSG_Household_stopped_using(offer_idx, user, using_member));
     }
     
-    pub fn started_actively_using(self, offer_idx: OfferIdx, user: HouseholdID, using_member: MemberIdx, world: &mut World) {
-        world.send(self.as_raw(), MSG_Household_started_actively_using(offer_idx, user, using_member));
+    pub fn started_actively_using(self, offer_idx: OfferIdx, user: HouseholdID, using_member: MemberIdx, arrival: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_started_actively_using(offer_idx, user, using_member, arrival));
+    }
+    
+    pub fn delay_task(self, member: MemberIdx, delay: Duration, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_delay_task(member, delay));
+    }
+    
+    pub fn hasten_task(self, member: MemberIdx, by: Duration, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_hasten_task(member, by));
     }
     
-    pub fn stopped_actively_using(self, offer_idx: OfferIdx, user: HouseholdID, using_member: MemberIdx, world: &mut World) {
-        world.send(self.as_raw(), MSG_Household_stopped_actively_using(offer_idx, user, using_member));
+    pub fn stopped_actively_using(self, offer_idx: OfferIdx, user: HouseholdID, using_member: MemberIdx, left_at: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_stopped_actively_using(offer_idx, user, using_member, left_at));
     }
     
     pub fn withdrawal_confirmed(self, offer_idx: OfferIdx, world: &mut World) {
@@ -173,6 +193,7 @@ impl HouseholdID {
         system.register_trait_message::<MSG_Household_stop_using>();
         system.register_trait_message::<MSG_Household_destroy>();
         system.register_trait_message::<MSG_Household_on_destroy>();
+        system.register_trait_message::<MSG_Household_relocate>();
         system.register_trait_message::<MSG_Household_update_core>();
         system.register_trait_message::<MSG_Household_find_new_task_for>();
         system.register_trait_message::<MSG_Household_update_results>();
@@ -183,12 +204,16 @@ impl HouseholdID {
         system.register_trait_message::<MSG_Household_start_task>();
         system.register_trait_message::<MSG_Household_stop_task>();
         system.register_trait_message::<MSG_Household_on_tick>();
+        system.register_trait_message::<MSG_Household_update_finances>();
         system.register_trait_message::<MSG_Household_evaluate>();
         system.register_trait_message::<MSG_Household_request_receive_deal>();
+        system.register_trait_message::<MSG_Household_pay_tax>();
         system.register_trait_message::<MSG_Household_request_receive_undo_deal>();
         system.register_trait_message::<MSG_Household_started_using>();
         system.register_trait_message::<MSG_Household_stopped_using>();
         system.register_trait_message::<MSG_Household_started_actively_using>();
+        system.register_trait_message::<MSG_Household_delay_task>();
+        system.register_trait_message::<MSG_Household_hasten_task>();
         system.register_trait_message::<MSG_Household_stopped_actively_using>();
         system.register_trait_message::<MSG_Household_withdrawal_confirmed>();
         system.register_trait_message::<MSG_Household_get_ui_info>();
@@ -250,6 +275,12 @@ impl HouseholdID {
             }, false
         );
         
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_relocate(new_building, unit, new_site_position), instance, world| {
+                instance.relocate(new_building, unit, new_site_position, world); Fate::Live
+            }, false
+        );
+        
         system.add_handler::<Act, _, _>(
             |&MSG_Household_update_core(current_instant), instance, world| {
                 instance.update_core(current_instant, world); Fate::Live
@@ -305,8 +336,14 @@ impl HouseholdID {
         );
         
         system.add_handler::<Act, _, _>(
-            |&MSG_Household_on_tick(current_instant), instance, world| {
-                instance.on_tick(current_instant, world); Fate::Live
+            |&MSG_Household_on_tick(dt, current_instant), instance, world| {
+                instance.on_tick(dt, current_instant, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_update_finances(dt, current_instant), instance, world| {
+                instance.update_finances(dt, current_instant, world); Fate::Live
             }, false
         );
         
@@ -322,6 +359,12 @@ impl HouseholdID {
             }, false
         );
         
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_pay_tax(amount, item, budget), instance, world| {
+                instance.pay_tax(amount, item, budget, world); Fate::Live
+            }, false
+        );
+        
         system.add_handler::<Act, _, _>(
             |&MSG_Household_request_receive_undo_deal(offer_idx, requester, requester_member), instance, world| {
                 instance.request_receive_undo_deal(offer_idx, requester, requester_member, world); Fate::Live
@@ -341,14 +384,26 @@ impl HouseholdID {
         );
         
         system.add_handler::<Act, _, _>(
-            |&MSG_Household_started_actively_using(offer_idx, user, using_member), instance, world| {
-                instance.started_actively_using(offer_idx, user, using_member, world); Fate::Live
+            |&MSG_Household_started_actively_using(offer_idx, user, using_member, arrival), instance, world| {
+                instance.started_actively_using(offer_idx, user, using_member, arrival, world); Fate::Live
             }, false
         );
         
         system.add_handler::<Act, _, _>(
-            |&MSG_Household_stopped_actively_using(offer_idx, user, using_member), instance, world| {
-                instance.stopped_actively_using(offer_idx, user, using_member, world); Fate::Live
+            |&MSG_Household_delay_task(member, delay), instance, world| {
+                instance.delay_task(member, delay, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_hasten_task(member, by), instance, world| {
+                instance.hasten_task(member, by, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_stopped_actively_using(offer_idx, user, using_member, left_at), instance, world| {
+                instance.stopped_actively_using(offer_idx, user, using_member, left_at, world); Fate::Live
             }, false
         );
         
@@ -385,6 +440,8 @@ struct MSG_Household_destroy();
 #[derive(Copy, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Household_on_destroy();
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_relocate(pub BuildingID, pub UnitIdx, pub P2);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Household_update_core(pub Instant);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Household_find_new_task_for(pub MemberIdx, pub Instant, pub RoughLocationID);
@@ -403,21 +460,29 @@ struct MSG_Household_start_task(pub MemberIdx, pub Instant, pub RoughLocationID)
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Household_stop_task(pub MemberIdx, pub Option < RoughLocationID >);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_Household_on_tick(pub Instant);
+struct MSG_Household_on_tick(pub f32, pub Instant);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_update_finances(pub Duration, pub Instant);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Household_evaluate(pub OfferIdx, pub Instant, pub RoughLocationID, pub EvaluationRequesterID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Household_request_receive_deal(pub OfferIdx, pub HouseholdID, pub MemberIdx);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_pay_tax(pub ResourceAmount, pub BudgetItem, pub CityBudgetID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Household_request_receive_undo_deal(pub OfferIdx, pub HouseholdID, pub MemberIdx);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Household_started_using(pub OfferIdx, pub HouseholdID, pub Option < MemberIdx >);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Household_stopped_using(pub OfferIdx, pub HouseholdID, pub Option < MemberIdx >);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_Household_started_actively_using(pub OfferIdx, pub HouseholdID, pub MemberIdx);
+struct MSG_Household_started_actively_using(pub OfferIdx, pub HouseholdID, pub MemberIdx, pub Instant);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_delay_task(pub MemberIdx, pub Duration);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_hasten_task(pub MemberIdx, pub Duration);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_Household_stopped_actively_using(pub OfferIdx, pub HouseholdID, pub MemberIdx);
+struct MSG_Household_stopped_actively_using(pub OfferIdx, pub HouseholdID, pub MemberIdx, pub Instant);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Household_withdrawal_confirmed(pub OfferIdx);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
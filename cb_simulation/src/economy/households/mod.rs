@@ -1,7 +1,7 @@
 use kay::{ActorSystem, World, Actor, TypedID, Fate};
 use compact::{CVec, CDict, COption};
 use cb_time::actors::{TimeID, Sleeper, Temporal};
-use cb_time::units::{Duration, TimeOfDay, Instant, Ticks, TICKS_PER_SIM_SECOND};
+use cb_time::units::{Duration, TimeOfDay, Instant, Ticks, Weekday, TICKS_PER_SIM_SECOND};
 use cb_util::async_counter::AsyncCounter;
 use cb_util::random::{seed, Rng};
 use ordered_float::OrderedFloat;
@@ -11,11 +11,15 @@ const LOG_T: &str = "Households";
 pub mod tasks;
 pub mod offers;
 pub mod ui;
+pub mod finances;
+pub mod satisfaction;
+pub mod agenda;
+pub mod decision_trace;
 
 pub mod household_kinds;
 use self::household_kinds::*;
 
-#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
 pub struct MemberIdx(u32);
 
 impl MemberIdx {
@@ -28,17 +32,28 @@ impl MemberIdx {
     }
 }
 
-use super::market::{MarketID, Deal, EvaluatedDeal, EvaluationRequester, EvaluationRequesterID,
+use super::market::{self, Deal, EvaluatedDeal, EvaluationRequester, EvaluationRequesterID,
 TripCostEstimatorID, EvaluatedSearchResult};
 use super::resources::{Resource, ResourceAmount, ResourceMap, Entry, Inventory};
 use transport::pathfinding::{RoughLocationID, RoughLocation};
+use descartes::P2;
 use transport::pathfinding::trip::{TripListener, TripID, TripResult, TripFate};
 use self::tasks::{Task, TaskState, TaskEndSchedulerID};
 pub use self::offers::{Offer, OfferIdx, OfferID};
+use self::finances::{Finances, FinancialFailure};
+use self::satisfaction::Satisfaction;
+use self::agenda::Agenda;
+use self::decision_trace::{DecisionCandidate, DecisionTrace, DecisionHistory};
+use land_use::buildings::{BuildingID, UnitIdx};
+use land_use::buildings::architecture::materials_and_props::BuildingMaterial;
+use super::immigration_and_development::{ImmigrationManagerID, DevelopmentManagerID, SearchTally};
+use super::city_budget::{CityBudgetID, BudgetItem};
 
 const N_TOP_PROBLEMS: usize = 5;
 const DECISION_PAUSE: Ticks = Ticks(200);
 const UPDATE_EVERY_N_SECS: u32 = 4;
+const REPORT_SATISFACTION_EVERY_N_SECS: u32 = 60;
+const DEFAULT_MAX_DEBT: ResourceAmount = 1000.0;
 
 // TODO: make kay_codegen figure this out on it's own
 impl Into<RoughLocationID> for HouseholdID {
@@ -53,29 +68,73 @@ pub trait Household:
     fn core(&self) -> &HouseholdCore;
     fn core_mut(&mut self) -> &mut HouseholdCore;
     fn site(&self) -> RoughLocationID;
+    fn building(&self) -> BuildingID;
+    fn set_building(&mut self, building: BuildingID);
+    /// How the fields of the household's building should look, if it has any
+    fn field_material(&self) -> Option<BuildingMaterial> {
+        None
+    }
 
-    fn is_shared(resource: Resource) -> bool;
-    fn supplier_shared(resource: Resource) -> bool;
-    fn importance(resource: Resource, time: TimeOfDay) -> f32;
-    fn graveness(resource: Resource, amount: ResourceAmount, time: TimeOfDay) -> f32 {
-        -amount * Self::importance(resource, time)
+    fn is_shared(&self, resource: Resource) -> bool;
+    fn supplier_shared(&self, resource: Resource) -> bool;
+    fn importance(&self, resource: Resource, time: TimeOfDay) -> f32;
+    fn graveness(&self, resource: Resource, amount: ResourceAmount, time: TimeOfDay) -> f32 {
+        -amount * self.importance(resource, time)
     }
-    fn interesting_resources() -> &'static [Resource];
+    fn interesting_resources(&self) -> &[Resource];
     fn decay(&mut self, dt: Duration, world: &mut World);
 
+    /// How far into debt this household may go before risking bankruptcy,
+    /// `None` for households that can never fail or emigrate
+    fn max_debt(&self) -> Option<ResourceAmount> {
+        Some(DEFAULT_MAX_DEBT)
+    }
+
+    /// Whether this household is subject to city taxes
+    fn pays_taxes(&self) -> bool {
+        true
+    }
+
+    /// Whether this household's satisfaction makes the city more or less
+    /// attractive to immigrating families
+    fn reports_family_satisfaction(&self) -> bool {
+        false
+    }
+
+    fn plan_agenda(&self, _member: MemberIdx, instant: Instant) -> Agenda {
+        Agenda::plan(instant, self.interesting_resources(), |resource, time| {
+            self.importance(resource, time)
+        })
+    }
+
     fn household_name(&self) -> String;
     fn member_name(&self, member: MemberIdx) -> String;
 
+    fn shared_resources_of(&self, deal: &Deal) -> Vec<Resource> {
+        deal.delta
+            .iter()
+            .filter_map(|&Entry(resource, _)| {
+                if self.is_shared(resource) {
+                    Some(resource)
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
     fn receive_deal(&mut self, deal: &Deal, member: MemberIdx, _: &mut World) {
+        let shared_resources = self.shared_resources_of(deal);
         let core = self.core_mut();
         deal.delta.give_to_shared_private(
             &mut core.resources,
             &mut core.member_resources[member.as_idx()],
-            Self::is_shared,
+            |resource| shared_resources.contains(&resource),
         );
     }
 
     fn provide_deal(&mut self, deal: &Deal, member: MemberIdx, _: &mut World) {
+        let shared_resources = self.shared_resources_of(deal);
         let core = self.core_mut();
         let provide_wakefulness =
             deal.delta.len() == 1 && deal.delta.get(Resource::Wakefulness).is_some();
@@ -83,7 +142,7 @@ pub trait Household:
             deal.delta.take_from_shared_private(
                 &mut core.resources,
                 &mut core.member_resources[member.as_idx()],
-                Self::is_shared,
+                |resource| shared_resources.contains(&resource),
             );
         }
     }
@@ -177,6 +236,7 @@ pub trait Household:
             .collect::<Vec<_>>();
 
         for member_to_reset in members_to_reset {
+            self.core_mut().member_agendas[member_to_reset.as_idx()].invalidate();
             self.reset_member_task(member_to_reset, world);
         }
     }
@@ -201,8 +261,10 @@ pub trait Household:
             }
         }
 
+        let partition = market::partition_for(self.core().site_position);
+
         for (idx, offer) in self.core().provided_offers.iter().enumerate() {
-            MarketID::local_first(world).withdraw(
+            partition.withdraw(
                 offer.deal.main_given(),
                 OfferID {
                     household: self.id_as(),
@@ -212,10 +274,79 @@ pub trait Household:
             )
         }
 
+        // it might have been waiting for a new home
+        DevelopmentManagerID::global_first(world).cancel_relocation(self.id_as(), world);
+
         self.on_destroy(world);
     }
     fn on_destroy(&mut self, world: &mut World);
 
+    /// Moves into a unit of another building, for example because the current one is
+    /// about to be demolished. Provided offers are moved to the new site once the market
+    /// confirmed their withdrawal at the old one.
+    fn relocate(
+        &mut self,
+        new_building: BuildingID,
+        unit: UnitIdx,
+        new_site_position: P2,
+        world: &mut World,
+    ) {
+        if self.core().being_destroyed {
+            new_building.release_unit(unit, world);
+            return;
+        }
+
+        let old_building = self.building();
+        let old_site = self.site();
+        let old_partition = market::partition_for(self.core().site_position);
+
+        let offers_to_move = self
+            .core()
+            .provided_offers
+            .iter()
+            .enumerate()
+            .map(|(idx, offer)| (OfferIdx(idx as u16), offer.deal.main_given()))
+            .collect::<Vec<_>>();
+
+        for (offer_idx, resource) in offers_to_move {
+            old_partition.withdraw(
+                resource,
+                OfferID {
+                    household: self.id_as(),
+                    idx: offer_idx,
+                },
+                world,
+            );
+            self.core_mut().relocating_offers.push(offer_idx);
+        }
+
+        self.set_building(new_building);
+        self.core_mut().site_position = new_site_position;
+        new_building.add_household(self.id_as(), unit, self.field_material(), world);
+        old_building.remove_household(self.id_as(), world);
+
+        let members_at_old_site = self
+            .core()
+            .member_tasks
+            .iter()
+            .enumerate()
+            .filter_map(|(i, task)| match task.state {
+                TaskState::GettingReadyAt(location)
+                | TaskState::StartedAt(_, location)
+                | TaskState::IdleAt(location)
+                    if location == old_site =>
+                {
+                    Some(MemberIdx::new(i))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        for member in members_at_old_site {
+            self.reset_member_task(member, world);
+        }
+    }
+
     fn update_core(&mut self, current_instant: Instant, world: &mut World) {
         if let DecisionState::None = self.core().decision_state {
             let idle_members_idx_loc = self
@@ -241,6 +372,19 @@ pub trait Household:
         };
     }
 
+    fn worst_graveness(&self, time: TimeOfDay) -> f32 {
+        let core = self.core();
+        core.resources
+            .iter()
+            .chain(
+                core.member_resources
+                    .iter()
+                    .flat_map(|resources| resources.iter()),
+            )
+            .map(|&Entry(resource, amount)| self.graveness(resource, amount, time))
+            .fold(0.0, f32::max)
+    }
+
     fn top_problems(&self, member: MemberIdx, time: TimeOfDay) -> Vec<(Resource, f32)> {
         let mut resource_graveness = self
             .core()
@@ -248,7 +392,7 @@ pub trait Household:
             .iter()
             .chain(self.core().member_resources[member.as_idx()].iter())
             .filter_map(|&Entry(resource, amount)| {
-                let graveness = Self::graveness(resource, amount, time);
+                let graveness = self.graveness(resource, amount, time);
                 if graveness > 0.1 {
                     Some((resource, graveness))
                 } else {
@@ -271,8 +415,37 @@ pub trait Household:
     ) {
         debug(LOG_T, "Top N Problems", self.id(), world);
 
+        if self.core().member_agendas[member.as_idx()].needs_planning(instant) {
+            let agenda = self.plan_agenda(member, instant);
+            debug(
+                LOG_T,
+                format!(
+                    "Member #{} planned agenda {:?}",
+                    member.as_idx(),
+                    agenda.items
+                ),
+                self.id(),
+                world,
+            );
+            self.core_mut().member_agendas[member.as_idx()] = agenda;
+        }
+
         let time = TimeOfDay::from(instant);
-        let top_problems = self.top_problems(member, time);
+        let all_problems = self.top_problems(member, time);
+
+        let away_from_home = location != self.site();
+
+        let top_problems = match self.core_mut().member_agendas[member.as_idx()].take_due(
+            instant,
+            away_from_home,
+            &all_problems,
+        ) {
+            Some(agenda_resource) => all_problems
+                .into_iter()
+                .filter(|&(resource, _)| resource == agenda_resource)
+                .collect(),
+            None => all_problems,
+        };
 
         if top_problems.is_empty() {
             TimeID::local_first(world).wake_up_in(DECISION_PAUSE, self.id_as(), world);
@@ -280,16 +453,27 @@ pub trait Household:
             let mut decision_entries = CDict::<Resource, DecisionResourceEntry>::new();
             let id_as_eval_requester = self.id_as();
             let log_as = self.id();
+            let supplier_shared = top_problems
+                .iter()
+                .map(|&(resource, _)| self.supplier_shared(resource))
+                .collect::<Vec<_>>();
             let core = self.core_mut();
+            let known_position = if away_from_home {
+                None
+            } else {
+                Some(core.site_position)
+            };
 
-            for &(resource, graveness) in &top_problems {
+            for (&(resource, graveness), &is_supplier_shared) in
+                top_problems.iter().zip(supplier_shared.iter())
+            {
                 debug(
                     LOG_T,
                     format!("Member #{}: {} = {}", member.as_idx(), resource, graveness),
                     log_as,
                     world,
                 );
-                let maybe_offer = if Self::supplier_shared(resource) {
+                let maybe_offer = if is_supplier_shared {
                     core.used_offers.get(resource)
                 } else {
                     core.member_used_offers[member.as_idx()].get(resource)
@@ -318,9 +502,10 @@ pub trait Household:
                         log_as,
                         world,
                     );
-                    MarketID::global_first(world).search(
+                    market::search(
                         instant,
                         location,
+                        known_position,
                         resource,
                         id_as_eval_requester,
                         world,
@@ -335,6 +520,7 @@ pub trait Household:
                         results_counter: initial_counter,
                         best_deal: COption(None),
                         best_deal_usefulness: 0.0,
+                        candidates: CVec::new(),
                     },
                 );
             }
@@ -363,21 +549,29 @@ pub trait Household:
                                 debug(
                                     LOG_T,
                                     format!(
-                                        "Got eval'd deal for {}, {:?} -> {:?}\n",
+                                        "Got eval'd deal for {}, open {:?} today\n",
                                         evaluated_deal.deal.main_given(),
-                                        evaluated_deal.opening_hours.start.hours_minutes(),
-                                        evaluated_deal.opening_hours.end.hours_minutes(),
+                                        evaluated_deal.opening_hours.on(Weekday::from(instant)),
                                     ),
                                     log_as,
                                     world,
                                 );
-                                if evaluated_deal.opening_hours.contains(instant) {
-                                    let new_deal_usefulness = Self::deal_usefulness(
-                                        top_problems,
-                                        evaluated_deal,
-                                        log_as,
-                                        world,
-                                    );
+                                let is_open = evaluated_deal.opening_hours.contains(instant);
+                                let new_deal_usefulness = Self::deal_usefulness(
+                                    top_problems,
+                                    evaluated_deal,
+                                    log_as,
+                                    world,
+                                );
+                                entry.candidates.push(DecisionCandidate {
+                                    resource,
+                                    offer: evaluated_deal.offer,
+                                    usefulness: new_deal_usefulness,
+                                    travel_time: evaluated_deal.travel_time,
+                                    waiting_time: evaluated_deal.waiting_time,
+                                    open: is_open,
+                                });
+                                if is_open {
                                     if new_deal_usefulness > entry.best_deal_usefulness {
                                         entry.best_deal = COption(Some(evaluated_deal.clone()));
                                         entry.best_deal_usefulness = new_deal_usefulness;
@@ -460,20 +654,38 @@ pub trait Household:
         let id_as_household = self.id_as();
         let id_as_sleeper = self.id_as();
         debug(LOG_T, "Choosing deal!", self.id(), world);
-
         let maybe_best_info = {
             let core = self.core_mut();
 
-            if let DecisionState::Choosing(member, instant, _, ref entries) = core.decision_state {
+            if let DecisionState::Choosing(member, instant, ref top_problems, ref entries) =
+                core.decision_state
+            {
+                for (resource, entry) in entries.pairs() {
+                    core.unreported_searches
+                        .mut_entry_or(*resource, SearchTally::default())
+                        .record(entry.best_deal.is_some());
+                }
                 let maybe_best = most_useful_evaluated_deal(entries);
 
+                core.decision_history.record(DecisionTrace {
+                    member,
+                    instant,
+                    top_problems: top_problems.clone(),
+                    candidates: entries
+                        .values()
+                        .flat_map(|entry| entry.candidates.iter().cloned())
+                        .collect(),
+                    chosen: maybe_best.as_ref().map(|best| best.offer),
+                });
+
                 if let Some(best) = maybe_best {
                     let task = &mut core.member_tasks[member.as_idx()];
 
                     *task = if let TaskState::IdleAt(location) = task.state {
                         Task {
                             goal: Some((best.deal.main_given(), best.offer)),
-                            duration: best.deal.duration,
+                            // actual waiting is added once the member arrives and queues
+                            duration: Duration(best.deal.duration.0 - best.waiting_time.0),
                             state: TaskState::GettingReadyAt(location),
                         }
                     } else {
@@ -498,7 +710,8 @@ pub trait Household:
 
         if let Some((member, instant, best)) = maybe_best_info {
             {
-                let (used_offers, maybe_member) = if Self::supplier_shared(best.deal.main_given()) {
+                let is_supplier_shared = self.supplier_shared(best.deal.main_given());
+                let (used_offers, maybe_member) = if is_supplier_shared {
                     (&mut self.core_mut().used_offers, None)
                 } else {
                     (
@@ -616,8 +829,9 @@ pub trait Household:
             .expect("Should have a matching task");
         {
             let id_as_household = self.id_as();
+            let is_supplier_shared = self.supplier_shared(matching_resource);
 
-            let (used_offers, maybe_member) = if Self::supplier_shared(matching_resource) {
+            let (used_offers, maybe_member) = if is_supplier_shared {
                 (&mut self.core_mut().used_offers, None)
             } else {
                 (
@@ -658,6 +872,8 @@ pub trait Household:
                     world,
                 );
 
+                self.core_mut().member_agendas[matching_task_member.as_idx()].invalidate();
+
                 if let Some((_, offer)) =
                     self.core().member_tasks[matching_task_member.as_idx()].goal
                 {
@@ -695,7 +911,7 @@ pub trait Household:
         if let Some((_, offer)) = self.core().member_tasks[member.as_idx()].goal {
             offer
                 .household
-                .started_actively_using(offer.idx, self.id_as(), member, world);
+                .started_actively_using(offer.idx, self.id_as(), member, start, world);
         }
         self.core_mut().member_tasks[member.as_idx()].state = TaskState::StartedAt(start, location);
     }
@@ -732,22 +948,97 @@ pub trait Household:
             self.core_mut().member_tasks[member.as_idx()].state =
                 TaskState::IdleAt(location.unwrap_or_else(|| self.site()));
 
-            if let Some((_, offer)) = self.core().member_tasks[member.as_idx()].goal {
-                offer
-                    .household
-                    .stopped_actively_using(offer.idx, self.id_as(), member, world);
+            if let (TaskState::StartedAt(start, _), Some((_, offer))) =
+                (old_state, self.core().member_tasks[member.as_idx()].goal)
+            {
+                // the member leaves at the latest when the task was due to end
+                let left_at = start + self.core().member_tasks[member.as_idx()].duration;
+                offer.household.stopped_actively_using(
+                    offer.idx,
+                    self.id_as(),
+                    member,
+                    left_at,
+                    world,
+                );
             }
 
             TimeID::local_first(world).wake_up_in(Ticks(0), self.id_as(), world);
         }
     }
 
-    fn on_tick(&mut self, current_instant: Instant, world: &mut World) {
-        if (current_instant.ticks() + self.id().as_raw().instance_id as usize)
-            % (UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND) as usize
-            == 0
-        {
-            self.decay(Duration(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND), world);
+    fn on_tick(&mut self, dt: f32, current_instant: Instant, world: &mut World) {
+        // a tick covers more than one tick's worth of time when time is fast-forwarded,
+        // so catch up on all the updates that would have happened in between
+        let start = current_instant.ticks() + self.id().as_raw().instance_id as usize;
+        let end = start + ((dt * TICKS_PER_SIM_SECOND as f32).round() as usize).max(1);
+        let periods_within =
+            |period: usize| (end + period - 1) / period - (start + period - 1) / period;
+
+        let n_updates = periods_within((UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND) as usize);
+        if n_updates > 0 {
+            let update_dt = Duration(n_updates as u32 * UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND);
+            self.decay(update_dt, world);
+            let worst_graveness = self.worst_graveness(TimeOfDay::from(current_instant));
+            self.core_mut()
+                .satisfaction
+                .update(worst_graveness, update_dt);
+            self.update_finances(update_dt, current_instant, world);
+        }
+
+        if periods_within((REPORT_SATISFACTION_EVERY_N_SECS * TICKS_PER_SIM_SECOND) as usize) > 0 {
+            self.building()
+                .report_satisfaction(self.id_as(), self.core().satisfaction.0, world);
+
+            if self.reports_family_satisfaction() {
+                ImmigrationManagerID::global_first(world)
+                    .report_family_satisfaction(self.core().satisfaction.0, world);
+            }
+
+            if !self.core().unreported_searches.is_empty() {
+                let searches = ::std::mem::replace(
+                    &mut self.core_mut().unreported_searches,
+                    ResourceMap::new(),
+                );
+                DevelopmentManagerID::global_first(world).report_search_outcomes(searches, world);
+            }
+        }
+    }
+
+    fn update_finances(&mut self, dt: Duration, current_instant: Instant, world: &mut World) {
+        let max_debt = match self.max_debt() {
+            Some(max_debt) if !self.core().being_destroyed => max_debt,
+            _ => return,
+        };
+
+        let money = self
+            .core()
+            .resources
+            .get(Resource::Money)
+            .cloned()
+            .unwrap_or(0.0);
+        let worst_graveness = self.worst_graveness(TimeOfDay::from(current_instant));
+
+        let maybe_failure =
+            self.core_mut()
+                .finances
+                .update(money, max_debt, worst_graveness, dt, current_instant);
+
+        if let Some(failure) = maybe_failure {
+            info(
+                LOG_T,
+                match failure {
+                    FinancialFailure::Bankruptcy => {
+                        format!("{} went bankrupt and closes", self.household_name())
+                    }
+                    FinancialFailure::UnmetNeeds => format!(
+                        "{} couldn't meet their needs for too long and emigrates",
+                        self.household_name()
+                    ),
+                },
+                self.id(),
+                world,
+            );
+            self.destroy(world);
         }
     }
 
@@ -769,10 +1060,11 @@ pub trait Household:
     ) {
         let offer = self.get_offer(offer_idx);
 
-        if offer
-            .opening_hours
-            .end_after_on_same_day(TimeOfDay::from(instant))
-        {
+        if offer.opening_hours.end_after_on_same_day(instant) {
+            let waiting_time = offer.expected_waiting_time();
+            let mut deal = offer.deal.clone();
+            deal.duration += waiting_time;
+
             let search_result = EvaluatedSearchResult {
                 resource: offer.deal.main_given(),
                 evaluated_deals: vec![EvaluatedDeal {
@@ -780,8 +1072,10 @@ pub trait Household:
                         household: self.id_as(),
                         idx: offer_idx,
                     },
-                    deal: offer.deal.clone(),
+                    deal,
                     opening_hours: offer.opening_hours,
+                    travel_time: Duration(0),
+                    waiting_time,
                 }]
                 .into(),
             };
@@ -820,6 +1114,32 @@ pub trait Household:
         let offer = self.get_offer(offer_idx).clone(); // borrow checker too dumb
         self.provide_deal(&offer.deal, offer.offering_member, world);
         requester.receive_deal(offer.deal, requester_member, world);
+
+        if !offer.is_internal {
+            if let Some(&money) = offer.deal.delta.get(Resource::Money) {
+                if money != 0.0 {
+                    CityBudgetID::global_first(world).levy_on_deal(
+                        money,
+                        self.id_as(),
+                        requester,
+                        world,
+                    );
+                }
+            }
+        }
+    }
+
+    fn pay_tax(
+        &mut self,
+        amount: ResourceAmount,
+        item: BudgetItem,
+        budget: CityBudgetID,
+        world: &mut World,
+    ) {
+        if self.pays_taxes() && !self.core().being_destroyed {
+            *self.core_mut().resources.mut_entry_or(Resource::Money, 0.0) -= amount;
+            budget.receive(item, amount, world);
+        }
     }
 
     fn request_receive_undo_deal(
@@ -842,11 +1162,12 @@ pub trait Household:
         world: &mut World,
     ) {
         let id_as_household = self.id_as();
+        let partition = market::partition_for(self.core().site_position);
         let offer = self.get_offer_mut(offer_idx);
         if !offer.users.contains(&(user, using_member)) {
             offer.users.push((user, using_member));
             if !offer.is_internal && offer.users.len() >= offer.max_users as usize {
-                MarketID::global_first(world).withdraw(
+                partition.withdraw(
                     offer.deal.main_given(),
                     OfferID {
                         household: id_as_household,
@@ -880,6 +1201,7 @@ pub trait Household:
     ) -> Fate {
         {
             let id_as_household = self.id_as();
+            let site_position = self.core().site_position;
             let offer = self.get_offer_mut(offer_idx);
             let users_before = offer.users.len();
 
@@ -891,12 +1213,13 @@ pub trait Household:
                 && users_before >= offer.max_users as usize
                 && offer.users.len() < offer.max_users as usize
             {
-                MarketID::global_first(world).register(
+                market::partition_for(site_position).register(
                     offer.deal.main_given(),
                     OfferID {
                         household: id_as_household,
                         idx: offer_idx,
                     },
+                    site_position,
                     world,
                 );
             }
@@ -920,11 +1243,50 @@ pub trait Household:
         offer_idx: OfferIdx,
         user: HouseholdID,
         using_member: MemberIdx,
-        _: &mut World,
+        arrival: Instant,
+        world: &mut World,
     ) {
         let offer = self.get_offer_mut(offer_idx);
-        if !offer.active_users.contains(&(user, using_member)) {
-            offer.active_users.push((user, using_member));
+        if !offer
+            .active_users
+            .iter()
+            .any(|&(o_user, o_using_member, _)| o_user == user && o_using_member == using_member)
+        {
+            let waiting_time = offer.waiting_time_at(offer.active_users.len());
+            offer
+                .active_users
+                .push((user, using_member, arrival + waiting_time));
+
+            if waiting_time > Duration(0) {
+                user.delay_task(using_member, waiting_time, world);
+            }
+        }
+    }
+
+    /// Called by the provider of the offer a member is using when they have to queue
+    fn delay_task(&mut self, member: MemberIdx, delay: Duration, world: &mut World) {
+        if let TaskState::StartedAt(start, _) = self.core().member_tasks[member.as_idx()].state {
+            self.core_mut().member_tasks[member.as_idx()].duration += delay;
+            let task_end = start + self.core().member_tasks[member.as_idx()].duration;
+
+            let scheduler = TaskEndSchedulerID::local_first(world);
+            scheduler.deschedule(self.id_as(), member, world);
+            scheduler.schedule(task_end, self.id_as(), member, world);
+        }
+    }
+
+    /// Called by the provider of the offer a member is using when users ahead in the
+    /// queue left and the member will be served earlier than expected
+    fn hasten_task(&mut self, member: MemberIdx, by: Duration, world: &mut World) {
+        if let TaskState::StartedAt(start, _) = self.core().member_tasks[member.as_idx()].state {
+            let duration = self.core().member_tasks[member.as_idx()].duration;
+            self.core_mut().member_tasks[member.as_idx()].duration =
+                Duration(duration.0.saturating_sub(by.0));
+            let task_end = start + self.core().member_tasks[member.as_idx()].duration;
+
+            let scheduler = TaskEndSchedulerID::local_first(world);
+            scheduler.deschedule(self.id_as(), member, world);
+            scheduler.schedule(task_end, self.id_as(), member, world);
         }
     }
 
@@ -933,12 +1295,29 @@ pub trait Household:
         offer_idx: OfferIdx,
         user: HouseholdID,
         using_member: MemberIdx,
-        _: &mut World,
+        left_at: Instant,
+        world: &mut World,
     ) {
         let offer = self.get_offer_mut(offer_idx);
-        offer
-            .active_users
-            .retain(|&(o_user, o_using_member)| o_user != user || o_using_member != using_member);
+        offer.active_users.retain(|&(o_user, o_using_member, _)| {
+            o_user != user || o_using_member != using_member
+        });
+
+        // everyone still queueing behind moves up
+        for i in 0..offer.active_users.len() {
+            let (queued_user, queued_member, served_at) = offer.active_users[i];
+            let served_at_now = left_at + offer.waiting_time_at(i);
+
+            if served_at_now < served_at {
+                offer.active_users[i].2 = served_at_now;
+                let earlier_by = (served_at.ticks() - served_at_now.ticks()) as u32;
+                queued_user.hasten_task(
+                    queued_member,
+                    Duration(earlier_by / TICKS_PER_SIM_SECOND),
+                    world,
+                );
+            }
+        }
     }
 
     // TODO: there is still a tiny potential race condition here:
@@ -970,6 +1349,24 @@ pub trait Household:
                 Fate::Live // for now
             }
         } else {
+            if let Some(position) = self
+                .core()
+                .relocating_offers
+                .iter()
+                .position(|&relocating| relocating == offer_idx)
+            {
+                self.core_mut().relocating_offers.remove(position);
+                let site_position = self.core().site_position;
+                market::partition_for(site_position).register(
+                    self.get_offer(offer_idx).deal.main_given(),
+                    OfferID {
+                        household: self.id_as(),
+                        idx: offer_idx,
+                    },
+                    site_position,
+                    world,
+                );
+            }
             Fate::Live
         }
     }
@@ -990,6 +1387,7 @@ pub struct DecisionResourceEntry {
     results_counter: AsyncCounter,
     best_deal: COption<EvaluatedDeal>,
     best_deal_usefulness: f32,
+    candidates: CVec<DecisionCandidate>,
 }
 
 #[derive(Compact, Clone, Debug, Serialize)]
@@ -1009,11 +1407,22 @@ pub struct HouseholdCore {
     pub resources: Inventory,
     pub member_resources: CVec<Inventory>,
     pub member_tasks: CVec<Task>,
+    pub member_agendas: CVec<Agenda>,
     pub decision_state: DecisionState,
     pub used_offers: ResourceMap<OfferID>,
     pub member_used_offers: CVec<ResourceMap<OfferID>>,
     pub provided_offers: CVec<Offer>,
     pub being_destroyed: bool,
+    /// Position of the building the household lives or works in,
+    /// which its offers are registered at in the market
+    pub site_position: P2,
+    /// Offers withdrawn from the old site of a relocating household, to be registered again
+    pub relocating_offers: CVec<OfferIdx>,
+    /// Outcomes of market searches since they were last reported to the development manager
+    pub unreported_searches: ResourceMap<SearchTally>,
+    pub finances: Finances,
+    pub satisfaction: Satisfaction,
+    pub decision_history: DecisionHistory,
 }
 
 impl HouseholdCore {
@@ -1022,17 +1431,21 @@ impl HouseholdCore {
         world: &mut World,
         n_members: usize,
         initial_location: RoughLocationID,
+        initial_position: P2,
         provided_offers: CVec<Offer>,
     ) -> Self {
         assert!(n_members > 0);
 
+        let partition = market::partition_for(initial_position);
+
         for (idx, offer) in provided_offers.iter().enumerate() {
-            MarketID::local_first(world).register(
+            partition.register(
                 offer.deal.main_given(),
                 OfferID {
                     household: owner,
                     idx: OfferIdx(idx as u16),
                 },
+                initial_position,
                 world,
             )
         }
@@ -1041,11 +1454,18 @@ impl HouseholdCore {
             resources: Inventory::new(),
             member_resources: vec![Inventory::new(); n_members].into(),
             member_tasks: vec![Task::idle_at(initial_location); n_members].into(),
+            member_agendas: vec![Agenda::default(); n_members].into(),
             decision_state: DecisionState::None,
             used_offers: ResourceMap::new(),
             member_used_offers: vec![ResourceMap::new(); n_members].into(),
             provided_offers,
             being_destroyed: false,
+            site_position: initial_position,
+            relocating_offers: CVec::new(),
+            unreported_searches: ResourceMap::new(),
+            finances: Finances::default(),
+            satisfaction: Satisfaction::default(),
+            decision_history: DecisionHistory::default(),
         }
     }
 }
@@ -1054,18 +1474,14 @@ pub fn setup(system: &mut ActorSystem) {
     auto_setup(system);
     tasks::setup(system);
     family::setup(system);
-    grocery_shop::setup(system);
-    grain_farm::setup(system);
-    cow_farm::setup(system);
-    vegetable_farm::setup(system);
-    mill::setup(system);
-    bakery::setup(system);
+    configurable::setup(system);
     neighboring_town_trade::setup(system);
     ui::auto_setup(system);
 }
 
-pub fn spawn(world: &mut World) {
-    tasks::spawn(world);
+pub fn spawn(world: &mut World, time: TimeID) {
+    tasks::spawn(world, time);
+    configurable::spawn(world);
 }
 
 mod kay_auto;
@@ -1,12 +1,12 @@
 use compact::CVec;
 use economy::market::Deal;
 use super::{HouseholdID, MemberIdx};
-use cb_time::units::TimeOfDayRange;
+use cb_time::units::{OpeningHours, Duration, Instant};
 
-#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
 pub struct OfferIdx(pub u16);
 
-#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
 pub struct OfferID {
     pub household: HouseholdID,
     pub idx: OfferIdx,
@@ -15,34 +15,55 @@ pub struct OfferID {
 #[derive(Compact, Clone, Debug, Serialize)]
 pub struct Offer {
     pub offering_member: MemberIdx,
-    pub opening_hours: TimeOfDayRange,
+    pub opening_hours: OpeningHours,
     pub deal: Deal,
     pub max_users: u32,
+    /// How many active users can be served at the same time, any further ones have to queue
+    pub service_capacity: u32,
     pub is_internal: bool,
     pub users: CVec<(HouseholdID, Option<MemberIdx>)>,
-    pub active_users: CVec<(HouseholdID, MemberIdx)>,
+    /// Users at the offer in order of arrival, with the instant they are expected to be served
+    pub active_users: CVec<(HouseholdID, MemberIdx, Instant)>,
     pub being_withdrawn: bool,
 }
 
 impl Offer {
-    pub fn new(
+    pub fn new<H: Into<OpeningHours>>(
         offering_member: MemberIdx,
-        opening_hours: TimeOfDayRange,
+        opening_hours: H,
         deal: Deal,
         max_users: usize,
         is_internal: bool,
     ) -> Offer {
         Offer {
             offering_member,
-            opening_hours,
+            opening_hours: opening_hours.into(),
             deal,
             users: CVec::new(),
             active_users: CVec::new(),
             is_internal,
             max_users: max_users as u32,
+            service_capacity: max_users as u32,
             being_withdrawn: false,
         }
     }
+
+    pub fn with_service_capacity(mut self, service_capacity: u32) -> Offer {
+        self.service_capacity = service_capacity.max(1);
+        self
+    }
+
+    /// How long the active user at `queue_position` (0 being the first to arrive)
+    /// has to wait before being served, assuming users are served in batches
+    pub fn waiting_time_at(&self, queue_position: usize) -> Duration {
+        let batches_ahead = queue_position / self.service_capacity.max(1) as usize;
+        Duration(self.deal.duration.0 * batches_ahead as u32)
+    }
+
+    /// How long a newly arriving user would have to wait
+    pub fn expected_waiting_time(&self) -> Duration {
+        self.waiting_time_at(self.active_users.len())
+    }
 }
 
 //     // The offer stays alive until the withdrawal is confirmed
@@ -0,0 +1,24 @@
+use cb_time::units::Duration;
+
+/// Graveness of the worst problem above which needs count as unmet
+pub const UNMET_NEEDS_GRAVENESS: f32 = 20.0;
+/// Over how long unmet needs are remembered in the satisfaction score
+const SATISFACTION_WINDOW: Duration = Duration(24 * 60 * 60);
+
+/// Wellbeing of a household, from 0 (needs gravely unmet for about a day) to 1 (all needs met)
+#[derive(Copy, Clone, Debug, Serialize)]
+pub struct Satisfaction(pub f32);
+
+impl Default for Satisfaction {
+    fn default() -> Self {
+        Satisfaction(1.0)
+    }
+}
+
+impl Satisfaction {
+    pub fn update(&mut self, worst_graveness: f32, dt: Duration) {
+        let needs_met = 1.0 - (worst_graveness / UNMET_NEEDS_GRAVENESS).max(0.0).min(1.0);
+        let smoothing = dt.as_seconds() / (dt.as_seconds() + SATISFACTION_WINDOW.as_seconds());
+        self.0 += smoothing * (needs_met - self.0);
+    }
+}
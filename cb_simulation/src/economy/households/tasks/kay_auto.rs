@@ -55,10 +55,10 @@ impl TypedID for TaskEndSchedulerID {
 }
 
 impl TaskEndSchedulerID {
-    pub fn spawn(world: &mut World) -> Self {
+    pub fn spawn(time: TimeID, world: &mut World) -> Self {
         let id = TaskEndSchedulerID::from_raw(world.allocate_instance_id::<TaskEndScheduler>());
         let swarm = world.local_broadcast::<TaskEndScheduler>();
-        world.send(swarm, MSG_TaskEndScheduler_spawn(id, ));
+        world.send(swarm, MSG_TaskEndScheduler_spawn(id, time));
         id
     }
     
@@ -71,16 +71,16 @@ impl TaskEndSchedulerID {
     }
 }
 
-#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
-struct MSG_TaskEndScheduler_spawn(pub TaskEndSchedulerID, );
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TaskEndScheduler_spawn(pub TaskEndSchedulerID, pub TimeID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_TaskEndScheduler_schedule(pub Instant, pub HouseholdID, pub MemberIdx);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_TaskEndScheduler_deschedule(pub HouseholdID, pub MemberIdx);
 
-impl Into<TemporalID> for TaskEndSchedulerID {
-    fn into(self) -> TemporalID {
-        TemporalID::from_raw(self.as_raw())
+impl Into<SleeperID> for TaskEndSchedulerID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
     }
 }
 
@@ -88,10 +88,10 @@ impl Into<TemporalID> for TaskEndSchedulerID {
 #[allow(unused_mut)]
 pub fn auto_setup(system: &mut ActorSystem) {
     
-    TemporalID::register_implementor::<TaskEndScheduler>(system);
+    SleeperID::register_implementor::<TaskEndScheduler>(system);
     system.add_spawner::<TaskEndScheduler, _, _>(
-        |&MSG_TaskEndScheduler_spawn(id, ), world| {
-            TaskEndScheduler::spawn(id, world)
+        |&MSG_TaskEndScheduler_spawn(id, time), world| {
+            TaskEndScheduler::spawn(id, time, world)
         }, false
     );
     
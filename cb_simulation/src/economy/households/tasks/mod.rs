@@ -1,7 +1,7 @@
 use kay::{ActorSystem, World};
-use compact::CVec;
-use cb_time::actors::{Temporal, TemporalID};
-use cb_time::units::{Instant, Duration};
+use compact::CHashMap;
+use cb_time::actors::{TimeID, Sleeper, SleeperID, ScheduleHandle};
+use cb_time::units::{Instant, Duration, Schedule};
 use transport::pathfinding::RoughLocationID;
 use transport::pathfinding::trip::TripID;
 use super::super::resources::Resource;
@@ -34,17 +34,25 @@ impl Task {
     }
 }
 
+/// Keeps track of when the current tasks of household members end, using
+/// scheduled events of `Time` which can be cancelled when a task is interrupted
 #[derive(Compact, Clone)]
 pub struct TaskEndScheduler {
     id: TaskEndSchedulerID,
-    task_ends: CVec<(Instant, HouseholdID, MemberIdx)>,
+    time: TimeID,
+    task_ends: CHashMap<ScheduleHandle, (HouseholdID, MemberIdx)>,
+    handles: CHashMap<(HouseholdID, MemberIdx), ScheduleHandle>,
+    n_scheduled: u32,
 }
 
 impl TaskEndScheduler {
-    pub fn spawn(id: TaskEndSchedulerID, _: &mut World) -> TaskEndScheduler {
+    pub fn spawn(id: TaskEndSchedulerID, time: TimeID, _: &mut World) -> TaskEndScheduler {
         TaskEndScheduler {
             id,
-            task_ends: CVec::new(),
+            time,
+            task_ends: CHashMap::new(),
+            handles: CHashMap::new(),
+            n_scheduled: 0,
         }
     }
 
@@ -53,36 +61,33 @@ impl TaskEndScheduler {
         end: Instant,
         household: HouseholdID,
         member: MemberIdx,
-        _: &mut World,
+        world: &mut World,
     ) {
-        let maybe_idx = self
-            .task_ends
-            .binary_search_by_key(&(-end.iticks()), |&(e, ..)| -(e.iticks()));
-        let insert_idx = match maybe_idx {
-            Ok(idx) | Err(idx) => idx,
-        };
-        self.task_ends.insert(insert_idx, (end, household, member));
+        self.deschedule(household, member, world);
+
+        let handle = ScheduleHandle::new(self.id, self.n_scheduled);
+        self.n_scheduled = self.n_scheduled.wrapping_add(1);
+        self.task_ends.insert(handle, (household, member));
+        self.handles.insert((household, member), handle);
+        self.time
+            .schedule(handle, Schedule::At(end), self.id.into(), world);
     }
 
-    pub fn deschedule(&mut self, household: HouseholdID, member: MemberIdx, _: &mut World) {
-        self.task_ends.retain(|&(_, task_household, task_member)| {
-            task_household != household || task_member != member
-        });
+    pub fn deschedule(&mut self, household: HouseholdID, member: MemberIdx, world: &mut World) {
+        if let Some(handle) = self.handles.remove((household, member)) {
+            self.task_ends.remove(handle);
+            self.time.cancel(handle, world);
+        }
     }
 }
 
-impl Temporal for TaskEndScheduler {
-    fn tick(&mut self, _dt: f32, current_instant: Instant, world: &mut World) {
-        while self
-            .task_ends
-            .last()
-            .map(|&(end, ..)| end < current_instant)
-            .unwrap_or(false)
-        {
-            let (_, household, member) = self
-                .task_ends
-                .pop()
-                .expect("just checked that there are WIP tasks");
+impl Sleeper for TaskEndScheduler {
+    fn wake(&mut self, _current_instant: Instant, _: &mut World) {}
+
+    fn wake_scheduled(&mut self, handle: ScheduleHandle, _: Instant, world: &mut World) {
+        // the task might have been descheduled while this was already on its way
+        if let Some((household, member)) = self.task_ends.remove(handle) {
+            self.handles.remove((household, member));
             household.task_succeeded(member, world);
         }
     }
@@ -93,8 +98,8 @@ pub fn setup(system: &mut ActorSystem) {
     auto_setup(system);
 }
 
-pub fn spawn(world: &mut World) {
-    TaskEndSchedulerID::spawn(world);
+pub fn spawn(world: &mut World, time: TimeID) {
+    TaskEndSchedulerID::spawn(time, world);
 }
 
 mod kay_auto;
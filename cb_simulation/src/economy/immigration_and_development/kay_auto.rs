@@ -62,15 +62,39 @@ impl ImmigrationManagerID {
         id
     }
     
-    pub fn on_unit_offer(self, building_id: BuildingID, unit_idx: UnitIdx, world: &mut World) {
-        world.send(self.as_raw(), MSG_ImmigrationManager_on_unit_offer(building_id, unit_idx));
+    pub fn on_unit_offer(self, building_id: BuildingID, unit_idx: UnitIdx, building_position: P2, world: &mut World) {
+        world.send(self.as_raw(), MSG_ImmigrationManager_on_unit_offer(building_id, unit_idx, building_position));
+    }
+    
+    pub fn request_household(self, household_type: HouseholdTypeToSpawn, world: &mut World) {
+        world.send(self.as_raw(), MSG_ImmigrationManager_request_household(household_type));
+    }
+    
+    pub fn update_job_availability(self, job_availability: f32, world: &mut World) {
+        world.send(self.as_raw(), MSG_ImmigrationManager_update_job_availability(job_availability));
+    }
+    
+    pub fn report_family_satisfaction(self, satisfaction: f32, world: &mut World) {
+        world.send(self.as_raw(), MSG_ImmigrationManager_report_family_satisfaction(satisfaction));
     }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_ImmigrationManager_spawn(pub ImmigrationManagerID, pub TimeID, pub DevelopmentManagerID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_ImmigrationManager_on_unit_offer(pub BuildingID, pub UnitIdx);
+struct MSG_ImmigrationManager_on_unit_offer(pub BuildingID, pub UnitIdx, pub P2);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ImmigrationManager_request_household(pub HouseholdTypeToSpawn);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ImmigrationManager_update_job_availability(pub f32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ImmigrationManager_report_family_satisfaction(pub f32);
+
+impl Into<ConfigUserID<HouseholdKindDefinition>> for ImmigrationManagerID {
+    fn into(self) -> ConfigUserID<HouseholdKindDefinition> {
+        ConfigUserID::from_raw(self.as_raw())
+    }
+}
 
 impl Into<SleeperID> for ImmigrationManagerID {
     fn into(self) -> SleeperID {
@@ -139,6 +163,22 @@ impl DevelopmentManagerID {
     pub fn on_suggested_lot(self, building_intent: BuildingIntent, based_on: PrototypeID, world: &mut World) {
         world.send(self.as_raw(), MSG_DevelopmentManager_on_suggested_lot(building_intent, based_on));
     }
+    
+    pub fn report_search_outcomes(self, searches: ResourceMap < SearchTally >, world: &mut World) {
+        world.send(self.as_raw(), MSG_DevelopmentManager_report_search_outcomes(searches));
+    }
+    
+    pub fn relocate(self, household: HouseholdID, unit_type: UnitType, building_style: BuildingStyle, world: &mut World) {
+        world.send(self.as_raw(), MSG_DevelopmentManager_relocate(household, unit_type, building_style));
+    }
+    
+    pub fn cancel_relocation(self, household: HouseholdID, world: &mut World) {
+        world.send(self.as_raw(), MSG_DevelopmentManager_cancel_relocation(household));
+    }
+    
+    pub fn on_relocation_unit_offer(self, household: HouseholdID, unit_type: UnitType, building_id: BuildingID, unit_idx: UnitIdx, building_position: P2, world: &mut World) {
+        world.send(self.as_raw(), MSG_DevelopmentManager_on_relocation_unit_offer(household, unit_type, building_id, unit_idx, building_position));
+    }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
@@ -147,6 +187,20 @@ struct MSG_DevelopmentManager_spawn(pub DevelopmentManagerID, pub TimeID, pub CB
 struct MSG_DevelopmentManager_try_develop(pub BuildingStyle);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_DevelopmentManager_on_suggested_lot(pub BuildingIntent, pub PrototypeID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_DevelopmentManager_report_search_outcomes(pub ResourceMap < SearchTally >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_DevelopmentManager_relocate(pub HouseholdID, pub UnitType, pub BuildingStyle);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_DevelopmentManager_cancel_relocation(pub HouseholdID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_DevelopmentManager_on_relocation_unit_offer(pub HouseholdID, pub UnitType, pub BuildingID, pub UnitIdx, pub P2);
+
+impl Into<ConfigUserID<HouseholdKindDefinition>> for DevelopmentManagerID {
+    fn into(self) -> ConfigUserID<HouseholdKindDefinition> {
+        ConfigUserID::from_raw(self.as_raw())
+    }
+}
 
 impl Into<SleeperID> for DevelopmentManagerID {
     fn into(self) -> SleeperID {
@@ -158,6 +212,7 @@ impl Into<SleeperID> for DevelopmentManagerID {
 #[allow(unused_mut)]
 pub fn auto_setup(system: &mut ActorSystem) {
     
+    ConfigUserID::<HouseholdKindDefinition>::register_implementor::<ImmigrationManager>(system);
     SleeperID::register_implementor::<ImmigrationManager>(system);
     system.add_spawner::<ImmigrationManager, _, _>(
         |&MSG_ImmigrationManager_spawn(id, time, development_manager), world| {
@@ -166,10 +221,29 @@ pub fn auto_setup(system: &mut ActorSystem) {
     );
     
     system.add_handler::<ImmigrationManager, _, _>(
-        |&MSG_ImmigrationManager_on_unit_offer(building_id, unit_idx), instance, world| {
-            instance.on_unit_offer(building_id, unit_idx, world); Fate::Live
+        |&MSG_ImmigrationManager_on_unit_offer(building_id, unit_idx, building_position), instance, world| {
+            instance.on_unit_offer(building_id, unit_idx, building_position, world); Fate::Live
         }, false
     );
+    
+    system.add_handler::<ImmigrationManager, _, _>(
+        |&MSG_ImmigrationManager_request_household(household_type), instance, world| {
+            instance.request_household(household_type, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<ImmigrationManager, _, _>(
+        |&MSG_ImmigrationManager_update_job_availability(job_availability), instance, world| {
+            instance.update_job_availability(job_availability, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<ImmigrationManager, _, _>(
+        |&MSG_ImmigrationManager_report_family_satisfaction(satisfaction), instance, world| {
+            instance.report_family_satisfaction(satisfaction, world); Fate::Live
+        }, false
+    );
+    ConfigUserID::<HouseholdKindDefinition>::register_implementor::<DevelopmentManager>(system);
     SleeperID::register_implementor::<DevelopmentManager>(system);
     system.add_spawner::<DevelopmentManager, _, _>(
         |&MSG_DevelopmentManager_spawn(id, time, plan_manager), world| {
@@ -188,4 +262,28 @@ pub fn auto_setup(system: &mut ActorSystem) {
             instance.on_suggested_lot(building_intent, based_on, world); Fate::Live
         }, false
     );
+    
+    system.add_handler::<DevelopmentManager, _, _>(
+        |&MSG_DevelopmentManager_report_search_outcomes(ref searches), instance, world| {
+            instance.report_search_outcomes(searches, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<DevelopmentManager, _, _>(
+        |&MSG_DevelopmentManager_relocate(household, unit_type, building_style), instance, world| {
+            instance.relocate(household, unit_type, building_style, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<DevelopmentManager, _, _>(
+        |&MSG_DevelopmentManager_cancel_relocation(household), instance, world| {
+            instance.cancel_relocation(household, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<DevelopmentManager, _, _>(
+        |&MSG_DevelopmentManager_on_relocation_unit_offer(household, unit_type, building_id, unit_idx, building_position), instance, world| {
+            instance.on_relocation_unit_offer(household, unit_type, building_id, unit_idx, building_position, world); Fate::Live
+        }, false
+    );
 }
\ No newline at end of file
@@ -1,65 +1,60 @@
 use kay::{World, ActorSystem, TypedID};
-use compact::COption;
+use compact::{CVec, CDict, COption, CHashMap};
 use land_use::buildings::{UnitType, BuildingID, UnitIdx};
 use cb_time::actors::{Sleeper, SleeperID, TimeID};
 use cb_time::units::{Instant, Duration};
+use cb_util::config_manager::{Name, ConfigUser, ConfigUserID};
 use cb_util::random::{seed, Rng};
-use cb_util::log::{debug};
+use cb_util::log::{debug, warn};
+use ordered_float::OrderedFloat;
+use descartes::P2;
 const LOG_T: &str = "Immigration/Development";
 
+use economy::resources::{Resource, ResourceMap, Entry};
+use economy::households::HouseholdID;
 use economy::households::household_kinds;
 use self::household_kinds::family::FamilyID;
-use self::household_kinds::grocery_shop::GroceryShopID;
-use self::household_kinds::cow_farm::CowFarmID;
-use self::household_kinds::grain_farm::GrainFarmID;
-use self::household_kinds::vegetable_farm::VegetableFarmID;
-use self::household_kinds::mill::MillID;
-use self::household_kinds::bakery::BakeryID;
+use self::household_kinds::configurable::{ConfigurableHouseholdID, HouseholdKindDefinition};
 use self::household_kinds::neighboring_town_trade::NeighboringTownTradeID;
 use land_use::buildings::BuildingStyle;
+use land_use::buildings::architecture::materials_and_props::BuildingMaterial;
 use land_use::vacant_lots::VacantLotID;
 use land_use::zone_planning::BuildingIntent;
 use cb_planning::{Project, PrototypeID, Plan, GestureID, Gesture};
 use planning::{CBPlanManagerID, CBGestureIntent};
 
-// TODO: somehow get rid of this horrible duplication by having something like
-// a pointer to an abstract Household trait...
-
 #[derive(Copy, Clone, Debug)]
 pub enum HouseholdTypeToSpawn {
     Family,
-    GroceryShop,
-    GrainFarm,
-    CowFarm,
-    VegetableFarm,
-    Mill,
-    Bakery,
     NeighboringTownTrade,
+    Configurable(Name),
 }
 
-pub fn unit_type_for(household_type: HouseholdTypeToSpawn) -> UnitType {
+pub fn unit_type_for(
+    household_type: HouseholdTypeToSpawn,
+    household_kinds: &CHashMap<Name, HouseholdKindDefinition>,
+) -> Option<UnitType> {
     match household_type {
-        HouseholdTypeToSpawn::Family => UnitType::Dwelling,
-        HouseholdTypeToSpawn::GroceryShop => UnitType::Retail,
-        HouseholdTypeToSpawn::GrainFarm
-        | HouseholdTypeToSpawn::CowFarm
-        | HouseholdTypeToSpawn::VegetableFarm => UnitType::Agriculture,
-        HouseholdTypeToSpawn::Mill => UnitType::Mill,
-        HouseholdTypeToSpawn::Bakery => UnitType::Bakery,
-        HouseholdTypeToSpawn::NeighboringTownTrade => UnitType::NeighboringTownTrade,
+        HouseholdTypeToSpawn::Family => Some(UnitType::Dwelling),
+        HouseholdTypeToSpawn::NeighboringTownTrade => Some(UnitType::NeighboringTownTrade),
+        HouseholdTypeToSpawn::Configurable(kind) => household_kinds
+            .get(kind)
+            .map(|definition| definition.unit_type),
     }
 }
 
-pub fn building_style_for(household_type: HouseholdTypeToSpawn) -> BuildingStyle {
+pub fn building_style_for(
+    household_type: HouseholdTypeToSpawn,
+    household_kinds: &CHashMap<Name, HouseholdKindDefinition>,
+) -> Option<BuildingStyle> {
     match household_type {
-        HouseholdTypeToSpawn::Family => BuildingStyle::FamilyHouse,
-        HouseholdTypeToSpawn::GroceryShop => BuildingStyle::GroceryShop,
-        HouseholdTypeToSpawn::GrainFarm
-        | HouseholdTypeToSpawn::CowFarm
-        | HouseholdTypeToSpawn::VegetableFarm => BuildingStyle::Field,
-        HouseholdTypeToSpawn::Mill => BuildingStyle::Mill,
-        HouseholdTypeToSpawn::Bakery => BuildingStyle::Bakery,
-        HouseholdTypeToSpawn::NeighboringTownTrade => BuildingStyle::NeighboringTownConnection,
+        HouseholdTypeToSpawn::Family => Some(BuildingStyle::FamilyHouse),
+        HouseholdTypeToSpawn::NeighboringTownTrade => {
+            Some(BuildingStyle::NeighboringTownConnection)
+        }
+        HouseholdTypeToSpawn::Configurable(kind) => household_kinds
+            .get(kind)
+            .map(|definition| definition.building_style),
     }
 }
 
@@ -69,6 +64,47 @@ pub struct ImmigrationManager {
     time: TimeID,
     development_manager: DevelopmentManagerID,
     state: ImmigrationManagerState,
+    household_kinds: CHashMap<Name, HouseholdKindDefinition>,
+    requested_household_types: CVec<HouseholdTypeToSpawn>,
+    conditions: ImmigrationConditions,
+}
+
+/// What makes the city attractive to immigrating families, each between 0 and 1
+#[derive(Copy, Clone, Debug)]
+pub struct ImmigrationConditions {
+    /// Smoothed share of attempts that found a free dwelling
+    pub housing_vacancy: f32,
+    /// Share of recent job searches that found a job
+    pub job_availability: f32,
+    /// Smoothed satisfaction reported by families already living here
+    pub family_satisfaction: f32,
+}
+
+impl Default for ImmigrationConditions {
+    fn default() -> Self {
+        ImmigrationConditions {
+            housing_vacancy: 1.0,
+            job_availability: 1.0,
+            family_satisfaction: 1.0,
+        }
+    }
+}
+
+impl ImmigrationConditions {
+    /// How much families would like to move here, regardless of housing
+    pub fn attractiveness(&self) -> f32 {
+        self.job_availability * self.family_satisfaction
+    }
+
+    pub fn family_immigration_chance(&self) -> f32 {
+        self.attractiveness()
+            * (MIN_VACANCY_FACTOR + (1.0 - MIN_VACANCY_FACTOR) * self.housing_vacancy)
+    }
+
+    fn record_housing_search(&mut self, found: bool) {
+        let outcome = if found { 1.0 } else { 0.0 };
+        self.housing_vacancy += VACANCY_SMOOTHING * (outcome - self.housing_vacancy);
+    }
 }
 
 impl ImmigrationManager {
@@ -80,15 +116,38 @@ impl ImmigrationManager {
     ) -> ImmigrationManager {
         time.wake_up_in(IMMIGRATION_PACE.into(), id.into(), world);
 
-        ImmigrationManager {
+        let manager = ImmigrationManager {
             id,
             time,
             development_manager,
             state: ImmigrationManagerState::Idle,
+            household_kinds: CHashMap::new(),
+            requested_household_types: CVec::new(),
+            conditions: ImmigrationConditions::default(),
+        };
+        manager.get_initial_config(world);
+        manager
+    }
+
+    fn choose_household_type(&mut self, current_instant: Instant) -> Option<HouseholdTypeToSpawn> {
+        if let Some(requested) = self.requested_household_types.pop() {
+            Some(requested)
+        } else if seed(current_instant).gen_range(0.0, 1.0)
+            < self.conditions.family_immigration_chance()
+        {
+            Some(HouseholdTypeToSpawn::Family)
+        } else {
+            None
         }
     }
 }
 
+impl ConfigUser<HouseholdKindDefinition> for ImmigrationManager {
+    fn local_cache(&mut self) -> &mut CHashMap<Name, HouseholdKindDefinition> {
+        &mut self.household_kinds
+    }
+}
+
 #[derive(Copy, Clone)]
 pub enum ImmigrationManagerState {
     Idle,
@@ -96,79 +155,63 @@ pub enum ImmigrationManagerState {
 }
 
 const IMMIGRATION_PACE: Duration = Duration(10);
+/// Fraction of the immigration rate that remains even when no dwellings are free,
+/// so that housing shortage still leads to new development
+const MIN_VACANCY_FACTOR: f32 = 0.5;
+const VACANCY_SMOOTHING: f32 = 0.1;
+const SATISFACTION_SMOOTHING: f32 = 0.01;
+/// Below this attractiveness, failing to find a dwelling doesn't lead to new houses
+const MIN_ATTRACTIVENESS_FOR_HOUSING: f32 = 0.3;
+const MAX_REQUESTED_HOUSEHOLDS: usize = 5;
 
 impl Sleeper for ImmigrationManager {
     fn wake(&mut self, current_instant: Instant, world: &mut World) {
         self.state = match self.state {
             ImmigrationManagerState::Idle => {
-                let family_share = 1.0;
-                let grocery_share = 0.2;
-                let cow_farm_share = 0.3;
-                let veg_farm_share = 0.26;
-                let grain_farm_share = 0.2; //0.0016;
-                let mill_share = 0.2; //0.001;
-                let bakery_share = 0.2; //0.01;
-
-                let total_share = family_share
-                    + grocery_share
-                    + cow_farm_share
-                    + veg_farm_share
-                    + grain_farm_share
-                    + mill_share
-                    + bakery_share;
-
-                let dot = seed(current_instant).gen_range(0.0, total_share);
-
-                let household_type_to_spawn = if dot < family_share {
-                    HouseholdTypeToSpawn::Family
-                } else if dot < family_share + grocery_share {
-                    HouseholdTypeToSpawn::GroceryShop
-                } else if dot < family_share + grocery_share + cow_farm_share {
-                    HouseholdTypeToSpawn::CowFarm
-                } else if dot < family_share + grocery_share + cow_farm_share + veg_farm_share {
-                    HouseholdTypeToSpawn::VegetableFarm
-                } else if dot
-                    < family_share
-                        + grocery_share
-                        + cow_farm_share
-                        + veg_farm_share
-                        + grain_farm_share
-                {
-                    HouseholdTypeToSpawn::GrainFarm
-                } else if dot
-                    < family_share
-                        + grocery_share
-                        + cow_farm_share
-                        + veg_farm_share
-                        + grain_farm_share
-                        + mill_share
-                {
-                    HouseholdTypeToSpawn::Mill
+                if let Some(household_type_to_spawn) = self.choose_household_type(current_instant) {
+                    debug(
+                        LOG_T,
+                        format!(
+                            "Trying to spawn {:?} ({:?})",
+                            household_type_to_spawn, self.conditions
+                        ),
+                        self.id,
+                        world,
+                    );
+
+                    if let Some(required_unit_type) =
+                        unit_type_for(household_type_to_spawn, &self.household_kinds)
+                    {
+                        BuildingID::global_broadcast(world).try_offer_unit(
+                            required_unit_type,
+                            self.id,
+                            world,
+                        );
+
+                        ImmigrationManagerState::FindingBuilding(household_type_to_spawn)
+                    } else {
+                        ImmigrationManagerState::Idle
+                    }
                 } else {
-                    HouseholdTypeToSpawn::Bakery
-                };
-
-                debug(
-                    LOG_T,
-                    format!("Trying to spawn {:?}", household_type_to_spawn),
-                    self.id,
-                    world,
-                );
-
-                let required_unit_type = unit_type_for(household_type_to_spawn);
-
-                BuildingID::global_broadcast(world).try_offer_unit(
-                    required_unit_type,
-                    self.id,
-                    world,
-                );
-
-                ImmigrationManagerState::FindingBuilding(household_type_to_spawn)
+                    ImmigrationManagerState::Idle
+                }
             }
             ImmigrationManagerState::FindingBuilding(household_type_to_spawn) => {
                 // didn't find a building in time
-                self.development_manager
-                    .try_develop(building_style_for(household_type_to_spawn), world);
+                let should_develop = if let HouseholdTypeToSpawn::Family = household_type_to_spawn {
+                    self.conditions.record_housing_search(false);
+                    self.conditions.attractiveness() > MIN_ATTRACTIVENESS_FOR_HOUSING
+                } else {
+                    true
+                };
+
+                if should_develop {
+                    if let Some(building_style) =
+                        building_style_for(household_type_to_spawn, &self.household_kinds)
+                    {
+                        self.development_manager.try_develop(building_style, world);
+                    }
+                }
 
                 ImmigrationManagerState::Idle
             }
@@ -180,46 +223,138 @@ impl Sleeper for ImmigrationManager {
 }
 
 impl ImmigrationManager {
-    pub fn on_unit_offer(&mut self, building_id: BuildingID, unit_idx: UnitIdx, world: &mut World) {
+    pub fn on_unit_offer(
+        &mut self,
+        building_id: BuildingID,
+        unit_idx: UnitIdx,
+        building_position: P2,
+        world: &mut World,
+    ) {
         debug(LOG_T, "Got offer", self.id, world);
         self.state = match self.state {
             ImmigrationManagerState::FindingBuilding(household_type_to_spawn) => {
                 debug(LOG_T, "Moving in", self.id, world);
 
-                let household_id = match household_type_to_spawn {
-                    HouseholdTypeToSpawn::Family => {
-                        FamilyID::move_into(3, building_id, self.time, world).into()
-                    }
-                    HouseholdTypeToSpawn::GroceryShop => {
-                        GroceryShopID::move_into(building_id, self.time, world).into()
-                    }
-                    HouseholdTypeToSpawn::GrainFarm => {
-                        GrainFarmID::move_into(building_id, self.time, world).into()
-                    }
-                    HouseholdTypeToSpawn::CowFarm => {
-                        CowFarmID::move_into(building_id, self.time, world).into()
-                    }
-                    HouseholdTypeToSpawn::VegetableFarm => {
-                        VegetableFarmID::move_into(building_id, self.time, world).into()
-                    }
-                    HouseholdTypeToSpawn::Mill => {
-                        MillID::move_into(building_id, self.time, world).into()
-                    }
-                    HouseholdTypeToSpawn::Bakery => {
-                        BakeryID::move_into(building_id, self.time, world).into()
-                    }
-                    HouseholdTypeToSpawn::NeighboringTownTrade => {
-                        NeighboringTownTradeID::move_into(building_id, self.time, world).into()
-                    }
-                };
-
-                building_id.add_household(household_id, unit_idx, world);
+                let maybe_household: Option<(HouseholdID, Option<BuildingMaterial>)> =
+                    match household_type_to_spawn {
+                        HouseholdTypeToSpawn::Family => {
+                            self.conditions.record_housing_search(true);
+                            Some((
+                                FamilyID::move_into(
+                                    3,
+                                    building_id,
+                                    building_position,
+                                    self.time,
+                                    world,
+                                )
+                                .into(),
+                                None,
+                            ))
+                        }
+                        HouseholdTypeToSpawn::NeighboringTownTrade => Some((
+                            NeighboringTownTradeID::move_into(
+                                building_id,
+                                building_position,
+                                self.time,
+                                world,
+                            )
+                            .into(),
+                            None,
+                        )),
+                        HouseholdTypeToSpawn::Configurable(kind) => {
+                            if let Some(definition) = self.household_kinds.get(kind) {
+                                Some((
+                                    ConfigurableHouseholdID::move_into(
+                                        kind,
+                                        definition.clone(),
+                                        building_id,
+                                        building_position,
+                                        self.time,
+                                        world,
+                                    )
+                                    .into(),
+                                    definition.field_material,
+                                ))
+                            } else {
+                                warn(
+                                    LOG_T,
+                                    format!("Household kind {} was removed", kind),
+                                    self.id,
+                                    world,
+                                );
+                                None
+                            }
+                        }
+                    };
+
+                if let Some((household_id, field_material)) = maybe_household {
+                    building_id.add_household(household_id, unit_idx, field_material, world);
+                } else {
+                    building_id.release_unit(unit_idx, world);
+                }
 
                 ImmigrationManagerState::Idle
             }
-            ImmigrationManagerState::Idle => ImmigrationManagerState::Idle,
+            ImmigrationManagerState::Idle => {
+                // another building was faster
+                building_id.release_unit(unit_idx, world);
+                ImmigrationManagerState::Idle
+            }
         }
     }
+
+    pub fn request_household(&mut self, household_type: HouseholdTypeToSpawn, _: &mut World) {
+        if self.requested_household_types.len() < MAX_REQUESTED_HOUSEHOLDS {
+            self.requested_household_types.insert(0, household_type);
+        }
+    }
+
+    pub fn update_job_availability(&mut self, job_availability: f32, _: &mut World) {
+        self.conditions.job_availability = job_availability;
+    }
+
+    pub fn report_family_satisfaction(&mut self, satisfaction: f32, _: &mut World) {
+        self.conditions.family_satisfaction +=
+            SATISFACTION_SMOOTHING * (satisfaction - self.conditions.family_satisfaction);
+    }
+}
+
+/// Exponentially forgetting count of market searches for one resource
+#[derive(Copy, Clone, Default, Debug)]
+pub struct SearchTally {
+    pub searches: f32,
+    pub failures: f32,
+}
+
+impl SearchTally {
+    pub fn record(&mut self, found: bool) {
+        self.searches += 1.0;
+        if !found {
+            self.failures += 1.0;
+        }
+    }
+
+    fn forget(&mut self, factor: f32) {
+        self.searches *= factor;
+        self.failures *= factor;
+    }
+
+    pub fn success_share(&self) -> f32 {
+        if self.searches > 0.0 {
+            1.0 - self.failures / self.searches
+        } else {
+            1.0
+        }
+    }
+}
+
+/// A household that has to move out of a building that is about to be demolished
+#[derive(Copy, Clone, Debug)]
+pub struct Relocation {
+    household: HouseholdID,
+    unit_type: UnitType,
+    /// The style of the building it lived in, to be developed if no unit is free
+    building_style: BuildingStyle,
 }
 
 #[derive(Compact, Clone)]
@@ -228,21 +363,39 @@ pub struct DevelopmentManager {
     time: TimeID,
     plan_manager: CBPlanManagerID,
     building_to_develop: COption<BuildingStyle>,
+    household_kinds: CHashMap<Name, HouseholdKindDefinition>,
+    search_tallies: CDict<Resource, SearchTally>,
+    relocation_queue: CVec<Relocation>,
+    current_relocation: COption<Relocation>,
 }
 
+const DEVELOPMENT_PACE: Duration = Duration(10);
+/// How much of the search tallies is remembered from one review to the next
+const SEARCH_MEMORY: f32 = 0.9;
+/// How many (recently) failed searches it takes for a resource to attract new suppliers
+const MIN_UNMET_SEARCHES: f32 = 5.0;
+
 impl DevelopmentManager {
     pub fn spawn(
         id: DevelopmentManagerID,
         time: TimeID,
         plan_manager: CBPlanManagerID,
-        _world: &mut World,
+        world: &mut World,
     ) -> DevelopmentManager {
-        DevelopmentManager {
+        time.wake_up_in(DEVELOPMENT_PACE.into(), id.into(), world);
+
+        let manager = DevelopmentManager {
             id,
             time,
             plan_manager,
             building_to_develop: COption(None),
-        }
+            household_kinds: CHashMap::new(),
+            search_tallies: CDict::new(),
+            relocation_queue: CVec::new(),
+            current_relocation: COption(None),
+        };
+        manager.get_initial_config(world);
+        manager
     }
 
     pub fn try_develop(&mut self, building_style: BuildingStyle, world: &mut World) {
@@ -255,8 +408,6 @@ impl DevelopmentManager {
             );
             self.building_to_develop = COption(Some(building_style));
             VacantLotID::global_broadcast(world).suggest_lot(building_style, self.id, world);
-            self.time
-                .wake_up_in(IMMIGRATION_PACE.into(), self.id.into(), world);
         }
     }
 
@@ -288,11 +439,186 @@ impl DevelopmentManager {
             }
         }
     }
+
+    pub fn report_search_outcomes(&mut self, searches: &ResourceMap<SearchTally>, _: &mut World) {
+        for &Entry(resource, reported) in searches.iter() {
+            let mut tally = self
+                .search_tallies
+                .get(resource)
+                .cloned()
+                .unwrap_or_default();
+            tally.searches += reported.searches;
+            tally.failures += reported.failures;
+            self.search_tallies.insert(resource, tally);
+        }
+    }
+
+    pub fn relocate(
+        &mut self,
+        household: HouseholdID,
+        unit_type: UnitType,
+        building_style: BuildingStyle,
+        _: &mut World,
+    ) {
+        self.relocation_queue.push(Relocation {
+            household,
+            unit_type,
+            building_style,
+        });
+    }
+
+    /// The household was evicted, went bankrupt or emigrated before a new home was found for it
+    pub fn cancel_relocation(&mut self, household: HouseholdID, _: &mut World) {
+        self.relocation_queue
+            .retain(|relocation| relocation.household != household);
+        if let Some(relocation) = *self.current_relocation {
+            if relocation.household == household {
+                self.current_relocation = COption(None);
+            }
+        }
+    }
+
+    /// Offers can arrive after the household they were made for stopped looking
+    /// or already accepted another offer, in which case they are declined
+    pub fn on_relocation_unit_offer(
+        &mut self,
+        household: HouseholdID,
+        unit_type: UnitType,
+        building_id: BuildingID,
+        unit_idx: UnitIdx,
+        building_position: P2,
+        world: &mut World,
+    ) {
+        let is_for_current = match *self.current_relocation {
+            Some(relocation) => {
+                relocation.household == household && relocation.unit_type == unit_type
+            }
+            None => false,
+        };
+
+        if is_for_current {
+            self.current_relocation = COption(None);
+            debug(LOG_T, format!("Relocating {:?}", household), self.id, world);
+            household.relocate(building_id, unit_idx, building_position, world);
+        } else {
+            building_id.release_unit(unit_idx, world);
+        }
+    }
+
+    /// Looks for a new home for one displaced household at a time
+    fn continue_relocations(&mut self, world: &mut World) {
+        if let Some(relocation) = self.current_relocation.0.take() {
+            // no unit was offered until the next review, try again later and
+            // make sure a suitable building will eventually be available
+            self.relocation_queue.push(relocation);
+            self.try_develop(relocation.building_style, world);
+        }
+
+        if !self.relocation_queue.is_empty() {
+            let relocation = self.relocation_queue.remove(0);
+            BuildingID::global_broadcast(world).try_offer_unit_for_relocation(
+                relocation.household,
+                relocation.unit_type,
+                self.id,
+                world,
+            );
+            self.current_relocation = COption(Some(relocation));
+        }
+    }
+
+    /// Picks a household kind that offers `resource`, weighted by development share
+    fn kind_supplying(&self, resource: Resource, current_instant: Instant) -> Option<Name> {
+        let candidates = self
+            .household_kinds
+            .pairs()
+            .filter(|(_, definition)| {
+                definition
+                    .offers
+                    .iter()
+                    .any(|offer| !offer.is_internal && offer.deal.main_given() == resource)
+            })
+            .map(|(kind, definition)| (*kind, definition.development_share))
+            .collect::<Vec<_>>();
+
+        let total_share: f32 = candidates.iter().map(|&(_, share)| share).sum();
+
+        if total_share <= 0.0 {
+            return None;
+        }
+
+        let mut dot = seed((current_instant, resource)).gen_range(0.0, total_share);
+
+        for &(kind, share) in &candidates {
+            if dot < share {
+                return Some(kind);
+            }
+            dot -= share;
+        }
+
+        None
+    }
+
+    fn review_demand(&mut self, current_instant: Instant, world: &mut World) {
+        let job_availability = self
+            .search_tallies
+            .get(Resource::Money)
+            .map(SearchTally::success_share)
+            .unwrap_or(1.0);
+        ImmigrationManagerID::global_first(world).update_job_availability(job_availability, world);
+
+        let most_unmet = self
+            .search_tallies
+            .pairs()
+            .filter(|(_, tally)| tally.failures >= MIN_UNMET_SEARCHES)
+            .max_by_key(|(_, tally)| OrderedFloat(tally.failures))
+            .map(|(resource, _)| *resource);
+
+        if let Some(resource) = most_unmet {
+            if let Some(kind) = self.kind_supplying(resource, current_instant) {
+                debug(
+                    LOG_T,
+                    format!("Unmet demand for {}, requesting {}", resource, kind),
+                    self.id,
+                    world,
+                );
+                ImmigrationManagerID::global_first(world)
+                    .request_household(HouseholdTypeToSpawn::Configurable(kind), world);
+
+                // give the new supplier a chance before asking for more
+                if let Some(tally) = self.search_tallies.get_mut(resource) {
+                    tally.failures = 0.0;
+                }
+            }
+        }
+
+        let resources = self
+            .search_tallies
+            .pairs()
+            .map(|(resource, _)| *resource)
+            .collect::<Vec<_>>();
+
+        for resource in resources {
+            if let Some(tally) = self.search_tallies.get_mut(resource) {
+                tally.forget(SEARCH_MEMORY);
+            }
+        }
+    }
+}
+
+impl ConfigUser<HouseholdKindDefinition> for DevelopmentManager {
+    fn local_cache(&mut self) -> &mut CHashMap<Name, HouseholdKindDefinition> {
+        &mut self.household_kinds
+    }
 }
 
 impl Sleeper for DevelopmentManager {
-    fn wake(&mut self, _: Instant, _world: &mut World) {
+    fn wake(&mut self, current_instant: Instant, world: &mut World) {
+        // a suggested lot didn't arrive until the next review, give up on it
         self.building_to_develop = COption(None);
+        self.review_demand(current_instant, world);
+        self.continue_relocations(world);
+        self.time
+            .wake_up_in(DEVELOPMENT_PACE.into(), self.id.into(), world);
     }
 }
 
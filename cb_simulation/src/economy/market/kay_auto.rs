@@ -133,34 +133,118 @@ impl TypedID for MarketID {
 }
 
 impl MarketID {
-    pub fn spawn(world: &mut World) -> Self {
+    pub fn spawn(region: MarketRegion, world: &mut World) -> Self {
         let id = MarketID::from_raw(world.allocate_instance_id::<Market>());
         let swarm = world.local_broadcast::<Market>();
-        world.send(swarm, MSG_Market_spawn(id, ));
+        world.send(swarm, MSG_Market_spawn(id, region));
         id
     }
     
-    pub fn search(self, instant: Instant, location: RoughLocationID, resource: Resource, requester: EvaluationRequesterID, world: &mut World) {
-        world.send(self.as_raw(), MSG_Market_search(instant, location, resource, requester));
-    }
-    
-    pub fn register(self, resource: Resource, offer: OfferID, world: &mut World) {
-        world.send(self.as_raw(), MSG_Market_register(resource, offer));
+    pub fn register(self, resource: Resource, offer: OfferID, position: P2, world: &mut World) {
+        world.send(self.as_raw(), MSG_Market_register(resource, offer, position));
     }
     
     pub fn withdraw(self, resource: Resource, offer: OfferID, world: &mut World) {
         world.send(self.as_raw(), MSG_Market_withdraw(resource, offer));
     }
+    
+    pub fn search(self, instant: Instant, location: RoughLocationID, position: P2, resource: Resource, requester: EvaluationRequesterID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Market_search(instant, location, position, resource, requester));
+    }
+    
+    pub fn continue_search(self, instant: Instant, location: RoughLocationID, position: P2, resource: Resource, requester: EvaluationRequesterID, candidates: CVec < (OfferID, P2) >, n_neighbors_searched: u8, world: &mut World) {
+        world.send(self.as_raw(), MSG_Market_continue_search(instant, location, position, resource, requester, candidates, n_neighbors_searched));
+    }
 }
 
-#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
-struct MSG_Market_spawn(pub MarketID, );
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_Market_search(pub Instant, pub RoughLocationID, pub Resource, pub EvaluationRequesterID);
+struct MSG_Market_spawn(pub MarketID, pub MarketRegion);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_Market_register(pub Resource, pub OfferID);
+struct MSG_Market_register(pub Resource, pub OfferID, pub P2);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Market_withdraw(pub Resource, pub OfferID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Market_search(pub Instant, pub RoughLocationID, pub P2, pub Resource, pub EvaluationRequesterID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Market_continue_search(pub Instant, pub RoughLocationID, pub P2, pub Resource, pub EvaluationRequesterID, pub CVec < (OfferID, P2) >, pub u8);
+
+
+impl Actor for PendingSearch {
+    type ID = PendingSearchID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct PendingSearchID {
+    _raw_id: RawID
+}
+
+impl Copy for PendingSearchID {}
+impl Clone for PendingSearchID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for PendingSearchID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "PendingSearchID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for PendingSearchID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for PendingSearchID {
+    fn eq(&self, other: &PendingSearchID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for PendingSearchID {}
+
+impl TypedID for PendingSearchID {
+    type Target = PendingSearch;
+
+    fn from_raw(id: RawID) -> Self {
+        PendingSearchID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl PendingSearchID {
+    pub fn spawn(instant: Instant, location: RoughLocationID, resource: Resource, requester: EvaluationRequesterID, world: &mut World) -> Self {
+        let id = PendingSearchID::from_raw(world.allocate_instance_id::<PendingSearch>());
+        let swarm = world.local_broadcast::<PendingSearch>();
+        world.send(swarm, MSG_PendingSearch_spawn(id, instant, location, resource, requester));
+        id
+    }
+    
+    pub fn done(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_PendingSearch_done());
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PendingSearch_spawn(pub PendingSearchID, pub Instant, pub RoughLocationID, pub Resource, pub EvaluationRequesterID);
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PendingSearch_done();
+
+impl Into<PositionRequesterID> for PendingSearchID {
+    fn into(self) -> PositionRequesterID {
+        PositionRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<SleeperID> for PendingSearchID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
 
 
 impl Actor for TripCostEstimator {
@@ -246,26 +330,45 @@ pub fn auto_setup(system: &mut ActorSystem) {
     EvaluationRequesterID::register_trait(system);
     
     system.add_spawner::<Market, _, _>(
-        |&MSG_Market_spawn(id, ), world| {
-            Market::spawn(id, world)
+        |&MSG_Market_spawn(id, region), world| {
+            Market::spawn(id, region, world)
         }, false
     );
     
     system.add_handler::<Market, _, _>(
-        |&MSG_Market_search(instant, location, resource, requester), instance, world| {
-            instance.search(instant, location, resource, requester, world); Fate::Live
+        |&MSG_Market_register(resource, offer, position), instance, world| {
+            instance.register(resource, offer, position, world); Fate::Live
         }, false
     );
     
     system.add_handler::<Market, _, _>(
-        |&MSG_Market_register(resource, offer), instance, world| {
-            instance.register(resource, offer, world); Fate::Live
+        |&MSG_Market_withdraw(resource, offer), instance, world| {
+            instance.withdraw(resource, offer, world); Fate::Live
         }, false
     );
     
     system.add_handler::<Market, _, _>(
-        |&MSG_Market_withdraw(resource, offer), instance, world| {
-            instance.withdraw(resource, offer, world); Fate::Live
+        |&MSG_Market_search(instant, location, position, resource, requester), instance, world| {
+            instance.search(instant, location, position, resource, requester, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Market, _, _>(
+        |&MSG_Market_continue_search(instant, location, position, resource, requester, ref candidates, n_neighbors_searched), instance, world| {
+            instance.continue_search(instant, location, position, resource, requester, candidates, n_neighbors_searched, world); Fate::Live
+        }, false
+    );
+    PositionRequesterID::register_implementor::<PendingSearch>(system);
+    SleeperID::register_implementor::<PendingSearch>(system);
+    system.add_spawner::<PendingSearch, _, _>(
+        |&MSG_PendingSearch_spawn(id, instant, location, resource, requester), world| {
+            PendingSearch::spawn(id, instant, location, resource, requester, world)
+        }, false
+    );
+    
+    system.add_handler::<PendingSearch, _, _>(
+        |&MSG_PendingSearch_done(), instance, world| {
+            instance.done(world)
         }, false
     );
     LocationRequesterID::register_implementor::<TripCostEstimator>(system);
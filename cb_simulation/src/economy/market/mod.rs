@@ -1,12 +1,23 @@
 use kay::{ActorSystem, Fate, World, Actor};
-use compact::{CVec, CDict};
+use compact::CVec;
 use super::resources::{Inventory, Entry, Resource, ResourceAmount};
 use super::households::OfferID;
-use cb_time::units::{TimeOfDayRange, Duration, Instant};
-use transport::pathfinding::{RoughLocationID, LocationRequesterID};
+use cb_time::units::{OpeningHours, Duration, Instant};
+use cb_time::actors::{Sleeper, SleeperID, TimeID};
+use transport::pathfinding::{
+    RoughLocationID, LocationRequesterID, PositionRequester, PositionRequesterID,
+};
 use cb_util::log::warn;
+use descartes::P2;
+use ordered_float::OrderedFloat;
+use std::sync::RwLock;
 const LOG_T: &str = "Market";
 
+pub mod spatial_index;
+use self::spatial_index::OfferIndex;
+pub mod regions;
+use self::regions::{MarketRegion, MAX_NEIGHBORING_REGIONS};
+
 #[derive(Compact, Clone, Debug, Serialize, Deserialize)]
 pub struct Deal {
     pub duration: Duration,
@@ -38,52 +49,238 @@ pub trait EvaluationRequester {
     fn on_result(&mut self, result: &EvaluatedSearchResult, world: &mut World);
 }
 
+/// How many of the nearest offers are evaluated for each search
+const N_NEAREST_OFFERS: usize = 10;
+
+/// How long a search waits for its rough location to be resolved before giving up,
+/// for example because the building or lot behind it was destroyed in the meantime
+const PENDING_SEARCH_TIMEOUT: Duration = Duration(60);
+
+lazy_static! {
+    /// All partitions of the market, indexed by their region
+    static ref PARTITIONS: RwLock<Vec<MarketID>> = RwLock::new(Vec::new());
+}
+
+/// The partition of the market responsible for offers at `position`
+/// and for searches starting there
+pub fn partition_for(position: P2) -> MarketID {
+    partition_of(MarketRegion::containing(position))
+}
+
+fn partition_of(region: MarketRegion) -> MarketID {
+    PARTITIONS.read().unwrap()[region.as_idx()]
+}
+
+/// Starts a search at `location`, directly in the responsible partition if the
+/// position of the location is already known, otherwise once it is resolved
+pub fn search(
+    instant: Instant,
+    location: RoughLocationID,
+    known_position: Option<P2>,
+    resource: Resource,
+    requester: EvaluationRequesterID,
+    world: &mut World,
+) {
+    if let Some(position) = known_position {
+        partition_for(position).search(instant, location, position, resource, requester, world);
+    } else {
+        PendingSearchID::spawn(instant, location, resource, requester, world);
+    }
+}
+
+/// One partition of the market, responsible for all offers in its region.
+/// Offers have to be registered and withdrawn at the partition for their position.
 #[derive(Compact, Clone)]
 pub struct Market {
     id: MarketID,
-    offers_by_resource: CDict<Resource, CVec<OfferID>>,
+    region: MarketRegion,
+    offer_index: OfferIndex,
 }
 
 impl Market {
-    pub fn spawn(id: MarketID, _: &mut World) -> Market {
+    pub fn spawn(id: MarketID, region: MarketRegion, _: &mut World) -> Market {
         Market {
             id,
-            offers_by_resource: CDict::new(),
+            region,
+            offer_index: OfferIndex::new(),
         }
     }
 
+    pub fn register(&mut self, resource: Resource, offer: OfferID, position: P2, _: &mut World) {
+        self.offer_index.insert(resource, offer, position);
+    }
+
+    pub fn withdraw(&mut self, resource: Resource, offer: OfferID, world: &mut World) {
+        self.offer_index.remove(resource, offer);
+        offer.household.withdrawal_confirmed(offer.idx, world);
+    }
+
+    /// Has to be sent to the partition for `position`
     pub fn search(
         &mut self,
         instant: Instant,
         location: RoughLocationID,
+        position: P2,
+        resource: Resource,
+        requester: EvaluationRequesterID,
+        world: &mut World,
+    ) {
+        self.continue_search(
+            instant,
+            location,
+            position,
+            resource,
+            requester,
+            &CVec::new(),
+            0,
+            world,
+        );
+    }
+
+    /// Merges the nearest offers in this partition with the `candidates` found so far.
+    /// As long as the next closest region could still contain nearer offers than the
+    /// candidates, the search continues there, otherwise the candidates are evaluated.
+    pub fn continue_search(
+        &mut self,
+        instant: Instant,
+        location: RoughLocationID,
+        position: P2,
         resource: Resource,
         requester: EvaluationRequesterID,
+        candidates: &CVec<(OfferID, P2)>,
+        n_neighbors_searched: u8,
         world: &mut World,
     ) {
-        let n_to_expect = if let Some(offers) = self.offers_by_resource.get(resource) {
-            for offer in offers.iter() {
+        let mut nearest_offers = self
+            .offer_index
+            .nearest(resource, position, N_NEAREST_OFFERS);
+        nearest_offers.extend(candidates.iter().cloned());
+        nearest_offers
+            .sort_by_key(|&(_, offer_position)| OrderedFloat((offer_position - position).norm()));
+        nearest_offers.truncate(N_NEAREST_OFFERS);
+
+        let maybe_next_neighbor = if (n_neighbors_searched as usize) < MAX_NEIGHBORING_REGIONS {
+            MarketRegion::others_by_distance_to(position)
+                .get(n_neighbors_searched as usize)
+                .cloned()
+        } else {
+            None
+        };
+
+        let next_neighbor_to_search = maybe_next_neighbor.and_then(|(region, distance)| {
+            let could_have_nearer_offers = nearest_offers.len() < N_NEAREST_OFFERS
+                || nearest_offers
+                    .last()
+                    .map(|&(_, offer_position)| (offer_position - position).norm() > distance)
+                    .unwrap_or(true);
+
+            if could_have_nearer_offers {
+                Some(region)
+            } else {
+                None
+            }
+        });
+
+        if let Some(region) = next_neighbor_to_search {
+            partition_of(region).continue_search(
+                instant,
+                location,
+                position,
+                resource,
+                requester,
+                nearest_offers.into(),
+                n_neighbors_searched + 1,
+                world,
+            );
+        } else {
+            for &(offer, _) in &nearest_offers {
                 offer
                     .household
                     .evaluate(offer.idx, instant, location, requester, world);
             }
 
-            offers.len()
-        } else {
-            0
-        };
+            requester.expect_n_results(resource, nearest_offers.len() as u32, world);
+        }
+    }
+}
+
+/// A search whose rough location still needs to be resolved to a position,
+/// to find out which partition of the market to start it in
+#[derive(Compact, Clone)]
+pub struct PendingSearch {
+    id: PendingSearchID,
+    instant: Instant,
+    location: RoughLocationID,
+    resource: Resource,
+    requester: EvaluationRequesterID,
+    started: bool,
+}
+
+impl PendingSearch {
+    pub fn spawn(
+        id: PendingSearchID,
+        instant: Instant,
+        location: RoughLocationID,
+        resource: Resource,
+        requester: EvaluationRequesterID,
+        world: &mut World,
+    ) -> PendingSearch {
+        location.resolve_as_position(id.into(), location, world);
+        TimeID::local_first(world).wake_up_in(PENDING_SEARCH_TIMEOUT.into(), id.into(), world);
+
+        PendingSearch {
+            id,
+            instant,
+            location,
+            resource,
+            requester,
+            started: false,
+        }
+    }
 
-        requester.expect_n_results(resource, n_to_expect as u32, world);
+    pub fn done(&mut self, _: &mut World) -> Fate {
+        Fate::Die
     }
+}
 
-    pub fn register(&mut self, resource: Resource, offer: OfferID, _: &mut World) {
-        self.offers_by_resource.push_at(resource, offer);
+impl PositionRequester for PendingSearch {
+    fn position_resolved(
+        &mut self,
+        _rough_location: RoughLocationID,
+        position: P2,
+        world: &mut World,
+    ) {
+        if !self.started {
+            self.started = true;
+            partition_for(position).search(
+                self.instant,
+                self.location,
+                position,
+                self.resource,
+                self.requester,
+                world,
+            );
+            self.id.done(world);
+        }
     }
+}
 
-    pub fn withdraw(&mut self, resource: Resource, offer: OfferID, world: &mut World) {
-        if let Some(offers) = self.offers_by_resource.get_mut(resource) {
-            offers.retain(|o| *o != offer);
+impl Sleeper for PendingSearch {
+    fn wake(&mut self, _current_instant: Instant, world: &mut World) {
+        if !self.started {
+            self.started = true;
+            warn(
+                LOG_T,
+                format!(
+                    "Couldn't resolve {:?} to search for {}",
+                    self.location, self.resource
+                ),
+                self.id,
+                world,
+            );
+            self.requester.expect_n_results(self.resource, 0, world);
+            self.id.done(world);
         }
-        offer.household.withdrawal_confirmed(offer.idx, world);
     }
 }
 
@@ -91,7 +288,11 @@ impl Market {
 pub struct EvaluatedDeal {
     pub offer: OfferID,
     pub deal: Deal,
-    pub opening_hours: TimeOfDayRange,
+    pub opening_hours: OpeningHours,
+    /// Estimated travel time to the offer, already included in the deal's duration
+    pub travel_time: Duration,
+    /// Expected time spent queuing at the offer, already included in the deal's duration
+    pub waiting_time: Duration,
 }
 
 #[derive(Compact, Clone)]
@@ -204,6 +405,7 @@ impl DistanceRequester for TripCostEstimator {
                         let estimated_travel_time = Duration((distance / ASSUMED_AVG_SPEED) as u32);
                         let mut new_deal = evaluated_deal.clone();
                         new_deal.deal.duration += estimated_travel_time;
+                        new_deal.travel_time += estimated_travel_time;
                         new_deal.opening_hours =
                             new_deal.opening_hours.earlier_by(estimated_travel_time);
                         // TODO: adjust resources to incorporate travel costs
@@ -235,11 +437,14 @@ impl DistanceRequester for TripCostEstimator {
 pub fn setup(system: &mut ActorSystem) {
     system.register::<Market>();
     system.register::<TripCostEstimator>();
+    system.register::<PendingSearch>();
     kay_auto::auto_setup(system);
 }
 
 pub fn spawn(world: &mut World) {
-    MarketID::spawn(world);
+    *PARTITIONS.write().unwrap() = MarketRegion::all()
+        .map(|region| MarketID::spawn(region, world))
+        .collect();
 }
 
 mod kay_auto;
@@ -0,0 +1,89 @@
+use descartes::P2;
+
+/// Side length of the square region each market partition is responsible for
+const REGION_SIZE: f32 = 2500.0;
+/// Regions per side of the square grid of regions centered on the origin,
+/// positions outside of it belong to the closest region on its border
+const REGIONS_PER_SIDE: i32 = 4;
+pub const N_REGIONS: usize = (REGIONS_PER_SIDE * REGIONS_PER_SIDE) as usize;
+/// How many of the closest other regions a search can extend into
+pub const MAX_NEIGHBORING_REGIONS: usize = 8;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct MarketRegion(u16);
+
+impl MarketRegion {
+    pub fn all() -> impl Iterator<Item = MarketRegion> {
+        (0..N_REGIONS).map(|idx| MarketRegion(idx as u16))
+    }
+
+    pub fn containing(position: P2) -> MarketRegion {
+        let to_region_coordinate = |coordinate: f32| {
+            ((coordinate / REGION_SIZE).floor() as i32 + REGIONS_PER_SIDE / 2)
+                .max(0)
+                .min(REGIONS_PER_SIDE - 1)
+        };
+
+        MarketRegion::from_coordinates(
+            to_region_coordinate(position.x),
+            to_region_coordinate(position.y),
+        )
+    }
+
+    fn from_coordinates(x: i32, y: i32) -> MarketRegion {
+        MarketRegion((y * REGIONS_PER_SIDE + x) as u16)
+    }
+
+    fn coordinates(self) -> (i32, i32) {
+        (
+            i32::from(self.0) % REGIONS_PER_SIDE,
+            i32::from(self.0) / REGIONS_PER_SIDE,
+        )
+    }
+
+    pub fn as_idx(self) -> usize {
+        self.0 as usize
+    }
+
+    /// Distance from `position` to the closest point of this region,
+    /// zero if the position belongs to it
+    pub fn distance_to(self, position: P2) -> f32 {
+        let distance_along = |region_coordinate: i32, coordinate: f32| {
+            let min = (region_coordinate - REGIONS_PER_SIDE / 2) as f32 * REGION_SIZE;
+            let max = min + REGION_SIZE;
+
+            // regions on the border of the grid extend to infinity
+            let below = if region_coordinate == 0 {
+                0.0
+            } else {
+                min - coordinate
+            };
+            let above = if region_coordinate == REGIONS_PER_SIDE - 1 {
+                0.0
+            } else {
+                coordinate - max
+            };
+
+            below.max(above).max(0.0)
+        };
+
+        let (x, y) = self.coordinates();
+        distance_along(x, position.x).hypot(distance_along(y, position.y))
+    }
+
+    /// All regions other than the one containing `position`,
+    /// closest to `position` first, together with their distance to it
+    pub fn others_by_distance_to(position: P2) -> Vec<(MarketRegion, f32)> {
+        let own = MarketRegion::containing(position);
+        let mut others = MarketRegion::all()
+            .filter(|&other| other != own)
+            .map(|other| (other, other.distance_to(position)))
+            .collect::<Vec<_>>();
+
+        others.sort_by(|&(_, distance_a), &(_, distance_b)| {
+            distance_a.partial_cmp(&distance_b).unwrap()
+        });
+
+        others
+    }
+}
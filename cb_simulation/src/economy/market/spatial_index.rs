@@ -0,0 +1,121 @@
+use compact::{CVec, CHashMap};
+use descartes::P2;
+use ordered_float::OrderedFloat;
+use economy::resources::Resource;
+use economy::households::OfferID;
+
+/// Side length of the square grid cells offers are bucketed into
+const CELL_SIZE: f32 = 500.0;
+/// How far (in rings of cells around the searcher) a search widens before giving up
+const MAX_SEARCH_RINGS: i32 = 40;
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct GridCell(i32, i32);
+
+impl GridCell {
+    fn containing(position: P2) -> GridCell {
+        GridCell(
+            (position.x / CELL_SIZE).floor() as i32,
+            (position.y / CELL_SIZE).floor() as i32,
+        )
+    }
+
+    fn ring(self, radius: i32) -> Vec<GridCell> {
+        if radius == 0 {
+            return vec![self];
+        }
+
+        let GridCell(x, y) = self;
+        let mut cells = Vec::with_capacity(8 * radius as usize);
+
+        for i in -radius..radius {
+            cells.push(GridCell(x + i, y - radius));
+            cells.push(GridCell(x + radius, y + i));
+            cells.push(GridCell(x - i, y + radius));
+            cells.push(GridCell(x - radius, y - i));
+        }
+
+        cells
+    }
+}
+
+/// Offers bucketed by the resource they provide and a grid cell of their location
+#[derive(Compact, Clone)]
+pub struct OfferIndex {
+    cells: CHashMap<(Resource, GridCell), CVec<(OfferID, P2)>>,
+    positions: CHashMap<OfferID, P2>,
+    /// How many offers there are for each resource, so searches can stop once all are found
+    counts: CHashMap<Resource, u32>,
+}
+
+impl OfferIndex {
+    pub fn new() -> Self {
+        OfferIndex {
+            cells: CHashMap::new(),
+            positions: CHashMap::new(),
+            counts: CHashMap::new(),
+        }
+    }
+
+    pub fn insert(&mut self, resource: Resource, offer: OfferID, position: P2) {
+        self.remove(resource, offer);
+        self.cells.push_at(
+            (resource, GridCell::containing(position)),
+            (offer, position),
+        );
+        self.positions.insert(offer, position);
+        let count = self.counts.get(resource).cloned().unwrap_or(0);
+        self.counts.insert(resource, count + 1);
+    }
+
+    pub fn remove(&mut self, resource: Resource, offer: OfferID) {
+        if let Some(position) = self.positions.remove(offer) {
+            if let Some(cell_offers) = self
+                .cells
+                .get_mut((resource, GridCell::containing(position)))
+            {
+                cell_offers.retain(|&(other_offer, _)| other_offer != offer);
+            }
+
+            if let Some(count) = self.counts.get_mut(resource) {
+                *count -= 1;
+            }
+        }
+    }
+
+    /// The `k` offers for `resource` nearest to `position`, searching in widening
+    /// rings of cells until at least `k` are found, all offers for `resource` are found
+    /// or the search radius is exhausted
+    pub fn nearest(&self, resource: Resource, position: P2, k: usize) -> Vec<(OfferID, P2)> {
+        let n_offers = self.counts.get(resource).cloned().unwrap_or(0) as usize;
+        let center = GridCell::containing(position);
+        let mut candidates = Vec::new();
+        let mut enough_at_ring = None;
+
+        for radius in 0..=MAX_SEARCH_RINGS {
+            if candidates.len() >= n_offers {
+                break;
+            }
+
+            for cell in center.ring(radius) {
+                if let Some(cell_offers) = self.cells.get((resource, cell)) {
+                    candidates.extend(cell_offers.iter().cloned());
+                }
+            }
+
+            match enough_at_ring {
+                // offers in the next ring can still be closer than
+                // far away ones in the corners of the previous rings
+                Some(ring) if radius > ring => break,
+                None if candidates.len() >= k => enough_at_ring = Some(radius),
+                _ => {}
+            }
+        }
+
+        candidates
+            .sort_by_key(|&(_, offer_position)| OrderedFloat((offer_position - position).norm()));
+
+        candidates.truncate(k);
+        candidates
+    }
+}
@@ -7,15 +7,18 @@ pub mod resources;
 pub mod market;
 pub mod households;
 pub mod immigration_and_development;
+pub mod city_budget;
 
 pub fn setup(system: &mut ActorSystem) {
     market::setup(system);
     households::setup(system);
     immigration_and_development::setup(system);
+    city_budget::setup(system);
 }
 
 pub fn spawn(world: &mut World, time: TimeID, plan_manager: CBPlanManagerID) {
     market::spawn(world);
-    households::spawn(world);
+    households::spawn(world, time);
     immigration_and_development::spawn(world, time, plan_manager);
+    city_budget::spawn(world, time);
 }
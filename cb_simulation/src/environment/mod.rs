@@ -1,6 +1,8 @@
 use kay::ActorSystem;
 pub mod vegetation;
+pub mod weather;
 
 pub fn setup(system: &mut ActorSystem) {
     vegetation::setup(system);
+    weather::setup(system);
 }
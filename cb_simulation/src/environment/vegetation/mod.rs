@@ -1,4 +1,5 @@
 use kay::{World, Fate, ActorSystem, TypedID};
+use cb_time::units::Instant;
 use compact::CVec;
 use descartes::{P2, RoughEq, AreaError};
 use planning::{CBPlanManagerID, CBConstructionID, CBPrototypeKind, CBGestureIntent};
@@ -93,7 +94,12 @@ impl Constructable<CBPrototypeKind> for Plant {
         }
     }
 
-    fn destruct(&mut self, report_to: CBConstructionID, world: &mut World) -> Fate {
+    fn destruct(
+        &mut self,
+        report_to: CBConstructionID,
+        _current_instant: Instant,
+        world: &mut World,
+    ) -> Fate {
         report_to.action_done(self.id.into(), world);
         VegetationUIID::global_broadcast(world).on_plant_destroyed(self.id, world);
         Fate::Die
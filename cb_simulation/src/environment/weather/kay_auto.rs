@@ -0,0 +1,164 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct WeatherListenerID {
+    _raw_id: RawID
+}
+
+impl Copy for WeatherListenerID {}
+impl Clone for WeatherListenerID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for WeatherListenerID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "WeatherListenerID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for WeatherListenerID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for WeatherListenerID {
+    fn eq(&self, other: &WeatherListenerID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for WeatherListenerID {}
+
+pub struct WeatherListenerRepresentative;
+
+impl ActorOrActorTrait for WeatherListenerRepresentative {
+    type ID = WeatherListenerID;
+}
+
+impl TypedID for WeatherListenerID {
+    type Target = WeatherListenerRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        WeatherListenerID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + WeatherListener> TraitIDFrom<Act> for WeatherListenerID {}
+
+impl WeatherListenerID {
+    pub fn on_weather(self, day: DayOfYear, today: WeatherConditions, world: &mut World) {
+        world.send(self.as_raw(), MSG_WeatherListener_on_weather(day, today));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<WeatherListenerRepresentative>();
+        system.register_trait_message::<MSG_WeatherListener_on_weather>();
+    }
+
+    pub fn register_implementor<Act: Actor + WeatherListener>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, WeatherListenerRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_WeatherListener_on_weather(day, today), instance, world| {
+                instance.on_weather(day, today, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_WeatherListener_on_weather(pub DayOfYear, pub WeatherConditions);
+
+
+impl Actor for Weather {
+    type ID = WeatherID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct WeatherID {
+    _raw_id: RawID
+}
+
+impl Copy for WeatherID {}
+impl Clone for WeatherID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for WeatherID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "WeatherID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for WeatherID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for WeatherID {
+    fn eq(&self, other: &WeatherID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for WeatherID {}
+
+impl TypedID for WeatherID {
+    type Target = Weather;
+
+    fn from_raw(id: RawID) -> Self {
+        WeatherID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl WeatherID {
+    pub fn spawn(time: TimeID, world: &mut World) -> Self {
+        let id = WeatherID::from_raw(world.allocate_instance_id::<Weather>());
+        let swarm = world.local_broadcast::<Weather>();
+        world.send(swarm, MSG_Weather_spawn(id, time));
+        id
+    }
+    
+    pub fn get_info(self, requester: WeatherListenerID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Weather_get_info(requester));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Weather_spawn(pub WeatherID, pub TimeID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Weather_get_info(pub WeatherListenerID);
+
+impl Into<SleeperID> for WeatherID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    WeatherListenerID::register_trait(system);
+    
+    SleeperID::register_implementor::<Weather>(system);
+    system.add_spawner::<Weather, _, _>(
+        |&MSG_Weather_spawn(id, time), world| {
+            Weather::spawn(id, time, world)
+        }, false
+    );
+    
+    system.add_handler::<Weather, _, _>(
+        |&MSG_Weather_get_info(requester), instance, world| {
+            instance.get_info(requester, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
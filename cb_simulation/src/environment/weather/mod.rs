@@ -0,0 +1,147 @@
+use kay::{ActorSystem, World};
+use cb_time::actors::{TimeID, Sleeper, SleeperID, ScheduleHandle};
+use cb_time::units::{Instant, DayOfYear, TimeOfDay, Schedule};
+use cb_util::random::{seed, Rng};
+use std::f32::consts::PI;
+
+/// Mean daily temperature over the year and how far it swings up in summer
+/// and down in winter, in °C
+const MEAN_TEMPERATURE: f32 = 10.0;
+const TEMPERATURE_AMPLITUDE: f32 = 10.0;
+const WARMEST_FRACTION_OF_YEAR: f32 = 0.35;
+/// How much of yesterday's deviation from the normal temperature carries over to today
+const TEMPERATURE_PERSISTENCE: f32 = 0.7;
+const MAX_TEMPERATURE_CHANGE: f32 = 4.0;
+
+const RAIN_CHANCE: f32 = 0.35;
+const MAX_DAILY_PRECIPITATION: f32 = 12.0;
+const NORMAL_PRECIPITATION: f32 = RAIN_CHANCE * MAX_DAILY_PRECIPITATION / 2.0;
+/// How much of the difference between current and rain-fed soil moisture is evened out per day
+const SOIL_MOISTURE_ADAPTATION: f32 = 0.25;
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct WeatherConditions {
+    /// Mean temperature of the day, in °C
+    pub temperature: f32,
+    /// Rainfall of the day, in mm
+    pub precipitation: f32,
+    /// Water available to plants, 1.0 being normal
+    pub soil_moisture: f32,
+}
+
+impl WeatherConditions {
+    /// The weather of `day` in an average year
+    pub fn normal(day: DayOfYear) -> WeatherConditions {
+        WeatherConditions {
+            temperature: MEAN_TEMPERATURE
+                + TEMPERATURE_AMPLITUDE
+                    * (2.0 * PI * (day.fraction_of_year() - WARMEST_FRACTION_OF_YEAR)).cos(),
+            precipitation: NORMAL_PRECIPITATION,
+            soil_moisture: 1.0,
+        }
+    }
+
+    /// How well plants grow on such a day, 1.0 being a warm day with enough water.
+    /// Cold slows growth down, as do droughts and waterlogged soil.
+    pub fn growing_conditions(&self) -> f32 {
+        let warmth = ((self.temperature - 5.0) / 15.0).max(0.0).min(1.2);
+        let water = self.soil_moisture.min(1.0) - 0.5 * (self.soil_moisture - 1.5).max(0.0);
+        warmth * water.max(0.0)
+    }
+}
+
+pub trait WeatherListener {
+    fn on_weather(&mut self, day: DayOfYear, today: WeatherConditions, world: &mut World);
+}
+
+#[derive(Compact, Clone)]
+pub struct Weather {
+    id: WeatherID,
+    day: DayOfYear,
+    today: WeatherConditions,
+    daily_update: ScheduleHandle,
+}
+
+impl Weather {
+    pub fn spawn(id: WeatherID, time: TimeID, world: &mut World) -> Weather {
+        let daily_update = ScheduleHandle::new(id, 0);
+        time.schedule(
+            daily_update,
+            Schedule::Daily(TimeOfDay::new(0, 0)),
+            id.into(),
+            world,
+        );
+
+        let day = DayOfYear::from(Instant::new(0));
+
+        Weather {
+            id,
+            day,
+            today: WeatherConditions::normal(day),
+            daily_update,
+        }
+    }
+
+    pub fn get_info(&mut self, requester: WeatherListenerID, world: &mut World) {
+        requester.on_weather(self.day, self.today, world);
+    }
+
+    /// Today's weather depends on the season, yesterday's weather and chance
+    fn weather_following(&self, day: DayOfYear, instant: Instant) -> WeatherConditions {
+        let mut rng = seed(instant.days_since_start());
+        let normal = WeatherConditions::normal(day);
+        let yesterdays_anomaly =
+            self.today.temperature - WeatherConditions::normal(self.day).temperature;
+
+        let precipitation = if rng.gen_range(0.0, 1.0) < RAIN_CHANCE {
+            rng.gen_range(0.0, MAX_DAILY_PRECIPITATION)
+        } else {
+            0.0
+        };
+
+        WeatherConditions {
+            temperature: normal.temperature
+                + TEMPERATURE_PERSISTENCE * yesterdays_anomaly
+                + rng.gen_range(-MAX_TEMPERATURE_CHANGE, MAX_TEMPERATURE_CHANGE),
+            precipitation,
+            soil_moisture: self.today.soil_moisture
+                + SOIL_MOISTURE_ADAPTATION
+                    * (precipitation / NORMAL_PRECIPITATION - self.today.soil_moisture),
+        }
+    }
+}
+
+impl Sleeper for Weather {
+    fn wake(&mut self, current_instant: Instant, world: &mut World) {
+        let day = DayOfYear::from(current_instant);
+
+        if day != self.day {
+            self.today = self.weather_following(day, current_instant);
+            self.day = day;
+            WeatherListenerID::global_broadcast(world).on_weather(day, self.today, world);
+        }
+    }
+
+    fn wake_scheduled(
+        &mut self,
+        handle: ScheduleHandle,
+        current_instant: Instant,
+        world: &mut World,
+    ) {
+        if handle == self.daily_update {
+            self.wake(current_instant, world);
+        }
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<Weather>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World, time: TimeID) {
+    WeatherID::spawn(time, world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
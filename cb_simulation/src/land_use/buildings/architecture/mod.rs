@@ -1,4 +1,4 @@
-use kay::{ActorSystem, World, TypedID};
+use kay::{ActorSystem, World};
 use compact::{COption, CHashMap};
 use descartes::{N, P2, V2, WithUniqueOrthogonal, LinePath, ClosedLinePath, PrimitiveArea, Area};
 use cb_util::random::{Rng, seed};
@@ -201,9 +201,9 @@ pub fn footprint_area(lot: &Lot, building_style: BuildingStyle, extra_padding: N
 pub fn build_building(
     lot: &Lot,
     building_style: BuildingStyle,
+    field_material: Option<BuildingMaterial>,
     architecture_rules: &CHashMap<Name, ArchitectureRule>,
-    household_ids: &[::economy::households::HouseholdID],
-    world: &mut World,
+    _world: &mut World,
 ) -> Result<BuildingGeometry, String> {
     // TODO keep original building if lot changes
     let mut rng = seed(lot.original_lot_id);
@@ -294,24 +294,7 @@ pub fn build_building(
             }
         }
         BuildingStyle::Field => {
-            use ::economy::households::household_kinds::*;
-
-            let material = if let Some(farm) = household_ids.get(0) {
-                let farm_type_id = farm.as_raw().type_id;
-                if farm_type_id == grain_farm::GrainFarmID::local_first(world).as_raw().type_id {
-                    BuildingMaterial::FieldWheat
-                } else if farm_type_id
-                    == vegetable_farm::VegetableFarmID::local_first(world)
-                        .as_raw()
-                        .type_id
-                {
-                    BuildingMaterial::FieldPlant
-                } else {
-                    BuildingMaterial::FieldMeadow
-                }
-            } else {
-                BuildingMaterial::FieldRows
-            };
+            let material = field_material.unwrap_or(BuildingMaterial::FieldRows);
 
             let lot_surface = FlatSurface::from_primitive_area(lot.area.primitives[0].clone(), 0.0);
             let (_, shrunk_lot_surface) = lot_surface.extrude(0.0, 2.0).unwrap();
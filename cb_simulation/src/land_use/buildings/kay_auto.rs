@@ -55,10 +55,10 @@ impl TypedID for BuildingID {
 }
 
 impl BuildingID {
-    pub fn spawn(style: BuildingStyle, lot: Lot, world: &mut World) -> Self {
+    pub fn spawn(style: BuildingStyle, lot: Lot, prototype_id: PrototypeID, world: &mut World) -> Self {
         let id = BuildingID::from_raw(world.allocate_instance_id::<Building>());
         let swarm = world.local_broadcast::<Building>();
-        world.send(swarm, MSG_Building_spawn(id, style, lot));
+        world.send(swarm, MSG_Building_spawn(id, style, lot, prototype_id));
         id
     }
     
@@ -66,8 +66,16 @@ impl BuildingID {
         world.send(self.as_raw(), MSG_Building_try_offer_unit(required_unit_type, requester));
     }
     
-    pub fn add_household(self, household: HouseholdID, unit: UnitIdx, world: &mut World) {
-        world.send(self.as_raw(), MSG_Building_add_household(household, unit));
+    pub fn try_offer_unit_for_relocation(self, household: HouseholdID, required_unit_type: UnitType, requester: DevelopmentManagerID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Building_try_offer_unit_for_relocation(household, required_unit_type, requester));
+    }
+    
+    pub fn release_unit(self, unit: UnitIdx, world: &mut World) {
+        world.send(self.as_raw(), MSG_Building_release_unit(unit));
+    }
+    
+    pub fn add_household(self, household: HouseholdID, unit: UnitIdx, field_material: Option < BuildingMaterial >, world: &mut World) {
+        world.send(self.as_raw(), MSG_Building_add_household(household, unit, field_material));
     }
     
     pub fn remove_household(self, household: HouseholdID, world: &mut World) {
@@ -82,17 +90,33 @@ impl BuildingID {
         world.send(self.as_raw(), MSG_Building_get_ui_info(requester));
     }
     
+    pub fn report_if_demolished(self, project_id: ProjectID, destroyed_prototypes: CVec < PrototypeID >, requester: LandUseUIID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Building_report_if_demolished(project_id, destroyed_prototypes, requester));
+    }
+    
+    pub fn report_satisfaction(self, household: HouseholdID, satisfaction: f32, world: &mut World) {
+        world.send(self.as_raw(), MSG_Building_report_satisfaction(household, satisfaction));
+    }
+    
+    pub fn levy_property_tax(self, rate_per_square_meter: ResourceAmount, budget: CityBudgetID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Building_levy_property_tax(rate_per_square_meter, budget));
+    }
+    
     pub fn reconnect(self, new_location: PreciseLocation, new_connection_point: P2, world: &mut World) {
         world.send(self.as_raw(), MSG_Building_reconnect(new_location, new_connection_point));
     }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_Building_spawn(pub BuildingID, pub BuildingStyle, pub Lot);
+struct MSG_Building_spawn(pub BuildingID, pub BuildingStyle, pub Lot, pub PrototypeID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Building_try_offer_unit(pub UnitType, pub ImmigrationManagerID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_Building_add_household(pub HouseholdID, pub UnitIdx);
+struct MSG_Building_try_offer_unit_for_relocation(pub HouseholdID, pub UnitType, pub DevelopmentManagerID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Building_release_unit(pub UnitIdx);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Building_add_household(pub HouseholdID, pub UnitIdx, pub Option < BuildingMaterial >);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Building_remove_household(pub HouseholdID);
 #[derive(Copy, Clone)] #[allow(non_camel_case_types)]
@@ -100,6 +124,12 @@ struct MSG_Building_finally_destroy();
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Building_get_ui_info(pub LandUseUIID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Building_report_if_demolished(pub ProjectID, pub CVec < PrototypeID >, pub LandUseUIID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Building_report_satisfaction(pub HouseholdID, pub f32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Building_levy_property_tax(pub ResourceAmount, pub CityBudgetID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Building_reconnect(pub PreciseLocation, pub P2);
 
 impl Into<ConstructableID<CBPrototypeKind>> for BuildingID {
@@ -135,8 +165,8 @@ pub fn auto_setup(system: &mut ActorSystem) {
     SleeperID::register_implementor::<Building>(system);
     RoughLocationID::register_implementor::<Building>(system);
     system.add_spawner::<Building, _, _>(
-        |&MSG_Building_spawn(id, style, ref lot), world| {
-            Building::spawn(id, style, lot, world)
+        |&MSG_Building_spawn(id, style, ref lot, prototype_id), world| {
+            Building::spawn(id, style, lot, prototype_id, world)
         }, false
     );
     
@@ -147,8 +177,20 @@ pub fn auto_setup(system: &mut ActorSystem) {
     );
     
     system.add_handler::<Building, _, _>(
-        |&MSG_Building_add_household(household, unit), instance, world| {
-            instance.add_household(household, unit, world); Fate::Live
+        |&MSG_Building_try_offer_unit_for_relocation(household, required_unit_type, requester), instance, world| {
+            instance.try_offer_unit_for_relocation(household, required_unit_type, requester, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Building, _, _>(
+        |&MSG_Building_release_unit(unit), instance, world| {
+            instance.release_unit(unit, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Building, _, _>(
+        |&MSG_Building_add_household(household, unit, field_material), instance, world| {
+            instance.add_household(household, unit, field_material, world); Fate::Live
         }, false
     );
     
@@ -170,6 +212,24 @@ pub fn auto_setup(system: &mut ActorSystem) {
         }, false
     );
     
+    system.add_handler::<Building, _, _>(
+        |&MSG_Building_report_if_demolished(project_id, ref destroyed_prototypes, requester), instance, world| {
+            instance.report_if_demolished(project_id, destroyed_prototypes, requester, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Building, _, _>(
+        |&MSG_Building_report_satisfaction(household, satisfaction), instance, world| {
+            instance.report_satisfaction(household, satisfaction, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Building, _, _>(
+        |&MSG_Building_levy_property_tax(rate_per_square_meter, budget), instance, world| {
+            instance.levy_property_tax(rate_per_square_meter, budget, world); Fate::Live
+        }, false
+    );
+    
     system.add_handler::<Building, _, _>(
         |&MSG_Building_reconnect(new_location, new_connection_point), instance, world| {
             instance.reconnect(new_location, new_connection_point, world); Fate::Live
@@ -1,28 +1,41 @@
 use kay::{ActorSystem, World, Actor, Fate, TypedID};
-use compact::{CVec, COption};
+use compact::{CVec, COption, CHashMap};
 use descartes::P2;
 
 use transport::lane::LaneID;
-use cb_time::actors::TimeID;
-use cb_time::units::Ticks;
-use cb_planning::Prototype;
+use cb_time::actors::{TimeID, ScheduleHandle};
+use cb_time::units::{Ticks, Schedule};
+use cb_planning::{Prototype, PrototypeID};
+use cb_planning::plan_manager::ProjectID;
 use cb_planning::construction::{Constructable, ConstructableID};
 use planning::{CBConstructionID, CBPrototypeKind};
 
 pub mod rendering;
 pub mod architecture;
+use self::architecture::materials_and_props::BuildingMaterial;
 
 use economy::households::HouseholdID;
+use economy::resources::ResourceAmount;
+use economy::city_budget::{CityBudgetID, BudgetItem};
 use transport::pathfinding::PreciseLocation;
-use economy::immigration_and_development::ImmigrationManagerID;
+use economy::immigration_and_development::{ImmigrationManagerID, DevelopmentManagerID};
 use land_use::zone_planning::{Lot, LandUse};
 use super::ui::{LandUseUIID};
 
-use cb_util::log::debug;
+use cb_util::log::{debug, warn};
 const LOG_T: &str = "Buildings";
 
 #[derive(Copy, Clone)]
-pub struct Unit(Option<HouseholdID>, UnitType);
+pub struct Unit(Occupancy, UnitType);
+
+/// A unit is reserved from the moment it is offered until the household moves in or
+/// the offer is declined, so that two offers never hand out the same unit
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum Occupancy {
+    Free,
+    Reserved,
+    Occupied(HouseholdID),
+}
 
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum UnitType {
@@ -67,17 +80,36 @@ pub struct Building {
     lot: Lot,
     pub location: Option<PreciseLocation>,
     style: BuildingStyle,
+    /// Set by the farm currently working the fields of this building
+    field_material: Option<BuildingMaterial>,
+    prototype_id: PrototypeID,
     being_destroyed_for: COption<CBConstructionID>,
+    /// Evicts households that didn't find a new home in time when it is due
+    relocation_timeout: COption<ScheduleHandle>,
     started_reconnect: bool,
+    household_satisfaction: CHashMap<HouseholdID, f32>,
+    reported_satisfaction: Option<f32>,
 }
 
+/// How much the average satisfaction of a building has to change before the UI is updated
+const SATISFACTION_UI_THRESHOLD: f32 = 0.05;
+/// How long households of a building that is to be demolished may look for a new home
+/// before they are evicted
+pub const RELOCATION_TIMEOUT_HOURS: usize = 48;
+
 //use stagemaster::geometry::add_debug_line;
 
 impl Building {
-    pub fn spawn(id: BuildingID, style: BuildingStyle, lot: &Lot, world: &mut World) -> Building {
+    pub fn spawn(
+        id: BuildingID,
+        style: BuildingStyle,
+        lot: &Lot,
+        prototype_id: PrototypeID,
+        world: &mut World,
+    ) -> Building {
         debug(LOG_T, format!("Spawned building {:?}", style), id, world);
 
-        rendering::on_add(id, lot, vec![], style, world);
+        rendering::on_add(id, lot, vec![], style, None, world);
 
         TimeID::local_first(world).wake_up_in(
             Ticks::from(Duration::from_minutes(10)),
@@ -91,8 +123,13 @@ impl Building {
             lot: lot.clone(),
             location: None,
             style,
+            field_material: None,
+            prototype_id,
             being_destroyed_for: COption(None),
+            relocation_timeout: COption(None),
             started_reconnect: false,
+            household_satisfaction: CHashMap::new(),
+            reported_satisfaction: None,
         }
     }
 
@@ -112,10 +149,8 @@ impl Building {
             world,
         );
         if self.being_destroyed_for.is_none() {
-            if let Some(idx) = self.units.iter().position(|&Unit(household, unit_type)| {
-                household.is_none() && unit_type == required_unit_type
-            }) {
-                requester.on_unit_offer(self.id, UnitIdx(idx), world);
+            if let Some(idx) = self.reserve_free_unit(required_unit_type) {
+                requester.on_unit_offer(self.id, idx, self.lot.center_point(), world);
                 debug(LOG_T, "...and responded positively!", self.id(), world);
             } else {
                 debug(LOG_T, "...but doesn't have the unit type", self.id(), world);
@@ -125,50 +160,243 @@ impl Building {
         }
     }
 
-    pub fn add_household(&mut self, household: HouseholdID, unit: UnitIdx, world: &mut World) {
-        self.units[unit.0].0 = Some(household);
-        // Refresh appearance
-        rendering::on_destroy(self.id, world);
-        rendering::on_add(self.id, &self.lot, self.all_households(), self.style, world);
+    /// Like `try_offer_unit`, but for households that have to move out of a demolished building
+    pub fn try_offer_unit_for_relocation(
+        &mut self,
+        household: HouseholdID,
+        required_unit_type: UnitType,
+        requester: DevelopmentManagerID,
+        world: &mut World,
+    ) {
+        if self.being_destroyed_for.is_none() {
+            if let Some(idx) = self.reserve_free_unit(required_unit_type) {
+                requester.on_relocation_unit_offer(
+                    household,
+                    required_unit_type,
+                    self.id,
+                    idx,
+                    self.lot.center_point(),
+                    world,
+                );
+            }
+        }
+    }
+
+    fn reserve_free_unit(&mut self, required_unit_type: UnitType) -> Option<UnitIdx> {
+        let maybe_idx = self.units.iter().position(|&Unit(occupancy, unit_type)| {
+            occupancy == Occupancy::Free && unit_type == required_unit_type
+        });
+
+        if let Some(idx) = maybe_idx {
+            self.units[idx].0 = Occupancy::Reserved;
+        }
+
+        maybe_idx.map(UnitIdx)
+    }
+
+    /// The household that a unit was offered to won't move in after all
+    pub fn release_unit(&mut self, unit: UnitIdx, _: &mut World) {
+        if self.units[unit.0].0 == Occupancy::Reserved {
+            self.units[unit.0].0 = Occupancy::Free;
+        }
+    }
+
+    pub fn add_household(
+        &mut self,
+        household: HouseholdID,
+        unit: UnitIdx,
+        field_material: Option<BuildingMaterial>,
+        world: &mut World,
+    ) {
+        if let Occupancy::Occupied(other_household) = self.units[unit.0].0 {
+            warn(
+                LOG_T,
+                format!(
+                    "{:?} tried to move into a unit occupied by {:?}",
+                    household, other_household
+                ),
+                self.id,
+                world,
+            );
+            return;
+        }
+
+        self.units[unit.0].0 = Occupancy::Occupied(household);
+        if field_material.is_some() {
+            self.field_material = field_material;
+        }
+
+        if self.being_destroyed_for.is_some() {
+            // the unit was offered before demolition started
+            DevelopmentManagerID::global_first(world).relocate(
+                household,
+                self.units[unit.0].1,
+                self.style,
+                world,
+            );
+        } else {
+            // Refresh appearance
+            rendering::on_destroy(self.id, world);
+            rendering::on_add(
+                self.id,
+                &self.lot,
+                self.all_households(),
+                self.style,
+                self.field_material,
+                world,
+            );
+        }
     }
 
     pub fn remove_household(&mut self, household: HouseholdID, world: &mut World) {
         let position = self
             .units
             .iter()
-            .position(|&Unit(user, _)| user == Some(household))
+            .position(|&Unit(occupancy, _)| occupancy == Occupancy::Occupied(household))
             .expect("Tried to remove a household not in the building");
-        self.units[position].0 = None;
+        self.units[position].0 = Occupancy::Free;
+        self.household_satisfaction.remove(household);
+        if self.all_households().is_empty() {
+            self.field_material = None;
+        }
 
-        if self.being_destroyed_for.is_some() && self.all_households().is_empty() {
-            self.id.finally_destroy(world);
+        if self.being_destroyed_for.is_some() {
+            if self.all_households().is_empty() {
+                self.id.finally_destroy(world);
+            }
         } else {
             // Refresh appearance
             rendering::on_destroy(self.id, world);
-            rendering::on_add(self.id, &self.lot, self.all_households(), self.style, world);
+            rendering::on_add(
+                self.id,
+                &self.lot,
+                self.all_households(),
+                self.style,
+                self.field_material,
+                world,
+            );
+            self.update_satisfaction_ui(world);
         }
     }
 
     pub fn all_households(&self) -> Vec<HouseholdID> {
         self.units
             .iter()
-            .filter_map(|&Unit(user, _)| user)
+            .filter_map(|&Unit(occupancy, _)| match occupancy {
+                Occupancy::Occupied(household) => Some(household),
+                _ => None,
+            })
             .collect()
     }
 
+    /// Called once the last household moved out of a demolished building
     pub fn finally_destroy(&mut self, world: &mut World) -> Fate {
-        rendering::on_destroy(self.id, world);
+        if let Some(handle) = *self.relocation_timeout {
+            TimeID::local_first(world).cancel(handle, world);
+        }
         if let Some(location) = self.location {
             location.link.remove_attachee(self.id_as(), world);
         }
-        self.being_destroyed_for
-            .unwrap()
-            .action_done(self.id.into(), world);
         Fate::Die
     }
 
     pub fn get_ui_info(&mut self, requester: LandUseUIID, world: &mut World) {
-        requester.on_building_ui_info(self.id, self.style, self.all_households().into(), world);
+        requester.on_building_ui_info(
+            self.id,
+            self.style,
+            self.all_households().into(),
+            self.satisfaction(),
+            world,
+        );
+    }
+
+    /// Tells the requester about this building and the households that would have to
+    /// move out if the given project was implemented
+    pub fn report_if_demolished(
+        &mut self,
+        project_id: ProjectID,
+        destroyed_prototypes: &CVec<PrototypeID>,
+        requester: LandUseUIID,
+        world: &mut World,
+    ) {
+        if destroyed_prototypes.contains(&self.prototype_id) {
+            requester.on_building_demolition_report(
+                project_id,
+                self.id,
+                self.style,
+                self.all_households().into(),
+                world,
+            );
+        }
+    }
+
+    pub fn report_satisfaction(
+        &mut self,
+        household: HouseholdID,
+        satisfaction: f32,
+        world: &mut World,
+    ) {
+        if self.being_destroyed_for.is_none() && self.all_households().contains(&household) {
+            self.household_satisfaction.insert(household, satisfaction);
+            self.update_satisfaction_ui(world);
+        }
+    }
+
+    /// Average satisfaction of all households living or working here
+    pub fn satisfaction(&self) -> Option<f32> {
+        let n_households = self.household_satisfaction.len();
+
+        if n_households == 0 {
+            None
+        } else {
+            Some(self.household_satisfaction.values().sum::<f32>() / n_households as f32)
+        }
+    }
+
+    fn update_satisfaction_ui(&mut self, world: &mut World) {
+        let satisfaction = self.satisfaction();
+        let changed_enough = match (satisfaction, self.reported_satisfaction) {
+            (Some(satisfaction), Some(reported)) => {
+                (satisfaction - reported).abs() > SATISFACTION_UI_THRESHOLD
+            }
+            (None, None) => false,
+            _ => true,
+        };
+
+        if changed_enough {
+            LandUseUIID::global_broadcast(world).on_building_satisfaction(
+                self.id,
+                satisfaction,
+                world,
+            );
+            self.reported_satisfaction = satisfaction;
+        }
+    }
+
+    /// Splits the property tax for this building's lot evenly among its households
+    pub fn levy_property_tax(
+        &mut self,
+        rate_per_square_meter: ResourceAmount,
+        budget: CityBudgetID,
+        world: &mut World,
+    ) {
+        if self.style == BuildingStyle::NeighboringTownConnection
+            || self.being_destroyed_for.is_some()
+        {
+            return;
+        }
+
+        let households = self.all_households();
+
+        if !households.is_empty() {
+            let lot_area = self.lot.area.primitives[0].area().abs();
+            let tax_per_household =
+                rate_per_square_meter * lot_area / households.len() as ResourceAmount;
+
+            for household in households {
+                household.pay_tax(tax_per_household, BudgetItem::PropertyTax, budget, world);
+            }
+        }
     }
 }
 
@@ -181,24 +409,58 @@ impl Constructable<CBPrototypeKind> for Building {
     ) {
         if let CBPrototypeKind::Lot(ref lot_prototype) = new_prototype.kind {
             self.lot = lot_prototype.lot.clone();
+            self.prototype_id = new_prototype.id;
             rendering::on_destroy(self.id, world);
-            rendering::on_add(self.id, &self.lot, self.all_households(), self.style, world);
+            rendering::on_add(
+                self.id,
+                &self.lot,
+                self.all_households(),
+                self.style,
+                self.field_material,
+                world,
+            );
             report_to.action_done(self.id.into(), world);
         } else {
             unreachable!()
         }
     }
 
-    fn destruct(&mut self, report_to: CBConstructionID, world: &mut World) -> Fate {
+    fn destruct(
+        &mut self,
+        report_to: CBConstructionID,
+        current_instant: Instant,
+        world: &mut World,
+    ) -> Fate {
         self.being_destroyed_for = COption(Some(report_to));
 
+        // As far as construction is concerned, the building is gone right away,
+        // its households move out in the background without holding up other actions
+        report_to.action_done(self.id.into(), world);
+        rendering::on_destroy(self.id, world);
+        if self.reported_satisfaction.is_some() {
+            LandUseUIID::global_broadcast(world).on_building_satisfaction(self.id, None, world);
+        }
+
         if self.all_households().is_empty() {
             self.finally_destroy(world)
         } else {
-            for household in &self.all_households() {
-                household.destroy(world);
+            let development_manager = DevelopmentManagerID::global_first(world);
+            for &Unit(occupancy, unit_type) in self.units.iter() {
+                if let Occupancy::Occupied(household) = occupancy {
+                    development_manager.relocate(household, unit_type, self.style, world);
+                }
             }
 
+            // a building is only ever demolished once, so this is its only handle
+            let handle = ScheduleHandle::new(self.id, 0);
+            TimeID::local_first(world).schedule(
+                handle,
+                Schedule::At(current_instant + Duration::from_hours(RELOCATION_TIMEOUT_HOURS)),
+                self.id_as(),
+                world,
+            );
+            self.relocation_timeout = COption(Some(handle));
+
             Fate::Live
         }
     }
@@ -259,6 +521,30 @@ impl Sleeper for Building {
             self.started_reconnect = true;
         }
     }
+
+    fn wake_scheduled(
+        &mut self,
+        handle: ScheduleHandle,
+        current_instant: Instant,
+        world: &mut World,
+    ) {
+        if *self.relocation_timeout == Some(handle) {
+            self.relocation_timeout = COption(None);
+
+            // destroyed households leave the relocation queue themselves
+            for household in self.all_households() {
+                debug(
+                    LOG_T,
+                    "Evicting household that didn't find a new home",
+                    self.id,
+                    world,
+                );
+                household.destroy(world);
+            }
+        } else {
+            self.wake(current_instant, world);
+        }
+    }
 }
 
 impl Building {
@@ -294,16 +580,16 @@ const FAMILIES_PER_NEIGHBORING_TOWN: usize = 5;
 
 pub fn units_for_style(style: BuildingStyle) -> CVec<Unit> {
     match style {
-        BuildingStyle::FamilyHouse => vec![Unit(None, UnitType::Dwelling)],
-        BuildingStyle::GroceryShop => vec![Unit(None, UnitType::Retail)],
-        BuildingStyle::Bakery => vec![Unit(None, UnitType::Bakery)],
-        BuildingStyle::Mill => vec![Unit(None, UnitType::Mill)],
-        BuildingStyle::Field => vec![Unit(None, UnitType::Agriculture)],
+        BuildingStyle::FamilyHouse => vec![Unit(Occupancy::Free, UnitType::Dwelling)],
+        BuildingStyle::GroceryShop => vec![Unit(Occupancy::Free, UnitType::Retail)],
+        BuildingStyle::Bakery => vec![Unit(Occupancy::Free, UnitType::Bakery)],
+        BuildingStyle::Mill => vec![Unit(Occupancy::Free, UnitType::Mill)],
+        BuildingStyle::Field => vec![Unit(Occupancy::Free, UnitType::Agriculture)],
         BuildingStyle::NeighboringTownConnection => {
-            Some(Unit(None, UnitType::NeighboringTownTrade))
+            Some(Unit(Occupancy::Free, UnitType::NeighboringTownTrade))
                 .into_iter()
                 .chain(vec![
-                    Unit(None, UnitType::Dwelling);
+                    Unit(Occupancy::Free, UnitType::Dwelling);
                     FAMILIES_PER_NEIGHBORING_TOWN
                 ])
                 .collect()
@@ -1,5 +1,6 @@
 use kay::{ World, TypedID};
 use super::{Building, Lot, BuildingID, BuildingStyle};
+use super::architecture::materials_and_props::BuildingMaterial;
 use super::super::ui::{LandUseUIID};
 use economy::households::HouseholdID;
 
@@ -10,6 +11,7 @@ impl Building {
             self.lot.clone(),
             self.all_households().into(),
             self.style,
+            self.field_material,
             world,
         )
     }
@@ -20,6 +22,7 @@ pub fn on_add(
     lot: &Lot,
     households: Vec<HouseholdID>,
     building_type: BuildingStyle,
+    field_material: Option<BuildingMaterial>,
     world: &mut World,
 ) {
     LandUseUIID::global_broadcast(world).on_building_constructed(
@@ -27,6 +30,7 @@ pub fn on_add(
         lot.clone(),
         households.into(),
         building_type,
+        field_material,
         world,
     );
 }
@@ -7,6 +7,11 @@ use land_use::buildings::BuildingID;
 use cb_planning::construction::ConstructableID;
 use cb_planning::PrototypeID;
 use planning::{CBConstructionID, CBPrototypeKind};
+use economy::resources::ResourceAmount;
+use economy::city_budget::LOT_CONSTRUCTION_COST_PER_SQUARE_METER;
+use cb_time::units::Duration;
+
+const LOT_SERVICING_HOURS: usize = 6;
 
 impl LotPrototype {
     pub fn construct(
@@ -18,13 +23,32 @@ impl LotPrototype {
         let id = match self.occupancy {
             LotOccupancy::Vacant => VacantLotID::spawn(self.lot.clone(), self_id, world).into(),
             LotOccupancy::Occupied(building_style) => {
-                BuildingID::spawn(building_style, self.lot.clone(), world).into()
+                BuildingID::spawn(building_style, self.lot.clone(), self_id, world).into()
             }
         };
         report_to.action_done(id, world);
         vec![id].into()
     }
 
+    /// Only newly zoned land needs to be serviced by the city, buildings are paid for privately
+    pub fn construction_cost(&self) -> ResourceAmount {
+        match self.occupancy {
+            LotOccupancy::Vacant => {
+                self.lot.area.primitives[0].area().abs() * LOT_CONSTRUCTION_COST_PER_SQUARE_METER
+            }
+            LotOccupancy::Occupied(_) => 0.0,
+        }
+    }
+
+    /// Newly zoned land needs to be serviced before it can be built on,
+    /// buildings themselves are put up by their developers right away
+    pub fn construction_duration(&self) -> Duration {
+        match self.occupancy {
+            LotOccupancy::Vacant => Duration::from_hours(LOT_SERVICING_HOURS),
+            LotOccupancy::Occupied(_) => Duration(0),
+        }
+    }
+
     pub fn morphable_from(&self, other: &LotPrototype) -> bool {
         // TODO: improve this
         (self.occupancy != LotOccupancy::Vacant)
@@ -50,16 +50,24 @@ impl TypedID for LandUseUIID {
 impl<Act: Actor + LandUseUI> TraitIDFrom<Act> for LandUseUIID {}
 
 impl LandUseUIID {
-    pub fn on_building_constructed(self, id: BuildingID, lot: Lot, households: CVec < HouseholdID >, style: BuildingStyle, world: &mut World) {
-        world.send(self.as_raw(), MSG_LandUseUI_on_building_constructed(id, lot, households, style));
+    pub fn on_building_constructed(self, id: BuildingID, lot: Lot, households: CVec < HouseholdID >, style: BuildingStyle, field_material: Option < BuildingMaterial >, world: &mut World) {
+        world.send(self.as_raw(), MSG_LandUseUI_on_building_constructed(id, lot, households, style, field_material));
     }
     
     pub fn on_building_destructed(self, id: BuildingID, world: &mut World) {
         world.send(self.as_raw(), MSG_LandUseUI_on_building_destructed(id));
     }
     
-    pub fn on_building_ui_info(self, id: BuildingID, style: BuildingStyle, households: CVec < HouseholdID >, world: &mut World) {
-        world.send(self.as_raw(), MSG_LandUseUI_on_building_ui_info(id, style, households));
+    pub fn on_building_ui_info(self, id: BuildingID, style: BuildingStyle, households: CVec < HouseholdID >, satisfaction: Option < f32 >, world: &mut World) {
+        world.send(self.as_raw(), MSG_LandUseUI_on_building_ui_info(id, style, households, satisfaction));
+    }
+    
+    pub fn on_building_satisfaction(self, id: BuildingID, satisfaction: Option < f32 >, world: &mut World) {
+        world.send(self.as_raw(), MSG_LandUseUI_on_building_satisfaction(id, satisfaction));
+    }
+    
+    pub fn on_building_demolition_report(self, project_id: ProjectID, id: BuildingID, style: BuildingStyle, households: CVec < HouseholdID >, world: &mut World) {
+        world.send(self.as_raw(), MSG_LandUseUI_on_building_demolition_report(project_id, id, style, households));
     }
 
     pub fn register_trait(system: &mut ActorSystem) {
@@ -67,13 +75,15 @@ impl LandUseUIID {
         system.register_trait_message::<MSG_LandUseUI_on_building_constructed>();
         system.register_trait_message::<MSG_LandUseUI_on_building_destructed>();
         system.register_trait_message::<MSG_LandUseUI_on_building_ui_info>();
+        system.register_trait_message::<MSG_LandUseUI_on_building_satisfaction>();
+        system.register_trait_message::<MSG_LandUseUI_on_building_demolition_report>();
     }
 
     pub fn register_implementor<Act: Actor + LandUseUI>(system: &mut ActorSystem) {
         system.register_implementor::<Act, LandUseUIRepresentative>();
         system.add_handler::<Act, _, _>(
-            |&MSG_LandUseUI_on_building_constructed(id, ref lot, ref households, style), instance, world| {
-                instance.on_building_constructed(id, lot, households, style, world); Fate::Live
+            |&MSG_LandUseUI_on_building_constructed(id, ref lot, ref households, style, field_material), instance, world| {
+                instance.on_building_constructed(id, lot, households, style, field_material, world); Fate::Live
             }, false
         );
         
@@ -84,19 +94,35 @@ impl LandUseUIID {
         );
         
         system.add_handler::<Act, _, _>(
-            |&MSG_LandUseUI_on_building_ui_info(id, style, ref households), instance, world| {
-                instance.on_building_ui_info(id, style, households, world); Fate::Live
+            |&MSG_LandUseUI_on_building_ui_info(id, style, ref households, satisfaction), instance, world| {
+                instance.on_building_ui_info(id, style, households, satisfaction, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_LandUseUI_on_building_satisfaction(id, satisfaction), instance, world| {
+                instance.on_building_satisfaction(id, satisfaction, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_LandUseUI_on_building_demolition_report(project_id, id, style, ref households), instance, world| {
+                instance.on_building_demolition_report(project_id, id, style, households, world); Fate::Live
             }, false
         );
     }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_LandUseUI_on_building_constructed(pub BuildingID, pub Lot, pub CVec < HouseholdID >, pub BuildingStyle);
+struct MSG_LandUseUI_on_building_constructed(pub BuildingID, pub Lot, pub CVec < HouseholdID >, pub BuildingStyle, pub Option < BuildingMaterial >);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_LandUseUI_on_building_destructed(pub BuildingID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_LandUseUI_on_building_ui_info(pub BuildingID, pub BuildingStyle, pub CVec < HouseholdID >);
+struct MSG_LandUseUI_on_building_ui_info(pub BuildingID, pub BuildingStyle, pub CVec < HouseholdID >, pub Option < f32 >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_LandUseUI_on_building_satisfaction(pub BuildingID, pub Option < f32 >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_LandUseUI_on_building_demolition_report(pub ProjectID, pub BuildingID, pub BuildingStyle, pub CVec < HouseholdID >);
 
 
 
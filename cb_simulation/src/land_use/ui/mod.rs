@@ -1,8 +1,10 @@
 use kay::World;
 use compact::CVec;
 use super::buildings::{BuildingID, BuildingStyle};
+use super::buildings::architecture::materials_and_props::BuildingMaterial;
 use economy::households::HouseholdID;
 use super::zone_planning::Lot;
+use cb_planning::plan_manager::ProjectID;
 
 pub trait LandUseUI {
     fn on_building_constructed(
@@ -11,6 +13,7 @@ pub trait LandUseUI {
         lot: &Lot,
         households: &CVec<HouseholdID>,
         style: BuildingStyle,
+        field_material: Option<BuildingMaterial>,
         _world: &mut World,
     );
 
@@ -21,6 +24,24 @@ pub trait LandUseUI {
         id: BuildingID,
         style: BuildingStyle,
         households: &CVec<HouseholdID>,
+        satisfaction: Option<f32>,
+        _world: &mut World,
+    );
+
+    /// `None` once the building has no households reporting satisfaction or is gone
+    fn on_building_satisfaction(
+        &mut self,
+        id: BuildingID,
+        satisfaction: Option<f32>,
+        _world: &mut World,
+    );
+
+    fn on_building_demolition_report(
+        &mut self,
+        project_id: ProjectID,
+        id: BuildingID,
+        style: BuildingStyle,
+        households: &CVec<HouseholdID>,
         _world: &mut World,
     );
 }
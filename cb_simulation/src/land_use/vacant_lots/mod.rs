@@ -1,4 +1,5 @@
 use kay::{World, Fate, ActorSystem};
+use cb_time::units::Instant;
 use compact::CVec;
 use descartes::{
     N, P2, V2, Area, WithUniqueOrthogonal, ClosedLinePath, LinePath, AreaError, AreaEmbedding,
@@ -348,7 +349,12 @@ impl Constructable<CBPrototypeKind> for VacantLot {
         unreachable!()
     }
 
-    fn destruct(&mut self, report_to: CBConstructionID, world: &mut World) -> Fate {
+    fn destruct(
+        &mut self,
+        report_to: CBConstructionID,
+        _current_instant: Instant,
+        world: &mut World,
+    ) -> Fate {
         report_to.action_done(self.id.into(), world);
         Fate::Die
     }
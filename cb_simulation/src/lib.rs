@@ -3,6 +3,8 @@
 #![feature(custom_inner_attributes)]
 extern crate ordered_float;
 extern crate itertools;
+#[macro_use]
+extern crate lazy_static;
 extern crate rand;
 extern crate noise;
 extern crate fnv;
@@ -36,10 +38,11 @@ pub fn setup_common(system: &mut kay::ActorSystem) {
         cb_util::log::setup,
         cb_planning::plan_manager::setup::<planning::CBPlanningLogic>,
         cb_planning::construction::setup::<planning::CBPrototypeKind>,
+        planning::templates::setup,
         transport::setup,
+        environment::setup,
         economy::setup,
         land_use::setup,
-        environment::setup,
     ] {
         setup_fn(system)
     }
@@ -50,9 +53,11 @@ pub fn spawn_for_server(world: &mut kay::World) -> cb_time::actors::TimeID {
     let time = cb_time::actors::spawn(world);
     let plan_manager = cb_planning::plan_manager::spawn::<planning::CBPlanningLogic>(world);
     cb_planning::construction::spawn::<planning::CBPrototypeKind>(world);
+    planning::templates::spawn(world);
     land_use::spawn(world);
     transport::spawn(world, time);
     economy::spawn(world, time, plan_manager);
     environment::vegetation::spawn(world, plan_manager);
+    environment::weather::spawn(world, time);
     time
 }
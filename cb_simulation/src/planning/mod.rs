@@ -1,14 +1,19 @@
 use kay::World;
 use compact::CVec;
+use descartes::{N, P2, V2};
 use transport::transport_planning::{RoadIntent, RoadPrototype};
-use land_use::zone_planning::{ZoneIntent, BuildingIntent, LotPrototype};
+use land_use::zone_planning::{ZoneIntent, BuildingIntent, LotPrototype, LotOccupancy};
 use environment::vegetation::{PlantIntent, PlantPrototype};
+use economy::city_budget::{CityBudgetID, BudgetItem};
+use cb_time::units::Duration;
 use cb_planning::{PlanningLogic, PrototypeID, PlanningStepFn};
 use cb_planning::plan_manager::{PlanManager, PlanManagerID};
 use cb_planning::construction::{
     Construction, ConstructionID, PrototypeKind, GestureIntent, ConstructableID,
 };
 
+pub mod templates;
+
 #[derive(Copy, Clone)]
 pub struct CBPlanningLogic {}
 
@@ -38,7 +43,58 @@ pub enum CBGestureIntent {
     Plant(PlantIntent),
 }
 
-impl GestureIntent for CBGestureIntent {}
+/// How far beyond their outline gestures of each kind can influence planning
+const ROAD_BOUNDS_MARGIN: N = 30.0;
+const ZONE_BOUNDS_MARGIN: N = 10.0;
+const PLANT_BOUNDS_MARGIN: N = 5.0;
+
+fn bounds_of<'a, I: IntoIterator<Item = &'a P2>>(points: I, margin: N) -> Option<(P2, P2)> {
+    points
+        .into_iter()
+        .fold(None, |bounds, point| match bounds {
+            None => Some((*point, *point)),
+            Some((min, max)) => Some((
+                P2::new(min.x.min(point.x), min.y.min(point.y)),
+                P2::new(max.x.max(point.x), max.y.max(point.y)),
+            )),
+        })
+        .map(|(min, max)| (min - V2::new(margin, margin), max + V2::new(margin, margin)))
+}
+
+impl GestureIntent for CBGestureIntent {
+    fn bounds(&self) -> Option<(P2, P2)> {
+        match self {
+            CBGestureIntent::Road(ref road_intent) => {
+                road_intent.path.resolve().0.and_then(|path| {
+                    bounds_of(
+                        path.to_line_path_with_max_angle(0.12).points.iter(),
+                        ROAD_BOUNDS_MARGIN,
+                    )
+                })
+            }
+            CBGestureIntent::Zone(ref zone_intent) => {
+                zone_intent.boundary.resolve().0.and_then(|path| {
+                    bounds_of(
+                        path.to_line_path_with_max_angle(0.12).points.iter(),
+                        ZONE_BOUNDS_MARGIN,
+                    )
+                })
+            }
+            CBGestureIntent::Building(BuildingIntent { ref lot, .. }) => bounds_of(
+                lot.original_area.primitives[0]
+                    .boundary
+                    .path()
+                    .points
+                    .iter(),
+                ZONE_BOUNDS_MARGIN,
+            ),
+            CBGestureIntent::Plant(PlantIntent::Individual(ref plant)) => {
+                bounds_of(Some(&plant.position), PLANT_BOUNDS_MARGIN)
+            }
+            CBGestureIntent::Plant(PlantIntent::NaturalGrowth) => None,
+        }
+    }
+}
 
 #[derive(Compact, Clone, Serialize, Deserialize, Debug)]
 pub enum CBPrototypeKind {
@@ -79,4 +135,134 @@ impl PrototypeKind for CBPrototypeKind {
             _ => false,
         }
     }
+
+    fn construction_cost(&self) -> f32 {
+        match self {
+            CBPrototypeKind::Road(ref road_prototype) => road_prototype.construction_cost(),
+            CBPrototypeKind::Lot(ref lot_prototype) => lot_prototype.construction_cost(),
+            CBPrototypeKind::Plant(_) => 0.0,
+        }
+    }
+
+    /// Construction is paid for when it starts, not when a construction site is finished
+    fn begin_construction(&self, world: &mut World) {
+        let cost = self.construction_cost();
+        if cost > 0.0 {
+            CityBudgetID::global_first(world).charge(BudgetItem::Construction, cost, world);
+        }
+    }
+
+    fn construction_duration(&self) -> Duration {
+        match self {
+            CBPrototypeKind::Road(ref road_prototype) => road_prototype.construction_duration(),
+            CBPrototypeKind::Lot(ref lot_prototype) => lot_prototype.construction_duration(),
+            CBPrototypeKind::Plant(_) => Duration(0),
+        }
+    }
+
+    fn impact_category(&self) -> &'static str {
+        match self {
+            CBPrototypeKind::Road(RoadPrototype::Lane(_))
+            | CBPrototypeKind::Road(RoadPrototype::SwitchLane(_)) => "lanes",
+            CBPrototypeKind::Road(RoadPrototype::Intersection(_)) => "intersections",
+            CBPrototypeKind::Road(RoadPrototype::PavedArea(_)) => "paved areas",
+            CBPrototypeKind::Lot(LotPrototype {
+                occupancy: LotOccupancy::Vacant,
+                ..
+            }) => "lots",
+            CBPrototypeKind::Lot(_) => "buildings",
+            CBPrototypeKind::Plant(_) => "plants",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use descartes::{Corner, EditArcLinePath, ResolutionStrategy, Closedness};
+    use cb_planning::{PlanHistory, PlanResult, Gesture, GestureID, VersionedGesture};
+    use transport::transport_planning::RoadLaneConfig;
+    use land_use::zone_planning::{ZoneConfig, LandUse};
+
+    fn corners(points: &[(N, N)]) -> Vec<Corner> {
+        points
+            .iter()
+            .map(|&(x, y)| Corner::new(P2::new(x, y), None, None))
+            .collect()
+    }
+
+    fn road(points: &[(N, N)]) -> CBGestureIntent {
+        CBGestureIntent::Road(RoadIntent::new(
+            corners(points),
+            RoadLaneConfig {
+                n_lanes_forward: 2,
+                n_lanes_backward: 2,
+            },
+        ))
+    }
+
+    fn zone(points: &[(N, N)]) -> CBGestureIntent {
+        CBGestureIntent::Zone(ZoneIntent {
+            boundary: EditArcLinePath::new(
+                corners(points),
+                ResolutionStrategy::AssumeLines,
+                Closedness::AlwaysClosed,
+            ),
+            config: ZoneConfig {
+                land_use: LandUse::Residential,
+                max_height: None,
+                set_back: None,
+            },
+        })
+    }
+
+    fn sorted_ids(result: &PlanResult<CBPrototypeKind>) -> Vec<PrototypeID> {
+        let mut ids = result.prototypes.keys().cloned().collect::<Vec<_>>();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn planning_regions_in_parallel_matches_planning_everything_at_once() {
+        // two blocks whose roads, intersections and lots reach into the grid cells
+        // next to their gestures, where the other block starts, and one far away
+        let gestures = vec![
+            road(&[(0.0, 150.0), (390.0, 150.0)]),
+            road(&[(150.0, 0.0), (150.0, 190.0)]),
+            zone(&[(160.0, 20.0), (380.0, 20.0), (380.0, 135.0), (160.0, 135.0)]),
+            road(&[(0.0, 430.0), (390.0, 430.0)]),
+            road(&[(250.0, 330.0), (250.0, 580.0)]),
+            zone(&[(10.0, 310.0), (240.0, 310.0), (240.0, 418.0), (10.0, 418.0)]),
+            road(&[(5000.0, 5000.0), (5400.0, 5000.0)]),
+            zone(&[
+                (5010.0, 5015.0),
+                (5390.0, 5015.0),
+                (5390.0, 5150.0),
+                (5010.0, 5150.0),
+            ]),
+        ];
+
+        let mut history = PlanHistory::<CBGestureIntent>::new();
+        let step_id = history.latest_step_id();
+
+        for intent in gestures {
+            history.gestures.insert(
+                GestureID::new(),
+                VersionedGesture(Gesture::new(intent), step_id),
+            );
+        }
+
+        let mut sequential = PlanResult::new();
+
+        for step_fn in CBPlanningLogic::planning_step_functions() {
+            for prototype in step_fn(&history, &sequential).unwrap() {
+                sequential.add_prototype(prototype);
+            }
+        }
+
+        let parallel = CBPlanningLogic::calculate_result(&history).unwrap();
+
+        assert!(!sequential.prototypes.is_empty());
+        assert_eq!(sorted_ids(&sequential), sorted_ids(&parallel));
+    }
 }
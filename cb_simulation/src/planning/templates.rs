@@ -0,0 +1,179 @@
+use kay::{ActorSystem, World};
+use compact::{CVec, CHashMap};
+use descartes::{N, P2, V2, Corner, EditArcLinePath, ResolutionStrategy, Closedness};
+use cb_util::config_manager::{Config, Name, ConfigManager, ConfigManagerID};
+use transport::transport_planning::{RoadIntent, RoadLaneConfig};
+use land_use::zone_planning::{ZoneIntent, ZoneConfig, LandUse};
+use environment::vegetation::{PlantIntent, PlantPrototype, VegetationType};
+use super::CBGestureIntent;
+
+/// A gesture of a template, with positions relative to the template's origin
+#[derive(Compact, Clone, Debug, Serialize, Deserialize)]
+pub enum TemplateGesture {
+    Road(CVec<P2>, RoadLaneConfig),
+    Zone(CVec<P2>, ZoneConfig),
+    Plant(P2, VegetationType),
+}
+
+/// A named group of gestures that can be placed into a project in one go,
+/// for example a block of a street grid
+#[derive(Compact, Clone, Debug, Serialize, Deserialize)]
+pub struct GestureTemplate {
+    pub gestures: CVec<TemplateGesture>,
+}
+
+impl Config for GestureTemplate {}
+
+impl GestureTemplate {
+    /// The template's gestures, rotated (in radians) and scaled around its origin
+    /// and then moved to `position`
+    pub fn placed(&self, position: P2, rotation: N, scale: N) -> Vec<CBGestureIntent> {
+        let (sin, cos) = rotation.sin_cos();
+        let transform = |point: &P2| {
+            position + scale * V2::new(point.x * cos - point.y * sin, point.x * sin + point.y * cos)
+        };
+
+        self.gestures
+            .iter()
+            .map(|gesture| match *gesture {
+                TemplateGesture::Road(ref points, lane_config) => {
+                    CBGestureIntent::Road(RoadIntent::new(
+                        points
+                            .iter()
+                            .map(|point| Corner::new(transform(point), None, None))
+                            .collect::<Vec<_>>(),
+                        lane_config,
+                    ))
+                }
+                TemplateGesture::Zone(ref points, config) => CBGestureIntent::Zone(ZoneIntent {
+                    boundary: EditArcLinePath::new(
+                        points
+                            .iter()
+                            .map(|point| Corner::new(transform(point), None, None))
+                            .collect::<Vec<_>>(),
+                        ResolutionStrategy::AssumeLines,
+                        Closedness::AlwaysClosed,
+                    ),
+                    config,
+                }),
+                TemplateGesture::Plant(ref point, vegetation_type) => {
+                    CBGestureIntent::Plant(PlantIntent::Individual(PlantPrototype {
+                        vegetation_type,
+                        position: transform(point),
+                    }))
+                }
+            })
+            .collect()
+    }
+}
+
+fn rectangle(min: P2, max: P2) -> CVec<P2> {
+    vec![min, P2::new(max.x, min.y), max, P2::new(min.x, max.y)].into()
+}
+
+fn zone(land_use: LandUse) -> ZoneConfig {
+    ZoneConfig {
+        land_use,
+        max_height: None,
+        set_back: None,
+    }
+}
+
+const GRID_SPACING: N = 100.0;
+const ROAD_CLEARANCE: N = 15.0;
+
+/// Three by three residential blocks, framed by two-lane roads
+fn grid_block() -> GestureTemplate {
+    let lanes = RoadLaneConfig {
+        n_lanes_forward: 1,
+        n_lanes_backward: 1,
+    };
+    let extent = 1.5 * GRID_SPACING;
+
+    let roads = (0..4).flat_map(|i| {
+        let offset = -extent + i as N * GRID_SPACING;
+        vec![
+            TemplateGesture::Road(
+                vec![P2::new(offset, -extent), P2::new(offset, extent)].into(),
+                lanes,
+            ),
+            TemplateGesture::Road(
+                vec![P2::new(-extent, offset), P2::new(extent, offset)].into(),
+                lanes,
+            ),
+        ]
+    });
+
+    let zones = (0..3).flat_map(|x| {
+        (0..3).map(move |y| {
+            let min = P2::new(
+                -extent + x as N * GRID_SPACING + ROAD_CLEARANCE,
+                -extent + y as N * GRID_SPACING + ROAD_CLEARANCE,
+            );
+            let max = min
+                + V2::new(
+                    GRID_SPACING - 2.0 * ROAD_CLEARANCE,
+                    GRID_SPACING - 2.0 * ROAD_CLEARANCE,
+                );
+            TemplateGesture::Zone(rectangle(min, max), zone(LandUse::Residential))
+        })
+    });
+
+    GestureTemplate {
+        gestures: roads.chain(zones).collect(),
+    }
+}
+
+const AVENUE_LENGTH: N = 300.0;
+const AVENUE_TREE_SPACING: N = 20.0;
+
+/// A four-lane avenue lined with trees and commercial zones on both sides
+fn avenue() -> GestureTemplate {
+    let half_length = AVENUE_LENGTH / 2.0;
+
+    let road = TemplateGesture::Road(
+        vec![P2::new(-half_length, 0.0), P2::new(half_length, 0.0)].into(),
+        RoadLaneConfig {
+            n_lanes_forward: 2,
+            n_lanes_backward: 2,
+        },
+    );
+
+    let trees = [-1.0, 1.0].iter().flat_map(|side| {
+        (0..=(AVENUE_LENGTH / AVENUE_TREE_SPACING) as usize).map(move |i| {
+            TemplateGesture::Plant(
+                P2::new(-half_length + i as N * AVENUE_TREE_SPACING, side * 16.0),
+                VegetationType::MediumTree,
+            )
+        })
+    });
+
+    let zones = [(-60.0, -20.0), (20.0, 60.0)]
+        .iter()
+        .map(|&(min_y, max_y)| {
+            TemplateGesture::Zone(
+                rectangle(P2::new(-half_length, min_y), P2::new(half_length, max_y)),
+                zone(LandUse::Commercial),
+            )
+        });
+
+    GestureTemplate {
+        gestures: Some(road).into_iter().chain(trees).chain(zones).collect(),
+    }
+}
+
+pub fn default_templates() -> CHashMap<Name, GestureTemplate> {
+    vec![("Grid block", grid_block()), ("Avenue", avenue())]
+        .into_iter()
+        .map(|(name, template)| (Name::from(name).unwrap(), template))
+        .collect()
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<ConfigManager<GestureTemplate>>();
+    ::cb_util::config_manager::auto_setup::<GestureTemplate>(system);
+}
+
+pub fn spawn(world: &mut World) {
+    ConfigManagerID::<GestureTemplate>::spawn(default_templates(), world);
+}
@@ -17,9 +17,20 @@ use super::transport_planning::{
     RoadPrototype, LanePrototype, SwitchLanePrototype, IntersectionPrototype,
 };
 
+use economy::resources::ResourceAmount;
+use economy::city_budget::{
+    CityBudgetID, LANE_CONSTRUCTION_COST_PER_METER, PAVING_CONSTRUCTION_COST_PER_SQUARE_METER,
+};
+
+use cb_time::units::{Duration, Instant};
+
 use cb_util::log::debug;
 const LOG_T: &str = "Transport Construction";
 
+const LANE_CONSTRUCTION_SECONDS_PER_METER: N = 60.0;
+const INTERSECTION_CONSTRUCTION_HOURS: usize = 12;
+const PAVING_CONSTRUCTION_SECONDS_PER_SQUARE_METER: N = 6.0;
+
 use dimensions::{LANE_CONNECTION_TOLERANCE, MAX_SWITCHING_LANE_DISTANCE, MIN_SWITCHING_LANE_LENGTH};
 
 impl RoadPrototype {
@@ -73,18 +84,79 @@ impl RoadPrototype {
             RoadPrototype::PavedArea(_) => CVec::new(),
         }
     }
+
+    pub fn construction_cost(&self) -> ResourceAmount {
+        match *self {
+            RoadPrototype::Lane(LanePrototype(ref path, _)) => {
+                path.length() * LANE_CONSTRUCTION_COST_PER_METER
+            }
+            RoadPrototype::SwitchLane(_) => 0.0,
+            RoadPrototype::Intersection(IntersectionPrototype {
+                ref connecting_lanes,
+                ..
+            }) => connecting_lanes
+                .values()
+                .flat_map(|group| group.iter())
+                .map(|&LanePrototype(ref path, _)| path.length() * LANE_CONSTRUCTION_COST_PER_METER)
+                .sum(),
+            RoadPrototype::PavedArea(ref area) => {
+                area.primitives
+                    .iter()
+                    .map(|primitive| primitive.area().abs())
+                    .sum::<N>()
+                    * PAVING_CONSTRUCTION_COST_PER_SQUARE_METER
+            }
+        }
+    }
+
+    /// How long the road stays a construction site before it can be used
+    pub fn construction_duration(&self) -> Duration {
+        match *self {
+            RoadPrototype::Lane(LanePrototype(ref path, _)) => Duration::from_seconds(
+                (path.length() * LANE_CONSTRUCTION_SECONDS_PER_METER) as usize,
+            ),
+            // switch lanes are built together with the lanes they connect
+            RoadPrototype::SwitchLane(_) => Duration(0),
+            RoadPrototype::Intersection(_) => Duration::from_hours(INTERSECTION_CONSTRUCTION_HOURS),
+            RoadPrototype::PavedArea(ref area) => Duration::from_seconds(
+                (area
+                    .primitives
+                    .iter()
+                    .map(|primitive| primitive.area().abs())
+                    .sum::<N>()
+                    * PAVING_CONSTRUCTION_SECONDS_PER_SQUARE_METER) as usize,
+            ),
+        }
+    }
 }
 
 impl Constructable<CBPrototypeKind> for Lane {
     fn morph(
         &mut self,
-        _new_prototype: &Prototype<CBPrototypeKind>,
+        new_prototype: &Prototype<CBPrototypeKind>,
         report_to: CBConstructionID,
         world: &mut World,
     ) {
+        if let CBPrototypeKind::Road(RoadPrototype::Lane(LanePrototype(ref new_path, _))) =
+            new_prototype.kind
+        {
+            // morphable lanes only differ slightly and keep their geometry,
+            // but maintenance costs should follow the planned length exactly
+            let length_difference = new_path.length() - self.construction.maintained_length;
+            if length_difference != 0.0 {
+                CityBudgetID::global_first(world)
+                    .add_maintained_lane_length(length_difference, world);
+                self.construction.maintained_length = new_path.length();
+            }
+        }
         report_to.action_done(self.id_as(), world);
     }
-    fn destruct(&mut self, report_to: CBConstructionID, world: &mut World) -> Fate {
+    fn destruct(
+        &mut self,
+        report_to: CBConstructionID,
+        _current_instant: Instant,
+        world: &mut World,
+    ) -> Fate {
         self.unbuild(report_to, world);
         Fate::Live
     }
@@ -99,7 +171,12 @@ impl Constructable<CBPrototypeKind> for SwitchLane {
     ) {
         report_to.action_done(self.id_as(), world);
     }
-    fn destruct(&mut self, report_to: CBConstructionID, world: &mut World) -> Fate {
+    fn destruct(
+        &mut self,
+        report_to: CBConstructionID,
+        _current_instant: Instant,
+        world: &mut World,
+    ) -> Fate {
         self.unbuild(report_to, world);
         Fate::Live
     }
@@ -112,16 +189,21 @@ pub struct ConstructionInfo {
     pub progress: f32,
     unbuilding_for: Option<CBConstructionID>,
     disconnects_remaining: u8,
+    /// The length the city budget pays maintenance for, which follows morphs
+    /// even though the actual geometry doesn't
+    maintained_length: f32,
 }
 
 impl ConstructionInfo {
     pub fn from_path(path: LinePath) -> Self {
+        let length = path.length();
         ConstructionInfo {
-            length: path.length(),
+            length,
             path,
             progress: 0.0,
             unbuilding_for: None,
             disconnects_remaining: 0,
+            maintained_length: length,
         }
     }
 }
@@ -151,6 +233,7 @@ impl Lane {
         if !on_intersection {
             SwitchLaneID::global_broadcast(world).connect_switch_to_normal(id, path.clone(), world);
         }
+        CityBudgetID::global_first(world).add_maintained_lane_length(path.length(), world);
         report_to.action_done(id.into(), world);
         Lane::spawn(id, path, on_intersection, timings, world)
     }
@@ -448,6 +531,8 @@ impl Lane {
 impl Lane {
     fn finalize(&self, report_to: CBConstructionID, world: &mut World) {
         report_to.action_done(self.id_as(), world);
+        CityBudgetID::global_first(world)
+            .add_maintained_lane_length(-self.construction.maintained_length, world);
 
         for car in &self.microtraffic.cars {
             car.trip.finish(
@@ -131,7 +131,7 @@ impl DerefMut for TransferringLaneCar {
     }
 }
 
-use cb_time::units::Instant;
+use cb_time::units::{Instant, Ticks, TICKS_PER_SIM_SECOND};
 
 pub trait LaneLike {
     fn add_car(
@@ -151,6 +151,20 @@ use cb_time::actors::{Temporal, TemporalID};
 const TRAFFIC_LOGIC_THROTTLING: usize = 10;
 const PATHFINDING_THROTTLING: usize = 10;
 
+/// How many ticks a tick of `dt` seconds covers, more than one while time is fast-forwarded
+fn ticks_within(dt: f32) -> usize {
+    ((dt * TICKS_PER_SIM_SECOND as f32).round() as usize).max(1)
+}
+
+/// Whether the throttled logic of `id`, which runs every `throttling` ticks staggered
+/// by instance, is due within the `elapsed_ticks` starting at `instant`. Checking the
+/// whole elapsed span instead of just `instant` lets it catch up on skipped ticks.
+fn is_due<ID: TypedID>(id: ID, throttling: usize, instant: Instant, elapsed_ticks: usize) -> bool {
+    let phase = id.as_raw().instance_id as usize % throttling;
+    let ticks_until_turn = (phase + throttling - instant.ticks() % throttling) % throttling;
+    ticks_until_turn < elapsed_ticks
+}
+
 impl LaneLike for Lane {
     fn add_car(
         &mut self,
@@ -247,12 +261,17 @@ impl Lane {
 
 impl Temporal for Lane {
     fn tick(&mut self, dt: f32, current_instant: Instant, world: &mut World) {
+        let elapsed_ticks = ticks_within(dt);
         let dt = dt / MICROTRAFFIC_UNREALISTIC_SLOWDOWN;
 
         // self.construction.progress += dt * 400.0;
 
-        let do_traffic = current_instant.ticks() % TRAFFIC_LOGIC_THROTTLING
-            == self.id.as_raw().instance_id as usize % TRAFFIC_LOGIC_THROTTLING;
+        let do_traffic = is_due(
+            self.id,
+            TRAFFIC_LOGIC_THROTTLING,
+            current_instant,
+            elapsed_ticks,
+        );
 
         let old_green = self.microtraffic.green;
         self.microtraffic.yellow_to_red = if self.microtraffic.timings.is_empty() {
@@ -283,9 +302,12 @@ impl Temporal for Lane {
             }
         }
 
-        if current_instant.ticks() % PATHFINDING_THROTTLING
-            == self.id.as_raw().instance_id as usize % PATHFINDING_THROTTLING
-        {
+        if is_due(
+            self.id,
+            PATHFINDING_THROTTLING,
+            current_instant,
+            elapsed_ticks,
+        ) {
             self.pathfinding_tick(world);
         }
 
@@ -445,10 +467,12 @@ impl Temporal for Lane {
         for interaction in self.connectivity.interactions.iter() {
             let cars = self.microtraffic.cars.iter();
 
-            if (current_instant.ticks() + 1) % TRAFFIC_LOGIC_THROTTLING
-                == interaction.direct_partner().as_raw().instance_id as usize
-                    % TRAFFIC_LOGIC_THROTTLING
-            {
+            if is_due(
+                interaction.direct_partner(),
+                TRAFFIC_LOGIC_THROTTLING,
+                current_instant + Ticks(1),
+                elapsed_ticks,
+            ) {
                 let maybe_obstacles = obstacles_for_interaction(
                     interaction,
                     cars,
@@ -530,12 +554,17 @@ impl LaneLike for SwitchLane {
 
 impl Temporal for SwitchLane {
     fn tick(&mut self, dt: f32, current_instant: Instant, world: &mut World) {
+        let elapsed_ticks = ticks_within(dt);
         let dt = dt / MICROTRAFFIC_UNREALISTIC_SLOWDOWN;
 
         // self.construction.progress += dt * 400.0;
 
-        let do_traffic = current_instant.ticks() % TRAFFIC_LOGIC_THROTTLING
-            == self.id.as_raw().instance_id as usize % TRAFFIC_LOGIC_THROTTLING;
+        let do_traffic = is_due(
+            self.id,
+            TRAFFIC_LOGIC_THROTTLING,
+            current_instant,
+            elapsed_ticks,
+        );
 
         if do_traffic {
             // TODO: optimize using BinaryHeap?
@@ -691,9 +720,12 @@ impl Temporal for SwitchLane {
                 }
             }
 
-            if (current_instant.ticks() + 1) % TRAFFIC_LOGIC_THROTTLING
-                == left.as_raw().instance_id as usize % TRAFFIC_LOGIC_THROTTLING
-            {
+            if is_due(
+                left,
+                TRAFFIC_LOGIC_THROTTLING,
+                current_instant + Ticks(1),
+                elapsed_ticks,
+            ) {
                 let obstacles = self
                     .microtraffic
                     .cars
@@ -712,9 +744,12 @@ impl Temporal for SwitchLane {
                 left_as_lane.add_obstacles(obstacles, self.id_as(), world);
             }
 
-            if (current_instant.ticks() + 1) % TRAFFIC_LOGIC_THROTTLING
-                == right.as_raw().instance_id as usize % TRAFFIC_LOGIC_THROTTLING
-            {
+            if is_due(
+                right,
+                TRAFFIC_LOGIC_THROTTLING,
+                current_instant + Ticks(1),
+                elapsed_ticks,
+            ) {
                 let obstacles = self
                     .microtraffic
                     .cars
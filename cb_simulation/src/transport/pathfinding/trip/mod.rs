@@ -2,6 +2,7 @@ use kay::{World, ActorSystem, Fate, TypedID, Actor};
 use compact::CVec;
 use ordered_float::OrderedFloat;
 use cb_time::units::Instant;
+use cb_time::actors::TimeID;
 
 use transport::lane::LaneID;
 use super::{PreciseLocation, RoughLocationID, LocationRequester, LocationRequesterID};
@@ -52,6 +53,8 @@ impl Trip {
         world: &mut World,
     ) -> Self {
         rough_source.resolve_as_location(id.into(), rough_source, instant, world);
+        // trips need to be simulated tick by tick, so time can't skip ahead while they last
+        TimeID::local_first(world).begin_activity(world);
 
         if let Some(listener) = listener {
             listener.trip_created(id, world);
@@ -101,6 +104,8 @@ impl Trip {
             );
         }
 
+        TimeID::local_first(world).end_activity(world);
+
         Fate::Die
     }
 }
@@ -174,7 +179,7 @@ impl LocationRequester for Trip {
     }
 }
 
-use cb_time::actors::{TimeID, Sleeper, SleeperID};
+use cb_time::actors::{Sleeper, SleeperID};
 use cb_time::units::Ticks;
 use super::super::microtraffic::{LaneLikeID, LaneCar, Obstacle};
 
@@ -119,10 +119,15 @@ impl SleeperID {
     pub fn wake(self, current_instant: Instant, world: &mut World) {
         world.send(self.as_raw(), MSG_Sleeper_wake(current_instant));
     }
+    
+    pub fn wake_scheduled(self, _handle: ScheduleHandle, current_instant: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_Sleeper_wake_scheduled(_handle, current_instant));
+    }
 
     pub fn register_trait(system: &mut ActorSystem) {
         system.register_trait::<SleeperRepresentative>();
         system.register_trait_message::<MSG_Sleeper_wake>();
+        system.register_trait_message::<MSG_Sleeper_wake_scheduled>();
     }
 
     pub fn register_implementor<Act: Actor + Sleeper>(system: &mut ActorSystem) {
@@ -132,11 +137,19 @@ impl SleeperID {
                 instance.wake(current_instant, world); Fate::Live
             }, false
         );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Sleeper_wake_scheduled(_handle, current_instant), instance, world| {
+                instance.wake_scheduled(_handle, current_instant, world); Fate::Live
+            }, false
+        );
     }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Sleeper_wake(pub Instant);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Sleeper_wake_scheduled(pub ScheduleHandle, pub Instant);
 
 impl Actor for Time {
     type ID = TimeID;
@@ -197,17 +210,47 @@ impl TimeID {
         world.send(self.as_raw(), MSG_Time_progress());
     }
     
+    pub fn begin_activity(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_Time_begin_activity());
+    }
+    
+    pub fn end_activity(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_Time_end_activity());
+    }
+    
     pub fn wake_up_in(self, remaining_ticks: Ticks, sleeper_id: SleeperID, world: &mut World) {
         world.send(self.as_raw(), MSG_Time_wake_up_in(remaining_ticks, sleeper_id));
     }
+    
+    pub fn wake_up_at(self, instant: Instant, sleeper_id: SleeperID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Time_wake_up_at(instant, sleeper_id));
+    }
+    
+    pub fn schedule(self, handle: ScheduleHandle, schedule: Schedule, sleeper_id: SleeperID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Time_schedule(handle, schedule, sleeper_id));
+    }
+    
+    pub fn cancel(self, handle: ScheduleHandle, world: &mut World) {
+        world.send(self.as_raw(), MSG_Time_cancel(handle));
+    }
 }
 
 #[derive(Copy, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Time_spawn(pub TimeID, );
 #[derive(Copy, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Time_progress();
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Time_begin_activity();
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Time_end_activity();
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Time_wake_up_in(pub Ticks, pub SleeperID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Time_wake_up_at(pub Instant, pub SleeperID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Time_schedule(pub ScheduleHandle, pub Schedule, pub SleeperID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Time_cancel(pub ScheduleHandle);
 
 
 #[allow(unused_variables)]
@@ -228,9 +271,39 @@ pub fn auto_setup(system: &mut ActorSystem) {
         }, false
     );
     
+    system.add_handler::<Time, _, _>(
+        |&MSG_Time_begin_activity(), instance, world| {
+            instance.begin_activity(world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Time, _, _>(
+        |&MSG_Time_end_activity(), instance, world| {
+            instance.end_activity(world); Fate::Live
+        }, false
+    );
+    
     system.add_handler::<Time, _, _>(
         |&MSG_Time_wake_up_in(remaining_ticks, sleeper_id), instance, world| {
             instance.wake_up_in(remaining_ticks, sleeper_id, world); Fate::Live
         }, false
     );
+    
+    system.add_handler::<Time, _, _>(
+        |&MSG_Time_wake_up_at(instant, sleeper_id), instance, world| {
+            instance.wake_up_at(instant, sleeper_id, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Time, _, _>(
+        |&MSG_Time_schedule(handle, schedule, sleeper_id), instance, world| {
+            instance.schedule(handle, schedule, sleeper_id, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Time, _, _>(
+        |&MSG_Time_cancel(handle), instance, world| {
+            instance.cancel(handle, world); Fate::Live
+        }, false
+    );
 }
\ No newline at end of file
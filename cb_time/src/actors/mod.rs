@@ -1,10 +1,12 @@
 use kay::{ActorSystem, World, TypedID};
-use compact::CVec;
 
 pub mod ui;
+pub mod timers;
+use self::timers::{TimerWheel, Timer};
+pub use self::timers::ScheduleHandle;
 
 pub use ::units::{Instant, Ticks, Duration, TICKS_PER_SIM_MINUTE, TICKS_PER_SIM_SECOND,
-TimeOfDay, TimeOfDayRange};
+TimeOfDay, TimeOfDayRange, Schedule};
 
 pub trait Temporal {
     fn tick(&mut self, dt: f32, current_instant: Instant, world: &mut World);
@@ -12,14 +14,32 @@ pub trait Temporal {
 
 pub trait Sleeper {
     fn wake(&mut self, current_instant: Instant, world: &mut World);
+
+    /// Called instead of `wake` for events scheduled with a handle
+    fn wake_scheduled(
+        &mut self,
+        _handle: ScheduleHandle,
+        current_instant: Instant,
+        world: &mut World,
+    ) {
+        self.wake(current_instant, world);
+    }
 }
 
 #[derive(Compact, Clone)]
 pub struct Time {
     id: TimeID,
     current_instant: Instant,
-    sleepers: CVec<(Instant, SleeperID)>,
+    timers: TimerWheel,
     speed: u16,
+    paused: bool,
+    /// Ticks still to be simulated one by one while paused
+    pending_steps: u32,
+    control_settings: ui::TimeControlSettings,
+    /// Whether to skip ahead to the next wake-up while nothing needs every tick
+    fast_forward: bool,
+    /// Things like trips that need to be simulated tick by tick while they last
+    ongoing_activities: u32,
 }
 
 impl Time {
@@ -27,43 +47,116 @@ impl Time {
         Time {
             id,
             current_instant: Instant::new(0),
-            sleepers: CVec::new(),
+            timers: TimerWheel::new(),
             speed: 1,
+            paused: false,
+            pending_steps: 0,
+            control_settings: ui::TimeControlSettings::default(),
+            fast_forward: false,
+            ongoing_activities: 0,
         }
     }
 
     pub fn progress(&mut self, world: &mut World) {
-        for _ in 0..self.speed {
+        let n_ticks = if self.paused {
+            let n_steps = self.pending_steps.min(u32::from(self.speed.max(1)));
+            self.pending_steps -= n_steps;
+            n_steps
+        } else {
+            u32::from(self.speed)
+        };
+
+        for tick_idx in 0..n_ticks {
+            let due_timers = self.timers.take_due(self.current_instant);
+            for timer in &due_timers {
+                if let Some((_, schedule)) = timer.scheduled {
+                    if let Some(next_due) = schedule.next_after(self.current_instant) {
+                        self.timers.insert(Timer {
+                            due: next_due.max(self.current_instant + Ticks(1)),
+                            ..*timer
+                        });
+                    }
+                }
+            }
+
+            // Woken sleepers might begin activities, which are only counted once their
+            // messages were handled after this call. So only skip ahead on the first tick
+            // of a call, and only if nobody is woken up right now.
+            let may_skip = self.fast_forward
+                && self.ongoing_activities == 0
+                && tick_idx == 0
+                && due_timers.is_empty();
+
+            let next_instant = if may_skip {
+                self.timers
+                    .next_due_after(self.current_instant)
+                    .map_or(self.current_instant + Ticks(1), |due| {
+                        due.max(self.current_instant + Ticks(1))
+                    })
+            } else {
+                self.current_instant + Ticks(1)
+            };
+            let elapsed_ticks = next_instant.ticks() - self.current_instant.ticks();
+
             TemporalID::global_broadcast(world).tick(
-                1.0 / (TICKS_PER_SIM_SECOND as f32),
+                elapsed_ticks as f32 / (TICKS_PER_SIM_SECOND as f32),
                 self.current_instant,
                 world,
             );
-            while self
-                .sleepers
-                .last()
-                .map(|&(end, _)| end < self.current_instant)
-                .unwrap_or(false)
-            {
-                let (_, sleeper) = self
-                    .sleepers
-                    .pop()
-                    .expect("just checked that there are sleepers");
-                sleeper.wake(self.current_instant, world);
+            for timer in due_timers {
+                if let Some((handle, _)) = timer.scheduled {
+                    timer
+                        .sleeper
+                        .wake_scheduled(handle, self.current_instant, world);
+                } else {
+                    timer.sleeper.wake(self.current_instant, world);
+                }
             }
-            self.current_instant += Ticks(1);
+            self.current_instant = next_instant;
         }
     }
 
-    pub fn wake_up_in(&mut self, remaining_ticks: Ticks, sleeper_id: SleeperID, _: &mut World) {
+    /// Prevents fast-forwarding until the matching `end_activity`
+    pub fn begin_activity(&mut self, _: &mut World) {
+        self.ongoing_activities += 1;
+    }
+
+    pub fn end_activity(&mut self, _: &mut World) {
+        self.ongoing_activities = self.ongoing_activities.saturating_sub(1);
+    }
+
+    pub fn wake_up_in(&mut self, remaining_ticks: Ticks, sleeper_id: SleeperID, world: &mut World) {
         let wake_up_at = self.current_instant + remaining_ticks;
-        let maybe_idx = self
-            .sleepers
-            .binary_search_by_key(&wake_up_at.iticks(), |&(t, _)| -(t.iticks()));
-        let insert_idx = match maybe_idx {
-            Ok(idx) | Err(idx) => idx,
-        };
-        self.sleepers.insert(insert_idx, (wake_up_at, sleeper_id));
+        self.wake_up_at(wake_up_at, sleeper_id, world);
+    }
+
+    pub fn wake_up_at(&mut self, instant: Instant, sleeper_id: SleeperID, _: &mut World) {
+        self.timers.insert(Timer {
+            due: instant.max(self.current_instant),
+            sleeper: sleeper_id,
+            scheduled: None,
+        });
+    }
+
+    /// Wakes up the sleeper according to `schedule` until cancelled with `handle`.
+    /// Scheduling again with the same handle replaces the previous schedule.
+    pub fn schedule(
+        &mut self,
+        handle: ScheduleHandle,
+        schedule: Schedule,
+        sleeper_id: SleeperID,
+        _: &mut World,
+    ) {
+        self.timers.cancel(handle);
+        self.timers.insert(Timer {
+            due: schedule.first(self.current_instant),
+            sleeper: sleeper_id,
+            scheduled: Some((handle, schedule)),
+        });
+    }
+
+    pub fn cancel(&mut self, handle: ScheduleHandle, _: &mut World) {
+        self.timers.cancel(handle);
     }
 }
 
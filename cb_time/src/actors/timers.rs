@@ -0,0 +1,217 @@
+use kay::{RawID, TypedID};
+use compact::{CVec, CHashMap};
+use units::{Instant, Ticks, Schedule};
+use super::SleeperID;
+
+/// How many ticks one revolution of the wheel covers. Timers further in the future
+/// than that just stay in their slot for more than one revolution.
+const WHEEL_SLOTS: usize = 4096;
+
+/// Identifies a scheduled event, so it can be cancelled or recognized when it is due.
+/// Consists of the scheduling actor and a number it counts up for each of its handles,
+/// which keeps handles unique and the simulation deterministic.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub struct ScheduleHandle {
+    owner: RawID,
+    idx: u32,
+}
+
+impl ScheduleHandle {
+    pub fn new<ID: TypedID>(owner: ID, idx: u32) -> ScheduleHandle {
+        ScheduleHandle {
+            owner: owner.as_raw(),
+            idx,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Timer {
+    pub due: Instant,
+    pub sleeper: SleeperID,
+    /// Only set for events scheduled with a handle
+    pub scheduled: Option<(ScheduleHandle, Schedule)>,
+}
+
+impl Timer {
+    fn handle(&self) -> Option<ScheduleHandle> {
+        self.scheduled.map(|(handle, _)| handle)
+    }
+}
+
+/// A hashed timer wheel: timers are put into the slot of their due tick modulo the
+/// number of slots, so inserting is cheap and each tick only looks at one slot
+#[derive(Compact, Clone)]
+pub struct TimerWheel {
+    slots: CVec<CVec<Timer>>,
+    due_by_handle: CHashMap<ScheduleHandle, Instant>,
+}
+
+impl TimerWheel {
+    pub fn new() -> TimerWheel {
+        TimerWheel {
+            slots: (0..WHEEL_SLOTS).map(|_| CVec::new()).collect(),
+            due_by_handle: CHashMap::new(),
+        }
+    }
+
+    fn slot_idx(instant: Instant) -> usize {
+        instant.ticks() % WHEEL_SLOTS
+    }
+
+    pub fn insert(&mut self, timer: Timer) {
+        if let Some(handle) = timer.handle() {
+            self.due_by_handle.insert(handle, timer.due);
+        }
+        self.slots[Self::slot_idx(timer.due)].push(timer);
+    }
+
+    pub fn cancel(&mut self, handle: ScheduleHandle) {
+        if let Some(due) = self.due_by_handle.remove(handle) {
+            self.slots[Self::slot_idx(due)].retain(|timer| timer.handle() != Some(handle));
+        }
+    }
+
+    /// Removes and returns all timers that are due at `instant`
+    pub fn take_due(&mut self, instant: Instant) -> Vec<Timer> {
+        let slot = &mut self.slots[Self::slot_idx(instant)];
+        if slot.is_empty() {
+            return Vec::new();
+        }
+
+        let (due, not_yet_due): (Vec<Timer>, Vec<Timer>) =
+            slot.iter().cloned().partition(|timer| timer.due <= instant);
+        *slot = not_yet_due.into();
+
+        for timer in &due {
+            if let Some(handle) = timer.handle() {
+                self.due_by_handle.remove(handle);
+            }
+        }
+
+        due
+    }
+
+    /// The earliest instant after `instant` at which any timer is due
+    pub fn next_due_after(&self, instant: Instant) -> Option<Instant> {
+        let mut earliest_later_revolution: Option<Instant> = None;
+
+        for offset in 1..(WHEEL_SLOTS + 1) {
+            let candidate = instant + Ticks(offset as u32);
+            for timer in self.slots[Self::slot_idx(candidate)].iter() {
+                if timer.due <= candidate {
+                    return Some(timer.due);
+                }
+                earliest_later_revolution = Some(
+                    earliest_later_revolution.map_or(timer.due, |earliest| earliest.min(timer.due)),
+                );
+            }
+        }
+
+        earliest_later_revolution
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kay::{ShortTypeId, MachineID};
+
+    fn sleeper(idx: u32) -> SleeperID {
+        SleeperID::from_raw(RawID::new(ShortTypeId::new(0), idx, MachineID(0), 0))
+    }
+
+    fn timer(due: usize, idx: u32) -> Timer {
+        Timer {
+            due: Instant::new(due),
+            sleeper: sleeper(idx),
+            scheduled: None,
+        }
+    }
+
+    fn dues(timers: Vec<Timer>) -> Vec<usize> {
+        let mut dues = timers
+            .into_iter()
+            .map(|timer| timer.due.ticks())
+            .collect::<Vec<_>>();
+        dues.sort();
+        dues
+    }
+
+    #[test]
+    fn timers_in_later_revolutions_stay_in_their_slot() {
+        let mut wheel = TimerWheel::new();
+        wheel.insert(timer(5, 0));
+        wheel.insert(timer(WHEEL_SLOTS + 5, 1));
+        wheel.insert(timer(2 * WHEEL_SLOTS + 5, 2));
+
+        assert_eq!(dues(wheel.take_due(Instant::new(5))), vec![5]);
+        assert!(wheel.take_due(Instant::new(5)).is_empty());
+        assert_eq!(
+            dues(wheel.take_due(Instant::new(WHEEL_SLOTS + 5))),
+            vec![WHEEL_SLOTS + 5]
+        );
+        assert_eq!(
+            dues(wheel.take_due(Instant::new(2 * WHEEL_SLOTS + 5))),
+            vec![2 * WHEEL_SLOTS + 5]
+        );
+    }
+
+    #[test]
+    fn overdue_timers_are_taken_on_the_next_revolution() {
+        let mut wheel = TimerWheel::new();
+        wheel.insert(timer(7, 0));
+
+        assert!(wheel.take_due(Instant::new(6)).is_empty());
+        assert_eq!(dues(wheel.take_due(Instant::new(WHEEL_SLOTS + 7))), vec![7]);
+    }
+
+    #[test]
+    fn cancelled_timers_are_never_due() {
+        let mut wheel = TimerWheel::new();
+        let handle = ScheduleHandle::new(sleeper(0), 0);
+        let other_handle = ScheduleHandle::new(sleeper(0), 1);
+        for &(handle, due) in &[(handle, 10), (other_handle, WHEEL_SLOTS + 10)] {
+            wheel.insert(Timer {
+                due: Instant::new(due),
+                sleeper: sleeper(0),
+                scheduled: Some((handle, Schedule::At(Instant::new(due)))),
+            });
+        }
+
+        wheel.cancel(handle);
+
+        assert!(wheel.take_due(Instant::new(10)).is_empty());
+        assert_eq!(
+            dues(wheel.take_due(Instant::new(WHEEL_SLOTS + 10))),
+            vec![WHEEL_SLOTS + 10]
+        );
+    }
+
+    #[test]
+    fn next_due_after_finds_the_earliest_timer() {
+        let mut wheel = TimerWheel::new();
+        assert_eq!(wheel.next_due_after(Instant::new(100)), None);
+
+        wheel.insert(timer(100 + WHEEL_SLOTS + 3, 0));
+        wheel.insert(timer(150, 1));
+        wheel.insert(timer(110, 2));
+
+        assert_eq!(
+            wheel.next_due_after(Instant::new(100)),
+            Some(Instant::new(110))
+        );
+    }
+
+    #[test]
+    fn next_due_after_looks_beyond_one_revolution() {
+        let mut wheel = TimerWheel::new();
+        wheel.insert(timer(100 + 2 * WHEEL_SLOTS + 1, 0));
+        wheel.insert(timer(100 + WHEEL_SLOTS + 3, 1));
+
+        assert_eq!(
+            wheel.next_due_after(Instant::new(100)),
+            Some(Instant::new(100 + WHEEL_SLOTS + 3))
+        );
+    }
+}
@@ -50,8 +50,8 @@ impl TypedID for TimeUIID {
 impl<Act: Actor + TimeUI> TraitIDFrom<Act> for TimeUIID {}
 
 impl TimeUIID {
-    pub fn on_time_info(self, current_instant: :: units :: Instant, speed: u16, world: &mut World) {
-        world.send(self.as_raw(), MSG_TimeUI_on_time_info(current_instant, speed));
+    pub fn on_time_info(self, current_instant: :: units :: Instant, speed: u16, paused: bool, fast_forward: bool, max_speed: u16, may_control: bool, key: ClientKey, world: &mut World) {
+        world.send(self.as_raw(), MSG_TimeUI_on_time_info(current_instant, speed, paused, fast_forward, max_speed, may_control, key));
     }
 
     pub fn register_trait(system: &mut ActorSystem) {
@@ -62,15 +62,15 @@ impl TimeUIID {
     pub fn register_implementor<Act: Actor + TimeUI>(system: &mut ActorSystem) {
         system.register_implementor::<Act, TimeUIRepresentative>();
         system.add_handler::<Act, _, _>(
-            |&MSG_TimeUI_on_time_info(current_instant, speed), instance, world| {
-                instance.on_time_info(current_instant, speed, world); Fate::Live
+            |&MSG_TimeUI_on_time_info(current_instant, speed, paused, fast_forward, max_speed, may_control, key), instance, world| {
+                instance.on_time_info(current_instant, speed, paused, fast_forward, max_speed, may_control, key, world); Fate::Live
             }, false
         );
     }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_TimeUI_on_time_info(pub :: units :: Instant, pub u16);
+struct MSG_TimeUI_on_time_info(pub :: units :: Instant, pub u16, pub bool, pub bool, pub u16, pub bool, pub ClientKey);
 
 
 
@@ -79,15 +79,45 @@ impl TimeID {
         world.send(self.as_raw(), MSG_Time_get_info(requester));
     }
     
-    pub fn set_speed(self, speed: u16, world: &mut World) {
-        world.send(self.as_raw(), MSG_Time_set_speed(speed));
+    pub fn configure_control(self, settings: TimeControlSettings, world: &mut World) {
+        world.send(self.as_raw(), MSG_Time_configure_control(settings));
+    }
+    
+    pub fn set_speed(self, speed: u16, requester: TimeUIID, key: ClientKey, world: &mut World) {
+        world.send(self.as_raw(), MSG_Time_set_speed(speed, requester, key));
+    }
+    
+    pub fn pause(self, requester: TimeUIID, key: ClientKey, world: &mut World) {
+        world.send(self.as_raw(), MSG_Time_pause(requester, key));
+    }
+    
+    pub fn resume(self, requester: TimeUIID, key: ClientKey, world: &mut World) {
+        world.send(self.as_raw(), MSG_Time_resume(requester, key));
+    }
+    
+    pub fn step(self, n_ticks: u32, requester: TimeUIID, key: ClientKey, world: &mut World) {
+        world.send(self.as_raw(), MSG_Time_step(n_ticks, requester, key));
+    }
+    
+    pub fn set_fast_forward(self, fast_forward: bool, requester: TimeUIID, key: ClientKey, world: &mut World) {
+        world.send(self.as_raw(), MSG_Time_set_fast_forward(fast_forward, requester, key));
     }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Time_get_info(pub TimeUIID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_Time_set_speed(pub u16);
+struct MSG_Time_configure_control(pub TimeControlSettings);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Time_set_speed(pub u16, pub TimeUIID, pub ClientKey);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Time_pause(pub TimeUIID, pub ClientKey);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Time_resume(pub TimeUIID, pub ClientKey);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Time_step(pub u32, pub TimeUIID, pub ClientKey);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Time_set_fast_forward(pub bool, pub TimeUIID, pub ClientKey);
 
 
 #[allow(unused_variables)]
@@ -102,8 +132,38 @@ pub fn auto_setup(system: &mut ActorSystem) {
     );
     
     system.add_handler::<Time, _, _>(
-        |&MSG_Time_set_speed(speed), instance, world| {
-            instance.set_speed(speed, world); Fate::Live
+        |&MSG_Time_configure_control(ref settings), instance, world| {
+            instance.configure_control(settings, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Time, _, _>(
+        |&MSG_Time_set_speed(speed, requester, key), instance, world| {
+            instance.set_speed(speed, requester, key, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Time, _, _>(
+        |&MSG_Time_pause(requester, key), instance, world| {
+            instance.pause(requester, key, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Time, _, _>(
+        |&MSG_Time_resume(requester, key), instance, world| {
+            instance.resume(requester, key, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Time, _, _>(
+        |&MSG_Time_step(n_ticks, requester, key), instance, world| {
+            instance.step(n_ticks, requester, key, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Time, _, _>(
+        |&MSG_Time_set_fast_forward(fast_forward, requester, key), instance, world| {
+            instance.set_fast_forward(fast_forward, requester, key, world); Fate::Live
         }, false
     );
 }
\ No newline at end of file
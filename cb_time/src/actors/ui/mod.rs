@@ -1,17 +1,113 @@
-use kay::World;
+use kay::{World, TypedID};
+use compact::CVec;
+use cb_util::client_keys::{ClientKey, client_of};
 use super::{Time, TimeID};
 
 pub trait TimeUI {
-    fn on_time_info(&mut self, current_instant: ::units::Instant, speed: u16, _world: &mut World);
+    fn on_time_info(
+        &mut self,
+        current_instant: ::units::Instant,
+        speed: u16,
+        paused: bool,
+        fast_forward: bool,
+        max_speed: u16,
+        may_control: bool,
+        key: ClientKey,
+        _world: &mut World,
+    );
+}
+
+/// Set by the server, limits how fast and by whom time can be changed
+#[derive(Compact, Clone)]
+pub struct TimeControlSettings {
+    pub max_speed: u16,
+    /// If set, any connected client may control time
+    pub allow_all: bool,
+    /// Machine ids of the clients that may control time
+    pub controllers: CVec<u8>,
+}
+
+impl Default for TimeControlSettings {
+    fn default() -> Self {
+        TimeControlSettings {
+            max_speed: 32,
+            allow_all: true,
+            controllers: CVec::new(),
+        }
+    }
 }
 
 impl Time {
+    /// Also hands out the key the requester's client has to send along with control requests
     pub fn get_info(&mut self, requester: TimeUIID, world: &mut World) {
-        requester.on_time_info(self.current_instant, self.speed, world);
+        let key = ClientKey::issue_for(client_of(requester));
+        requester.on_time_info(
+            self.current_instant,
+            self.speed,
+            self.paused,
+            self.fast_forward,
+            self.control_settings.max_speed,
+            self.may_control(requester, key),
+            key,
+            world,
+        );
+    }
+
+    /// Clients are identified by the machine their UI actor lives on,
+    /// which the key proves
+    fn may_control(&self, requester: TimeUIID, key: ClientKey) -> bool {
+        let client = client_of(requester);
+        self.control_settings.allow_all
+            || (key.is_valid_for(client) && self.control_settings.controllers.contains(&client))
+    }
+
+    pub fn configure_control(&mut self, settings: &TimeControlSettings, _world: &mut World) {
+        self.control_settings = settings.clone();
+        self.speed = self.speed.min(self.control_settings.max_speed);
+    }
+
+    pub fn set_speed(
+        &mut self,
+        speed: u16,
+        requester: TimeUIID,
+        key: ClientKey,
+        _world: &mut World,
+    ) {
+        if self.may_control(requester, key) {
+            self.speed = speed.max(1).min(self.control_settings.max_speed);
+        }
+    }
+
+    pub fn pause(&mut self, requester: TimeUIID, key: ClientKey, _world: &mut World) {
+        if self.may_control(requester, key) {
+            self.paused = true;
+        }
+    }
+
+    pub fn resume(&mut self, requester: TimeUIID, key: ClientKey, _world: &mut World) {
+        if self.may_control(requester, key) {
+            self.paused = false;
+            self.pending_steps = 0;
+        }
+    }
+
+    /// Simulates `n_ticks` more ticks while paused, at most `speed` of them per frame
+    pub fn step(&mut self, n_ticks: u32, requester: TimeUIID, key: ClientKey, _world: &mut World) {
+        if self.may_control(requester, key) && self.paused {
+            self.pending_steps += n_ticks;
+        }
     }
 
-    pub fn set_speed(&mut self, speed: u16, _world: &mut World) {
-        self.speed = speed as u16;
+    pub fn set_fast_forward(
+        &mut self,
+        fast_forward: bool,
+        requester: TimeUIID,
+        key: ClientKey,
+        _world: &mut World,
+    ) {
+        if self.may_control(requester, key) {
+            self.fast_forward = fast_forward;
+        }
     }
 }
 
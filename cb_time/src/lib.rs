@@ -3,6 +3,7 @@
 #![allow(clippy::new_without_default)]
 extern crate kay;
 extern crate compact;
+extern crate cb_util;
 #[macro_use]
 extern crate compact_macros;
 #[macro_use]
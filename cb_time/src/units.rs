@@ -72,6 +72,12 @@ impl Instant {
     pub fn iticks(self) -> isize {
         self.0 as isize
     }
+
+    /// Number of midnights that have passed since the simulation started
+    pub fn days_since_start(self) -> usize {
+        (BEGINNING_TIME_OF_DAY * 60 + self.ticks() / TICKS_PER_SIM_MINUTE as usize)
+            / MINUTES_PER_DAY
+    }
 }
 
 impl<D: Into<Ticks>> ::std::ops::Add<D> for Instant {
@@ -242,4 +248,477 @@ impl TimeOfDayRange {
             time > self.start || time < self.end
         }
     }
+
+    pub fn wraps_around_midnight(self) -> bool {
+        self.start > self.end
+    }
+
+    /// Whether the time lies in the part of the range before midnight
+    pub fn contains_before_midnight<T: Into<TimeOfDay>>(self, time: T) -> bool {
+        let time = time.into();
+        if self.wraps_around_midnight() {
+            self.start <= time
+        } else {
+            self.start <= time && time <= self.end
+        }
+    }
+
+    /// Whether the time lies in the part of a wrapping range after midnight
+    pub fn contains_after_midnight<T: Into<TimeOfDay>>(self, time: T) -> bool {
+        self.wraps_around_midnight() && time.into() <= self.end
+    }
+}
+
+pub const DAYS_PER_SEASON: usize = 91;
+pub const DAYS_PER_YEAR: usize = 4 * DAYS_PER_SEASON;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+/// A day within the year, the simulation starts on the first day of spring
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
+pub struct DayOfYear(pub u16);
+
+impl DayOfYear {
+    pub fn season(self) -> Season {
+        match self.0 as usize / DAYS_PER_SEASON {
+            0 => Season::Spring,
+            1 => Season::Summer,
+            2 => Season::Autumn,
+            _ => Season::Winter,
+        }
+    }
+
+    pub fn day_of_season(self) -> usize {
+        self.0 as usize % DAYS_PER_SEASON
+    }
+
+    /// Progress through the year, from 0.0 on its first day up to (excluding) 1.0
+    pub fn fraction_of_year(self) -> f32 {
+        f32::from(self.0) / DAYS_PER_YEAR as f32
+    }
+
+    /// Whether the day lies in `[start, end)`, which might wrap around the end of the year
+    pub fn is_within(self, start: DayOfYear, end: DayOfYear) -> bool {
+        if start <= end {
+            start <= self && self < end
+        } else {
+            start <= self || self < end
+        }
+    }
+
+    /// Number of days in `[start, end)`, which might wrap around the end of the year
+    pub fn days_between(start: DayOfYear, end: DayOfYear) -> usize {
+        (end.0 as usize + DAYS_PER_YEAR - start.0 as usize) % DAYS_PER_YEAR
+    }
+}
+
+impl From<Instant> for DayOfYear {
+    fn from(instant: Instant) -> DayOfYear {
+        DayOfYear((instant.days_since_start() % DAYS_PER_YEAR) as u16)
+    }
+}
+
+/// The simulation starts on a Monday, and since a year has exactly 52 weeks,
+/// so does every year
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Monday,
+    Weekday::Tuesday,
+    Weekday::Wednesday,
+    Weekday::Thursday,
+    Weekday::Friday,
+    Weekday::Saturday,
+    Weekday::Sunday,
+];
+
+impl Weekday {
+    pub fn as_idx(self) -> usize {
+        self as usize
+    }
+
+    pub fn is_weekend(self) -> bool {
+        self == Weekday::Saturday || self == Weekday::Sunday
+    }
+
+    pub fn previous(self) -> Weekday {
+        WEEKDAYS[(self.as_idx() + 6) % 7]
+    }
+}
+
+impl From<Instant> for Weekday {
+    fn from(instant: Instant) -> Weekday {
+        WEEKDAYS[instant.days_since_start() % 7]
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Month {
+    January,
+    February,
+    March,
+    April,
+    May,
+    June,
+    July,
+    August,
+    September,
+    October,
+    November,
+    December,
+}
+
+/// The months of each season, in order, starting with spring
+const MONTHS_BY_SEASON: [[Month; 3]; 4] = [
+    [Month::March, Month::April, Month::May],
+    [Month::June, Month::July, Month::August],
+    [Month::September, Month::October, Month::November],
+    [Month::December, Month::January, Month::February],
+];
+/// The first two months of a season have this many days, the last one gets the rest
+const DAYS_PER_MONTH: usize = 30;
+
+/// A calendar date. The calendar is simplified, every season has 91 days
+/// split into months of 30, 30 and 31 days.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct Date {
+    /// Starting with year 1, which begins on the first day of the simulation, in March
+    pub year: u32,
+    pub month: Month,
+    /// Starting with 1
+    pub day_of_month: u8,
+    pub weekday: Weekday,
+}
+
+impl From<Instant> for Date {
+    fn from(instant: Instant) -> Date {
+        let day_of_year = DayOfYear::from(instant);
+        let season_idx = day_of_year.season() as usize;
+        let month_idx = (day_of_year.day_of_season() / DAYS_PER_MONTH).min(2);
+        let month = MONTHS_BY_SEASON[season_idx][month_idx];
+        let years_passed = instant.days_since_start() / DAYS_PER_YEAR;
+        let is_next_calendar_year = month == Month::January || month == Month::February;
+
+        Date {
+            year: (years_passed + if is_next_calendar_year { 2 } else { 1 }) as u32,
+            month,
+            day_of_month: (day_of_year.day_of_season() - month_idx * DAYS_PER_MONTH + 1) as u8,
+            weekday: Weekday::from(instant),
+        }
+    }
+}
+
+impl ::std::fmt::Display for Date {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(
+            f,
+            "{:?}, {} {:?}, Year {}",
+            self.weekday, self.day_of_month, self.month, self.year
+        )
+    }
+}
+
+/// Opening hours which can differ per weekday, `None` meaning closed on that day.
+/// Ranges that wrap around midnight count as belonging to the day they start on.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct OpeningHours {
+    pub per_weekday: [Option<TimeOfDayRange>; 7],
+}
+
+impl OpeningHours {
+    pub fn every_day(range: TimeOfDayRange) -> OpeningHours {
+        OpeningHours {
+            per_weekday: [Some(range); 7],
+        }
+    }
+
+    pub fn closed_on(mut self, weekday: Weekday) -> OpeningHours {
+        self.per_weekday[weekday.as_idx()] = None;
+        self
+    }
+
+    pub fn with_hours_on(mut self, weekday: Weekday, range: TimeOfDayRange) -> OpeningHours {
+        self.per_weekday[weekday.as_idx()] = Some(range);
+        self
+    }
+
+    pub fn on(&self, weekday: Weekday) -> Option<TimeOfDayRange> {
+        self.per_weekday[weekday.as_idx()]
+    }
+
+    /// Whether the instant lies within the range of its weekday, or within the part
+    /// after midnight of the previous weekday's range
+    pub fn contains(&self, instant: Instant) -> bool {
+        let weekday = Weekday::from(instant);
+        let time = TimeOfDay::from(instant);
+
+        self.on(weekday)
+            .map(|range| range.contains_before_midnight(time))
+            .unwrap_or(false)
+            || self
+                .on(weekday.previous())
+                .map(|range| range.contains_after_midnight(time))
+                .unwrap_or(false)
+    }
+
+    /// Whether any range that is open on the day of the instant closes after it,
+    /// including the part after midnight of the previous weekday's range
+    pub fn end_after_on_same_day(&self, instant: Instant) -> bool {
+        let weekday = Weekday::from(instant);
+        let time = TimeOfDay::from(instant);
+
+        self.on(weekday)
+            .map(|range| range.wraps_around_midnight() || time < range.end)
+            .unwrap_or(false)
+            || self
+                .on(weekday.previous())
+                .map(|range| range.wraps_around_midnight() && time < range.end)
+                .unwrap_or(false)
+    }
+
+    pub fn earlier_by(self, delta: Duration) -> OpeningHours {
+        let mut per_weekday = self.per_weekday;
+        for range in per_weekday.iter_mut() {
+            *range = range.map(|range| range.earlier_by(delta));
+        }
+        OpeningHours { per_weekday }
+    }
+}
+
+impl From<TimeOfDayRange> for OpeningHours {
+    fn from(range: TimeOfDayRange) -> OpeningHours {
+        OpeningHours::every_day(range)
+    }
+}
+
+/// When a scheduled event is due, either once or repeatedly
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum Schedule {
+    /// Once, at the given instant or as soon as possible if it already passed
+    At(Instant),
+    /// Every day at the given time of day
+    Daily(TimeOfDay),
+    /// Every week on the given weekday, at the given time of day
+    Weekly(Weekday, TimeOfDay),
+    /// Repeatedly, with the given interval between occurrences
+    Every(Duration),
+}
+
+impl Schedule {
+    /// When the schedule is first due, if it is set up at `now`
+    pub fn first(self, now: Instant) -> Instant {
+        match self {
+            Schedule::At(instant) => instant.max(now),
+            _ => self
+                .next_after(now)
+                .expect("Recurring schedules always have a next occurrence"),
+        }
+    }
+
+    /// When the schedule is next due after it was due at `instant`, if ever
+    pub fn next_after(self, instant: Instant) -> Option<Instant> {
+        match self {
+            Schedule::At(_) => None,
+            Schedule::Daily(time_of_day) => Some(next_time_of_day(instant, time_of_day, None)),
+            Schedule::Weekly(weekday, time_of_day) => {
+                Some(next_time_of_day(instant, time_of_day, Some(weekday)))
+            }
+            Schedule::Every(interval) => Some(instant + interval),
+        }
+    }
+}
+
+/// The first instant after `instant` with the given time of day (and weekday)
+fn next_time_of_day(instant: Instant, time_of_day: TimeOfDay, weekday: Option<Weekday>) -> Instant {
+    let (hours, minutes) = time_of_day.hours_minutes();
+    let mut day = instant.days_since_start();
+
+    loop {
+        let minutes_since_start = (day * MINUTES_PER_DAY + hours * 60 + minutes) as isize
+            - (BEGINNING_TIME_OF_DAY * 60) as isize;
+        let is_right_weekday = weekday
+            .map(|weekday| WEEKDAYS[day % 7] == weekday)
+            .unwrap_or(true);
+
+        if minutes_since_start >= 0 && is_right_weekday {
+            let candidate =
+                Instant::new(minutes_since_start as usize * TICKS_PER_SIM_MINUTE as usize);
+            if candidate > instant {
+                return candidate;
+            }
+        }
+
+        day += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The instant at the given time of day on the given day since the start
+    fn at(day: usize, hours: usize, minutes: usize) -> Instant {
+        let minutes_since_start =
+            day * MINUTES_PER_DAY + hours * 60 + minutes - BEGINNING_TIME_OF_DAY * 60;
+        Instant::new(minutes_since_start * TICKS_PER_SIM_MINUTE as usize)
+    }
+
+    #[test]
+    fn days_start_at_midnight() {
+        assert_eq!(DayOfYear::from(at(0, 7, 0)), DayOfYear(0));
+        assert_eq!(DayOfYear::from(at(0, 23, 59)), DayOfYear(0));
+        assert_eq!(DayOfYear::from(at(1, 0, 0)), DayOfYear(1));
+        assert_eq!(DayOfYear::from(at(DAYS_PER_YEAR, 0, 0)), DayOfYear(0));
+    }
+
+    #[test]
+    fn seasons_follow_each_other_starting_with_spring() {
+        assert_eq!(DayOfYear(0).season(), Season::Spring);
+        assert_eq!(DayOfYear(90).season(), Season::Spring);
+        assert_eq!(DayOfYear(91).season(), Season::Summer);
+        assert_eq!(DayOfYear(91).day_of_season(), 0);
+        assert_eq!(DayOfYear(182).season(), Season::Autumn);
+        assert_eq!(DayOfYear(363).season(), Season::Winter);
+        assert_eq!(DayOfYear(363).day_of_season(), 90);
+    }
+
+    #[test]
+    fn day_ranges_can_wrap_around_the_end_of_the_year() {
+        let (start, end) = (DayOfYear(350), DayOfYear(10));
+        assert!(DayOfYear(360).is_within(start, end));
+        assert!(DayOfYear(5).is_within(start, end));
+        assert!(!DayOfYear(10).is_within(start, end));
+        assert!(!DayOfYear(100).is_within(start, end));
+        assert!(DayOfYear(100).is_within(end, start));
+        assert_eq!(DayOfYear::days_between(start, end), 24);
+        assert_eq!(DayOfYear::days_between(end, start), 340);
+    }
+
+    #[test]
+    fn dates_use_months_of_30_30_and_31_days_per_season() {
+        let date = |day| {
+            let date = Date::from(at(day, 12, 0));
+            (date.year, date.month, date.day_of_month)
+        };
+
+        assert_eq!(date(0), (1, Month::March, 1));
+        assert_eq!(date(29), (1, Month::March, 30));
+        assert_eq!(date(30), (1, Month::April, 1));
+        assert_eq!(date(60), (1, Month::May, 1));
+        assert_eq!(date(90), (1, Month::May, 31));
+        assert_eq!(date(91), (1, Month::June, 1));
+        assert_eq!(date(273), (1, Month::December, 1));
+        assert_eq!(date(303), (2, Month::January, 1));
+        assert_eq!(date(363), (2, Month::February, 31));
+        assert_eq!(date(DAYS_PER_YEAR), (2, Month::March, 1));
+    }
+
+    #[test]
+    fn weekdays_repeat_every_seven_days_starting_on_monday() {
+        assert_eq!(Weekday::from(at(0, 7, 0)), Weekday::Monday);
+        assert_eq!(Weekday::from(at(5, 0, 0)), Weekday::Saturday);
+        assert_eq!(Weekday::from(at(6, 23, 59)), Weekday::Sunday);
+        assert_eq!(Weekday::from(at(7, 0, 0)), Weekday::Monday);
+        assert_eq!(Weekday::from(at(DAYS_PER_YEAR, 0, 0)), Weekday::Monday);
+        assert!(Weekday::Sunday.is_weekend());
+        assert!(!Weekday::Friday.is_weekend());
+        assert_eq!(Weekday::Monday.previous(), Weekday::Sunday);
+    }
+
+    #[test]
+    fn times_of_day_wrap_around_midnight() {
+        let one_hour = Duration::from_hours(1);
+        assert_eq!(
+            TimeOfDay::new(0, 30).earlier_by(one_hour),
+            TimeOfDay::new(23, 30)
+        );
+        assert_eq!(
+            TimeOfDay::new(23, 30).later_by(one_hour),
+            TimeOfDay::new(0, 30)
+        );
+        assert_eq!(TimeOfDay::from(at(3, 0, 15)), TimeOfDay::new(0, 15));
+    }
+
+    #[test]
+    fn opening_hours_differ_per_weekday() {
+        let hours = OpeningHours::every_day(TimeOfDayRange::new(9, 0, 17, 0))
+            .closed_on(Weekday::Sunday)
+            .with_hours_on(Weekday::Saturday, TimeOfDayRange::new(10, 0, 14, 0));
+
+        assert!(hours.contains(at(0, 9, 30)));
+        assert!(!hours.contains(at(0, 8, 30)));
+        assert!(!hours.contains(at(0, 17, 30)));
+        assert!(hours.contains(at(5, 13, 0)));
+        assert!(!hours.contains(at(5, 15, 0)));
+        assert!(!hours.contains(at(6, 12, 0)));
+
+        assert!(hours.end_after_on_same_day(at(0, 7, 0)));
+        assert!(hours.end_after_on_same_day(at(0, 16, 0)));
+        assert!(!hours.end_after_on_same_day(at(0, 18, 0)));
+        assert!(!hours.end_after_on_same_day(at(6, 12, 0)));
+    }
+
+    #[test]
+    fn opening_hours_past_midnight_belong_to_the_day_they_start_on() {
+        let hours =
+            OpeningHours::every_day(TimeOfDayRange::new(22, 0, 2, 0)).closed_on(Weekday::Tuesday);
+
+        assert!(hours.contains(at(0, 23, 0)));
+        assert!(hours.contains(at(1, 1, 0)));
+        assert!(!hours.contains(at(1, 23, 0)));
+        assert!(!hours.contains(at(2, 1, 0)));
+        assert!(hours.contains(at(2, 23, 0)));
+
+        assert!(hours.end_after_on_same_day(at(1, 1, 0)));
+        assert!(!hours.end_after_on_same_day(at(1, 3, 0)));
+        assert!(hours.end_after_on_same_day(at(2, 12, 0)));
+    }
+
+    #[test]
+    fn daily_schedules_are_due_strictly_after_the_given_instant() {
+        let nine = Schedule::Daily(TimeOfDay::new(9, 0));
+        assert_eq!(nine.next_after(at(0, 8, 0)), Some(at(0, 9, 0)));
+        assert_eq!(nine.next_after(at(0, 9, 0)), Some(at(1, 9, 0)));
+        assert_eq!(nine.first(at(0, 9, 0)), at(1, 9, 0));
+    }
+
+    #[test]
+    fn daily_schedules_skip_times_before_the_simulation_started() {
+        let three = Schedule::Daily(TimeOfDay::new(3, 0));
+        assert_eq!(three.first(Instant::new(0)), at(1, 3, 0));
+    }
+
+    #[test]
+    fn weekly_schedules_wait_for_their_weekday() {
+        let wednesday = Schedule::Weekly(Weekday::Wednesday, TimeOfDay::new(9, 0));
+        assert_eq!(wednesday.first(at(0, 10, 0)), at(2, 9, 0));
+        assert_eq!(wednesday.next_after(at(2, 9, 0)), Some(at(9, 9, 0)));
+        assert_eq!(wednesday.next_after(at(2, 8, 59)), Some(at(2, 9, 0)));
+    }
+
+    #[test]
+    fn one_off_schedules_are_due_once_and_intervals_repeat() {
+        let past = Schedule::At(at(0, 8, 0));
+        assert_eq!(past.first(at(0, 10, 0)), at(0, 10, 0));
+        assert_eq!(past.next_after(at(0, 10, 0)), None);
+
+        let every = Schedule::Every(Duration::from_minutes(5));
+        assert_eq!(every.first(at(0, 10, 0)), at(0, 10, 5));
+        assert_eq!(every.next_after(at(0, 10, 5)), Some(at(0, 10, 10)));
+    }
 }
@@ -0,0 +1,38 @@
+//! Requests name the actor they come from themselves, so an id alone doesn't prove which
+//! client sent a request. Instead, the server derives a secret key for each client and
+//! only ever sends it to actors of that client, which are always on the client's machine.
+//! A request that carries the key of the machine its requester id belongs to really
+//! comes from that machine.
+
+use kay::TypedID;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash, Hasher};
+
+/// Identifies the client (machine) a request came from
+pub type ClientID = u8;
+
+pub fn client_of<ID: TypedID>(id: ID) -> ClientID {
+    id.as_raw().machine.0
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug, Serialize, Deserialize)]
+pub struct ClientKey(pub u64);
+
+lazy_static! {
+    /// Randomly keyed on each start of the server, so keys can't be derived elsewhere.
+    /// Shared by all threads, so a key issued on one of them is valid on all others.
+    static ref KEY_DERIVATION: RandomState = RandomState::new();
+}
+
+impl ClientKey {
+    /// Only meaningful on the server. Must only be sent to actors of `client`.
+    pub fn issue_for(client: ClientID) -> ClientKey {
+        let mut hasher = KEY_DERIVATION.build_hasher();
+        client.hash(&mut hasher);
+        ClientKey(hasher.finish())
+    }
+
+    pub fn is_valid_for(self, client: ClientID) -> bool {
+        self == ClientKey::issue_for(client)
+    }
+}
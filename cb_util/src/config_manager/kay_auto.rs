@@ -160,6 +160,10 @@ impl<C: Config> ConfigManagerID<C> {
     pub fn update_entry(self, name: Name, maybe_value: COption < C >, world: &mut World) {
         world.send(self.as_raw(), MSG_ConfigManager_update_entry::<C>(name, maybe_value));
     }
+    
+    pub fn update_own_entry(self, name: Name, maybe_value: COption < C >, requester: ConfigUserID < C >, key: ClientKey, world: &mut World) {
+        world.send(self.as_raw(), MSG_ConfigManager_update_own_entry::<C>(name, maybe_value, requester, key));
+    }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
@@ -168,6 +172,8 @@ struct MSG_ConfigManager_spawn<C: Config>(pub ConfigManagerID<C>, pub CHashMap <
 struct MSG_ConfigManager_request_current<C: Config>(pub ConfigUserID < C >);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_ConfigManager_update_entry<C: Config>(pub Name, pub COption < C >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ConfigManager_update_own_entry<C: Config>(pub Name, pub COption < C >, pub ConfigUserID < C >, pub ClientKey);
 
 
 #[allow(unused_variables)]
@@ -192,4 +198,10 @@ pub fn auto_setup<C: Config>(system: &mut ActorSystem) {
             instance.update_entry(name, maybe_value, world); Fate::Live
         }, false
     );
+    
+    system.add_handler::<ConfigManager<C>, _, _>(
+        |&MSG_ConfigManager_update_own_entry::<C>(name, ref maybe_value, requester, key), instance, world| {
+            instance.update_own_entry(name, maybe_value, requester, key, world); Fate::Live
+        }, false
+    );
 }
\ No newline at end of file
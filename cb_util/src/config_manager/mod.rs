@@ -1,6 +1,9 @@
 use kay::{World, Actor, TypedID};
 use compact::{CHashMap, Compact, COption};
 use arrayvec::ArrayString;
+use client_keys::{ClientID, ClientKey, client_of};
+use log::info;
+const LOG_T: &str = "Config";
 
 pub type Name = ArrayString<[u8; 16]>;
 pub trait Config: Compact + 'static {}
@@ -9,6 +12,9 @@ pub trait Config: Compact + 'static {}
 pub struct ConfigManager<C: Config> {
     id: ConfigManagerID<C>,
     entries: CHashMap<Name, C>,
+    /// Which client added an entry through `update_own_entry`. Other entries,
+    /// like the initial ones, are shared and can't be changed by clients
+    owners: CHashMap<Name, ClientID>,
 }
 
 impl<C: Config> ConfigManager<C> {
@@ -20,6 +26,7 @@ impl<C: Config> ConfigManager<C> {
         ConfigManager {
             id,
             entries: initial_entries.clone(),
+            owners: CHashMap::new(),
         }
     }
 
@@ -42,6 +49,56 @@ impl<C: Config> ConfigManager<C> {
             self.entries.remove(name);
         }
     }
+
+    /// Lets clients add entries and change or remove the ones they added themselves
+    pub fn update_own_entry(
+        &mut self,
+        name: Name,
+        maybe_value: &COption<C>,
+        requester: ConfigUserID<C>,
+        key: ClientKey,
+        world: &mut World,
+    ) {
+        let client = client_of(requester);
+
+        if !key.is_valid_for(client) {
+            info(
+                LOG_T,
+                format!(
+                    "Rejected config change with an invalid key for client {}",
+                    client
+                ),
+                self.id,
+                world,
+            );
+            return;
+        }
+
+        let may_update = match self.owners.get(name) {
+            Some(owner) => *owner == client,
+            None => !self.entries.contains_key(name),
+        };
+
+        if !may_update {
+            info(
+                LOG_T,
+                format!(
+                    "Client {} tried to change '{}', which it doesn't own",
+                    client, name
+                ),
+                self.id,
+                world,
+            );
+            return;
+        }
+
+        if let COption(Some(_)) = *maybe_value {
+            self.owners.insert(name, client);
+        } else {
+            self.owners.remove(name);
+        }
+        self.update_entry(name, maybe_value, world);
+    }
 }
 
 pub trait ConfigUser<C: Config>: Actor {
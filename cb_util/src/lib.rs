@@ -5,6 +5,8 @@ extern crate fnv;
 extern crate uuid;
 extern crate arrayvec;
 extern crate kay;
+#[macro_use]
+extern crate lazy_static;
 
 pub extern crate compact;
 #[macro_use]
@@ -16,3 +18,4 @@ pub mod async_counter;
 pub mod random;
 pub mod config_manager;
 pub mod log;
+pub mod client_keys;